@@ -1,12 +1,17 @@
 use crate::notifications::{NotificationManager, NotificationSettings};
 use crate::tray;
 use openminedash_core::{
-    AppState, CoinDefinition, CrashRecoveryState, MiningConfig, MiningStatus, Profile,
-    SessionConfig, SessionDetails, SessionManager, SessionSummary, LogsResponse,
-    create_diagnostics_export, Alert, AlertSeverity, AlertStore, BudgetStatus,
-    ThreadBudgetSettings, calculate_budget, BudgetMode, BudgetPreset,
+    AppState, CoinDefinition, ConfigStore, CrashRecoveryState, MiningConfig, MiningMode,
+    MiningStatus, Profile, SessionConfig, SessionConnection, SessionDetails, SessionManager,
+    SessionSummary, LogsResponse, create_diagnostics_export, create_diagnostics_bundle, Alert,
+    AlertQuery, AlertSeverity, AlertStore, BudgetStatus, ThreadBudgetSettings, calculate_budget, BudgetMode,
+    BudgetPreset, AutoMinerHandle, AutoMiningSettings, AutoMiningStatus, WorkerManager,
+    WorkerSnapshot, ScrubCommand, ScrubHandle, ScrubStatus,
+};
+use openminedash_pools::{
+    reconcile_balance, PoolBalance, PoolHealthDetail, PoolHealthFull, PoolHealthResult,
+    ReconciliationResult, StratumStats,
 };
-use openminedash_pools::PoolHealthResult;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::{Manager, State};
@@ -16,6 +21,7 @@ type AppStateHandle = Arc<Mutex<AppState>>;
 type NotificationHandle = Arc<Mutex<NotificationManager>>;
 type SessionManagerHandle = Arc<Mutex<SessionManager>>;
 type AlertStoreHandle = Arc<Mutex<AlertStore>>;
+type ConfigStoreHandle = ConfigStore;
 
 #[tauri::command]
 pub async fn get_consent(state: State<'_, AppStateHandle>) -> Result<bool, String> {
@@ -63,6 +69,15 @@ pub async fn list_coins(state: State<'_, AppStateHandle>) -> Result<Vec<CoinDefi
     state.list_coins().map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn validate_mining_config(
+    state: State<'_, AppStateHandle>,
+    config: MiningConfig,
+) -> Result<(), String> {
+    let state = state.lock().await;
+    state.validate_mining_config(&config).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn start_mining(
     state: State<'_, AppStateHandle>,
@@ -74,12 +89,13 @@ pub async fn start_mining(
         return Err("Mining consent not granted".to_string());
     }
     
+    let mode = state.mining_mode();
     state.start_mining(config, app_handle.clone()).await.map_err(|e| e.to_string())?;
-    
+
     // Update tray
     let status = state.status();
-    tray::update_tray(&app_handle, true, status.hashrate, status.accepted_shares, status.uptime, "balanced");
-    
+    tray::update_tray(&app_handle, true, status.hashrate, status.accepted_shares, status.uptime, mode.tray_label());
+
     Ok(())
 }
 
@@ -90,10 +106,11 @@ pub async fn stop_mining(
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let mut state = state.lock().await;
+    let mode = state.mining_mode();
     state.stop_mining().await.map_err(|e| e.to_string())?;
-    
+
     // Update tray
-    tray::update_tray(&app_handle, false, 0.0, 0, 0, "balanced");
+    tray::update_tray(&app_handle, false, 0.0, 0, 0, mode.tray_label());
     
     // Notify
     let notif = notifications.lock().await;
@@ -108,9 +125,9 @@ pub async fn get_status(
     app_handle: tauri::AppHandle,
 ) -> Result<MiningStatus, String> {
     let mut state = state.lock().await;
-    let _ = state.refresh_stats().await;
+    let _ = state.refresh_stats(&app_handle).await;
     let status = state.status().clone();
-    
+
     // Update tray with latest stats
     if status.is_running {
         tray::update_tray(
@@ -119,13 +136,39 @@ pub async fn get_status(
             status.hashrate,
             status.accepted_shares,
             status.uptime,
-            "balanced",
+            state.mining_mode().tray_label(),
         );
     }
     
     Ok(status)
 }
 
+/// Connect directly to a pool over Stratum, bypassing the external miner
+/// binary entirely.
+#[tauri::command]
+pub async fn stratum_connect(
+    state: State<'_, AppStateHandle>,
+    pool: String,
+    wallet: String,
+    worker: String,
+) -> Result<(), String> {
+    let mut state = state.lock().await;
+    state.connect_stratum(&pool, &wallet, &worker).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn stratum_disconnect(state: State<'_, AppStateHandle>) -> Result<(), String> {
+    let mut state = state.lock().await;
+    state.disconnect_stratum();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stratum_stats(state: State<'_, AppStateHandle>) -> Result<Option<StratumStats>, String> {
+    let state = state.lock().await;
+    Ok(state.stratum_stats())
+}
+
 #[tauri::command]
 pub async fn save_profile(state: State<'_, AppStateHandle>, profile: Profile) -> Result<(), String> {
     let mut state = state.lock().await;
@@ -144,18 +187,110 @@ pub async fn check_pool_health(url: String) -> Result<PoolHealthResult, String>
     openminedash_pools::check_health(&url).await.map_err(|e| e.to_string())
 }
 
+/// Repeated-probe variant of `check_pool_health` - runs `samples` sequential
+/// probes and returns a latency histogram alongside the last probe's
+/// result, so the UI can flag a pool that's stable on average but spikes
+/// occasionally. `p99_ceiling_ms` defaults to 500ms (the same threshold
+/// `check_health` uses for a single-probe `Degraded` verdict) when `None`.
+#[tauri::command]
+pub async fn check_pool_health_detailed(
+    url: String,
+    samples: u32,
+    p99_ceiling_ms: Option<u64>,
+) -> Result<PoolHealthDetail, String> {
+    openminedash_pools::check_health_detailed(&url, samples, p99_ceiling_ms.unwrap_or(500))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Opt-in session-validation variant of `check_pool_health` - actually
+/// authorizes `wallet`/`worker` against the pool and waits for a job
+/// instead of stopping at "did we get any bytes back", so it catches a
+/// rejected wallet or a dead job feed that the cheaper checks report as
+/// healthy. `job_timeout_secs` defaults to 15s when `None`.
+#[tauri::command]
+pub async fn check_pool_health_full(
+    url: String,
+    wallet: String,
+    worker: String,
+    job_timeout_secs: Option<u64>,
+) -> Result<PoolHealthFull, String> {
+    openminedash_pools::check_health_full(&url, &wallet, &worker, job_timeout_secs.unwrap_or(15))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fetch_pool_balance(pool_host: String, wallet: String) -> Result<PoolBalance, String> {
+    let templates = openminedash_core::AppConfig::load()
+        .map_err(|e| e.to_string())?
+        .pool_templates;
+    openminedash_pools::fetch_pool_balance(&pool_host, &wallet, &templates).await
+}
+
+#[tauri::command]
+pub async fn reconcile_wallet_balance(
+    pool_balance: PoolBalance,
+) -> Result<ReconciliationResult, String> {
+    let config = openminedash_core::AppConfig::load()
+        .map_err(|e| e.to_string())?
+        .wallet_rpc;
+    let wallet_balance = openminedash_pools::fetch_wallet_balance(&config).await?;
+    Ok(reconcile_balance(&pool_balance, &wallet_balance))
+}
+
 #[tauri::command]
 pub async fn export_diagnostics(
     state: State<'_, AppStateHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
     mask_wallets: bool,
 ) -> Result<String, String> {
     let _state = state.lock().await;
-    let config = openminedash_core::AppConfig::load().unwrap_or_default();
+    let config = config_store.get().await;
     let logs = Vec::new();
     let export = create_diagnostics_export(&config, logs, mask_wallets);
     serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
 }
 
+/// Zips the recent rotating log files with the last `MiningStatus`,
+/// `CrashRecoveryState`, and a fresh health check of each of `pool_urls`
+/// into one archive under the configured logging directory, so a user
+/// filing an issue can attach one file that explains a rejection spike or
+/// crash. Returns the archive's path.
+#[tauri::command]
+pub async fn export_diagnostics_bundle(
+    state: State<'_, AppStateHandle>,
+    pool_urls: Vec<String>,
+) -> Result<String, String> {
+    let state = state.lock().await;
+    let config = openminedash_core::AppConfig::load().map_err(|e| e.to_string())?;
+
+    let mut pool_health = Vec::new();
+    for url in &pool_urls {
+        if let Ok(result) = openminedash_pools::check_health(url).await {
+            pool_health.push(result);
+        }
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let bundle_path = config.logging.file_dir.join(format!("diagnostics-bundle-{timestamp}.zip"));
+
+    create_diagnostics_bundle(
+        &bundle_path,
+        &config.logging.file_dir,
+        config.logging.max_log_files,
+        state.status(),
+        state.crash_recovery_state(),
+        &pool_health,
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(bundle_path.display().to_string())
+}
+
 #[tauri::command]
 pub async fn get_crash_recovery_state(
     state: State<'_, AppStateHandle>,
@@ -220,19 +355,32 @@ pub async fn update_tray_state(
 #[tauri::command]
 pub async fn start_session(
     sessions: State<'_, SessionManagerHandle>,
-    config: SessionConfig,
+    config_store: State<'_, ConfigStoreHandle>,
+    mut config: SessionConfig,
 ) -> Result<String, String> {
     let manager = sessions.lock().await;
-    manager.start_session(config).await.map_err(|e| e.to_string())
+    let app_config = config_store.get().await;
+    let active_count = manager.active_count().await as u32;
+    config.apply_mode(app_config.mining_mode, &app_config.thread_budget, active_count);
+    let id = manager.start_session(config).await.map_err(|e| e.to_string())?;
+    // A newly-started session can push the running fleet over its thread
+    // budget; rebalance everyone under EnforceLimit before returning.
+    manager.enforce_thread_budget(&app_config.thread_budget).await;
+    Ok(id)
 }
 
 #[tauri::command]
 pub async fn stop_session(
     sessions: State<'_, SessionManagerHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
     session_id: String,
 ) -> Result<(), String> {
     let manager = sessions.lock().await;
-    manager.stop_session(&session_id).await.map_err(|e| e.to_string())
+    manager.stop_session(&session_id).await.map_err(|e| e.to_string())?;
+    // A session stopping frees up budget headroom for whoever's left.
+    let app_config = config_store.get().await;
+    manager.enforce_thread_budget(&app_config.thread_budget).await;
+    Ok(())
 }
 
 #[tauri::command]
@@ -300,12 +448,21 @@ pub async fn get_active_session_count(
 #[tauri::command]
 pub async fn refresh_session_stats(
     sessions: State<'_, SessionManagerHandle>,
+    alerts: State<'_, AlertStoreHandle>,
 ) -> Result<Vec<SessionSummary>, String> {
     let manager = sessions.lock().await;
-    manager.refresh_all_stats().await;
+    manager.refresh_all_stats(alerts.inner()).await;
     Ok(manager.list_sessions().await)
 }
 
+#[tauri::command]
+pub async fn get_session_connections(
+    sessions: State<'_, SessionManagerHandle>,
+) -> Result<Vec<SessionConnection>, String> {
+    let manager = sessions.lock().await;
+    Ok(manager.session_connections().await)
+}
+
 // ============================================================================
 // Alert Inbox Commands
 // ============================================================================
@@ -328,6 +485,16 @@ pub async fn get_unread_alert_count(
     Ok(store.unread_count())
 }
 
+#[tauri::command]
+pub async fn query_alerts(
+    alerts: State<'_, AlertStoreHandle>,
+    query: AlertQuery,
+    limit: Option<usize>,
+) -> Result<Vec<Alert>, String> {
+    let store = alerts.lock().await;
+    Ok(store.query(&query, limit.unwrap_or(50)))
+}
+
 #[tauri::command]
 pub async fn mark_alerts_read(
     alerts: State<'_, AlertStoreHandle>,
@@ -352,40 +519,119 @@ pub async fn clear_alerts(
 
 #[tauri::command]
 pub async fn get_thread_budget_settings(
-    state: State<'_, AppStateHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
 ) -> Result<ThreadBudgetSettings, String> {
-    let state = state.lock().await;
-    let config = openminedash_core::AppConfig::load().unwrap_or_default();
-    Ok(config.thread_budget)
+    Ok(config_store.get().await.thread_budget)
 }
 
 #[tauri::command]
 pub async fn set_thread_budget_settings(
-    state: State<'_, AppStateHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
     settings: ThreadBudgetSettings,
 ) -> Result<(), String> {
-    let _state = state.lock().await;
-    let mut config = openminedash_core::AppConfig::load().unwrap_or_default();
-    config.thread_budget = settings;
-    config.save().map_err(|e| e.to_string())
+    config_store
+        .update(|config| config.thread_budget = settings)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_mining_mode(state: State<'_, AppStateHandle>) -> Result<MiningMode, String> {
+    let state = state.lock().await;
+    Ok(state.mining_mode())
+}
+
+#[tauri::command]
+pub async fn set_mining_mode(
+    state: State<'_, AppStateHandle>,
+    sessions: State<'_, SessionManagerHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
+    mode: MiningMode,
+) -> Result<(), String> {
+    let mut state = state.lock().await;
+    state.set_mining_mode(mode).map_err(|e| e.to_string())?;
+    drop(state);
+
+    // No live thread-reconfigure API exists for either miner adapter, so
+    // re-applying the new mode to already-running sessions means
+    // restarting each one with the recalculated thread count.
+    let manager = sessions.lock().await;
+    let budget = config_store.get().await.thread_budget;
+    let running: Vec<_> = manager.list_sessions().await.into_iter()
+        .filter(|s| s.stats.status == openminedash_core::SessionStatus::Running)
+        .collect();
+    let active_count = running.len() as u32;
+    for summary in running {
+        let mut config = summary.config;
+        config.apply_mode(mode, &budget, active_count);
+        if manager.stop_session(&summary.id).await.is_ok() {
+            let _ = manager.start_session(config).await;
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_auto_mining_settings(
+    auto_miner: State<'_, AutoMinerHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
+    settings: AutoMiningSettings,
+) -> Result<(), String> {
+    auto_miner.update_settings(settings.clone());
+    config_store
+        .update(|config| config.auto_mining = settings)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_auto_mining_status(auto_miner: State<'_, AutoMinerHandle>) -> Result<AutoMiningStatus, String> {
+    Ok(auto_miner.status())
 }
 
 #[tauri::command]
 pub async fn get_budget_status(
     sessions: State<'_, SessionManagerHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
 ) -> Result<BudgetStatus, String> {
     let manager = sessions.lock().await;
     let sessions_list = manager.list_sessions().await;
-    
+
     let active_count = sessions_list.iter()
         .filter(|s| s.stats.status == openminedash_core::SessionStatus::Running)
         .count() as u32;
-    
+
     let total_threads: u32 = sessions_list.iter()
         .filter(|s| s.stats.status == openminedash_core::SessionStatus::Running)
         .map(|s| s.config.threads_hint)
         .sum();
-    
-    let config = openminedash_core::AppConfig::load().unwrap_or_default();
+
+    let config = config_store.get().await;
     Ok(calculate_budget(&config.thread_budget, active_count, total_threads))
 }
+
+#[tauri::command]
+pub async fn list_workers(workers: State<'_, WorkerManager>) -> Result<Vec<WorkerSnapshot>, String> {
+    Ok(workers.list_workers())
+}
+
+#[tauri::command]
+pub async fn get_scrub_status(scrub: State<'_, ScrubHandle>) -> Result<ScrubStatus, String> {
+    Ok(scrub.status())
+}
+
+#[tauri::command]
+pub async fn set_scrub_tranquility(
+    scrub: State<'_, ScrubHandle>,
+    config_store: State<'_, ConfigStoreHandle>,
+    tranquility: u8,
+) -> Result<(), String> {
+    scrub.send(ScrubCommand::SetTranquility(tranquility));
+    config_store
+        .update(|config| config.scrub.tranquility = tranquility)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -6,25 +6,79 @@
 mod commands;
 mod notifications;
 mod tray;
+mod workers;
 
 use commands::*;
-use notifications::NotificationManager;
-use openminedash_core::{AppState, SessionManager, AlertStore, AppConfig};
+use notifications::{spawn_digest_loop, NotificationManager};
+use openminedash_core::{
+    init_logging, new_shared_metrics, spawn_auto_miner, spawn_control_server, spawn_ipc_server,
+    spawn_metrics_server, spawn_session_scrub, AlertStore, AppConfig, AppState, AutoMinerHandle,
+    ConfigStore, HashrateSparkline, IpcContext, MiningHistory, ScrubHandle, SessionManager,
+    WorkerManager,
+};
+use std::time::Duration;
+use workers::{HistoryFlushWorker, PoolHealthWorker, StatsRefreshWorker};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 fn main() {
-    tracing_subscriber::fmt::init();
+    // Held for the process lifetime so the file target's background writer
+    // thread isn't torn down immediately after `main` returns from setup.
+    let _logging_guard = init_logging(&AppConfig::load().unwrap_or_default().logging);
 
     let state = Arc::new(Mutex::new(AppState::new()));
     let notification_manager = Arc::new(Mutex::new(
         NotificationManager::new("com.openminedash.app")
     ));
     let session_manager = Arc::new(Mutex::new(SessionManager::new()));
-    let alert_store = Arc::new(Mutex::new(AlertStore::new()));
+    let alert_store = Arc::new(Mutex::new(AlertStore::load()));
+    let mining_history = Arc::new(Mutex::new(MiningHistory::load()));
+    // Loaded first (and ahead of `auto_miner`/`scrub_handle`) since it's now
+    // the single authoritative store for settings a command can mutate at
+    // runtime - seeding those handles from the legacy file here instead
+    // would let them drift back to stale values across a restart.
+    let config_store = ConfigStore::load().expect("failed to open config database");
+    let startup_config = tauri::async_runtime::block_on(config_store.get());
+    let auto_miner = AutoMinerHandle::new(startup_config.auto_mining);
+    let worker_manager = WorkerManager::new();
+    let hashrate_sparkline = HashrateSparkline::new();
+    let (scrub_handle, scrub_commands) = ScrubHandle::new(startup_config.scrub);
+    spawn_session_scrub(
+        scrub_handle.clone(),
+        scrub_commands,
+        session_manager.clone(),
+        alert_store.clone(),
+        hashrate_sparkline.clone(),
+    );
+
+    spawn_digest_loop(notification_manager.clone());
+    spawn_auto_miner(auto_miner.clone(), session_manager.clone(), alert_store.clone());
+
+    // Prometheus exporter (opt-in, off by default)
+    let shared_metrics = new_shared_metrics();
+    let metrics_config = AppConfig::load().unwrap_or_default().metrics;
+    spawn_metrics_server(metrics_config, shared_metrics.clone());
+    let control_metrics = shared_metrics.clone();
 
     // Clone for quit handler
     let session_manager_quit = session_manager.clone();
+    let alert_store_quit = alert_store.clone();
+
+    // Headless JSON-RPC control socket (opt-in, off by default)
+    let headless_config = AppConfig::load().unwrap_or_default().headless;
+    let headless_state = state.clone();
+
+    // Local IPC control socket for sessions/history/crash-recovery (opt-in, off by default)
+    let ipc_config = AppConfig::load().unwrap_or_default().ipc;
+    let ipc_ctx = IpcContext {
+        state: state.clone(),
+        sessions: session_manager.clone(),
+        history: mining_history.clone(),
+    };
+
+    let worker_state = state.clone();
+    let worker_alerts = alert_store.clone();
+    let worker_history = mining_history.clone();
 
     tauri::Builder::default()
         .system_tray(tray::create_tray())
@@ -33,6 +87,13 @@ fn main() {
         .manage(notification_manager)
         .manage(session_manager.clone())
         .manage(alert_store)
+        .manage(shared_metrics)
+        .manage(mining_history)
+        .manage(auto_miner)
+        .manage(config_store)
+        .manage(worker_manager.clone())
+        .manage(scrub_handle)
+        .manage(hashrate_sparkline)
         .invoke_handler(tauri::generate_handler![
             // Legacy commands (backward compatibility)
             get_consent,
@@ -42,15 +103,23 @@ fn main() {
             get_custom_binary_path,
             set_custom_binary_path,
             list_coins,
+            validate_mining_config,
             start_mining,
             stop_mining,
             get_status,
+            stratum_connect,
+            stratum_disconnect,
+            stratum_stats,
             save_profile,
             delete_profile,
             list_profiles,
             check_pool_health,
+            check_pool_health_detailed,
+            check_pool_health_full,
             fetch_pool_balance,
+            reconcile_wallet_balance,
             export_diagnostics,
+            export_diagnostics_bundle,
             get_crash_recovery_state,
             clear_crash_recovery,
             get_notification_settings,
@@ -69,19 +138,30 @@ fn main() {
             stop_all_sessions,
             get_active_session_count,
             refresh_session_stats,
+            get_session_connections,
             // Alert commands
             list_alerts,
             get_unread_alert_count,
+            query_alerts,
             mark_alerts_read,
             clear_alerts,
             // Thread budget commands
             get_thread_budget_settings,
             set_thread_budget_settings,
             get_budget_status,
+            get_mining_mode,
+            set_mining_mode,
+            set_auto_mining_settings,
+            get_auto_mining_status,
             // Mining history commands
             get_mining_history,
             get_history_summary,
             clear_mining_history,
+            // Background worker commands
+            list_workers,
+            // Session scrub commands
+            get_scrub_status,
+            set_scrub_tranquility,
         ])
         .setup(move |app| {
             // Set app handle for session manager
@@ -90,7 +170,35 @@ fn main() {
             tauri::async_runtime::spawn(async move {
                 let mut manager = sm.lock().await;
                 manager.set_app_handle(handle);
+
+                // Auto-resume whatever was still running/suspended when we
+                // were last able to persist session state.
+                manager.restore_sessions().await;
             });
+
+            // Background workers: stat refresh, pool health, and history
+            // retention loops, introspectable via `list_workers`/the tray's
+            // "Background Tasks" submenu instead of running unsupervised.
+            worker_manager.spawn(
+                StatsRefreshWorker::new(
+                    worker_state.clone(),
+                    session_manager.clone(),
+                    worker_alerts.clone(),
+                    worker_manager.clone(),
+                    app.handle(),
+                ),
+                Duration::from_secs(5),
+            );
+            worker_manager.spawn(PoolHealthWorker::new(worker_state.clone()), Duration::from_secs(60));
+            worker_manager.spawn(HistoryFlushWorker::new(worker_history.clone()), Duration::from_secs(30));
+
+            spawn_control_server(
+                headless_config.clone(),
+                headless_state.clone(),
+                app.handle(),
+                control_metrics.clone(),
+            );
+            spawn_ipc_server(ipc_config.clone(), ipc_ctx.clone());
             Ok(())
         })
         .on_window_event(|event| {
@@ -116,6 +224,13 @@ fn main() {
                         let _ = manager.stop_all().await;
                     });
                 }
+
+                // Force a final write so whatever's accumulated since the
+                // last debounced flush isn't lost.
+                let alerts = alert_store_quit.clone();
+                tauri::async_runtime::block_on(async {
+                    alerts.lock().await.flush_now();
+                });
             }
         });
 }
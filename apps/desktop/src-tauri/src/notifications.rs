@@ -2,9 +2,9 @@
 //! All notifications are opt-in by default.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::api::notification::Notification;
 use tracing::{info, warn};
 
@@ -16,11 +16,52 @@ pub struct NotificationSettings {
     pub hashrate_drop: bool,
     pub hashrate_drop_threshold: f64, // percentage, e.g., 30.0
     pub miner_crash: bool,
+    /// Alert when `HashrateTracker::share_reject_rate` exceeds
+    /// `high_reject_rate_threshold` over its default window.
+    #[serde(default = "default_high_reject_rate_enabled")]
+    pub high_reject_rate: bool,
+    /// Reject-rate fraction (0.0-1.0, e.g. 0.1 = 10%) that counts as "high".
+    #[serde(default = "default_high_reject_rate_threshold")]
+    pub high_reject_rate_threshold: f64,
     pub remote_offline: bool,
     pub update_available: bool,
     pub quiet_hours_enabled: bool,
     pub quiet_hours_start: u8, // 0-23
     pub quiet_hours_end: u8,   // 0-23
+    /// Minute component of `quiet_hours_start`, 0-59. Defaults to 0 so
+    /// settings saved before per-minute granularity still load correctly.
+    #[serde(default)]
+    pub quiet_hours_start_minute: u8,
+    /// Minute component of `quiet_hours_end`, 0-59.
+    #[serde(default)]
+    pub quiet_hours_end_minute: u8,
+    /// Batch everything but high-severity alerts into a periodic rollup
+    /// instead of sending each one the moment it passes the checks above.
+    #[serde(default)]
+    pub digest_enabled: bool,
+    /// How often the digest is flushed, in seconds.
+    #[serde(default = "default_digest_interval_secs")]
+    pub digest_interval_secs: u64,
+    /// Also POST alerts to `webhook_url` (e.g. a self-hosted relay or chat
+    /// bridge), for when nobody's in front of the machine.
+    #[serde(default)]
+    pub webhook_enabled: bool,
+    /// Destination for the webhook sink. Ignored while `webhook_enabled` is
+    /// false.
+    #[serde(default)]
+    pub webhook_url: String,
+}
+
+fn default_digest_interval_secs() -> u64 {
+    60
+}
+
+fn default_high_reject_rate_enabled() -> bool {
+    true
+}
+
+fn default_high_reject_rate_threshold() -> f64 {
+    0.1
 }
 
 impl Default for NotificationSettings {
@@ -31,15 +72,53 @@ impl Default for NotificationSettings {
             hashrate_drop: true,
             hashrate_drop_threshold: 30.0,
             miner_crash: true,
+            high_reject_rate: default_high_reject_rate_enabled(),
+            high_reject_rate_threshold: default_high_reject_rate_threshold(),
             remote_offline: false,
             update_available: true,
             quiet_hours_enabled: false,
             quiet_hours_start: 22,
             quiet_hours_end: 8,
+            quiet_hours_start_minute: 0,
+            quiet_hours_end_minute: 0,
+            digest_enabled: false, // Opt-in by default
+            digest_interval_secs: default_digest_interval_secs(),
+            webhook_enabled: false, // Opt-in by default
+            webhook_url: String::new(),
         }
     }
 }
 
+/// Source of the current local wall-clock time, kept separate from the
+/// quiet-hours math so the backend can be swapped per target without
+/// touching the range logic.
+trait Clock {
+    /// Current local (hour, minute), 0-23 / 0-59.
+    fn local_hour_minute(&self) -> (u8, u8);
+}
+
+/// Real local time. On native targets `chrono::Local` already resolves the
+/// OS timezone; on wasm it doesn't (no OS to ask), so we read the
+/// browser's `Date` object directly instead.
+struct SystemClock;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn local_hour_minute(&self) -> (u8, u8) {
+        use chrono::Timelike;
+        let now = chrono::Local::now();
+        (now.hour() as u8, now.minute() as u8)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for SystemClock {
+    fn local_hour_minute(&self) -> (u8, u8) {
+        let now = js_sys::Date::new_0();
+        (now.get_hours() as u8, now.get_minutes() as u8)
+    }
+}
+
 /// Notification types for deduplication
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NotificationType {
@@ -48,8 +127,12 @@ pub enum NotificationType {
     HashrateDrop(String),  // session_id
     MinerCrash(String),    // session_id
     MinerStopped(String),  // session_id
+    HighRejectRate(String), // session_id
     RemoteOffline(String), // endpoint_id
     UpdateAvailable,
+    /// Not tied to a single session/endpoint: digest rollups, test pings,
+    /// snooze-status messages.
+    System,
 }
 
 /// Dedup key for rate limiting
@@ -60,9 +143,211 @@ fn dedup_key(notification_type: &NotificationType) -> String {
         NotificationType::HashrateDrop(id) => format!("hashrate_drop:{}", id),
         NotificationType::MinerCrash(id) => format!("miner_crash:{}", id),
         NotificationType::MinerStopped(id) => format!("miner_stopped:{}", id),
+        NotificationType::HighRejectRate(id) => format!("high_reject_rate:{}", id),
         NotificationType::RemoteOffline(id) => format!("remote_offline:{}", id),
         NotificationType::UpdateAvailable => "update_available".to_string(),
+        NotificationType::System => "system".to_string(),
+    }
+}
+
+/// Session (or endpoint) id carried by a notification type, if any - used
+/// to populate the webhook payload's `session_id` field.
+fn session_id_of(notification_type: &NotificationType) -> Option<String> {
+    match notification_type {
+        NotificationType::PoolDown(id)
+        | NotificationType::PoolRecovered(id)
+        | NotificationType::HashrateDrop(id)
+        | NotificationType::MinerCrash(id)
+        | NotificationType::MinerStopped(id)
+        | NotificationType::HighRejectRate(id)
+        | NotificationType::RemoteOffline(id) => Some(id.clone()),
+        NotificationType::UpdateAvailable | NotificationType::System => None,
+    }
+}
+
+/// High-severity types skip digest batching and always fire immediately,
+/// even while digest mode is on.
+fn is_high_severity(notification_type: &NotificationType) -> bool {
+    matches!(notification_type, NotificationType::MinerCrash(_))
+}
+
+/// Stable kind tag for a notification type, independent of which session
+/// (or endpoint) it's about - the `dedup_key` prefix. Used to snooze or
+/// roll up by type rather than by individual instance.
+fn notification_kind(notification_type: &NotificationType) -> String {
+    dedup_key(notification_type)
+        .split(':')
+        .next()
+        .unwrap_or("alert")
+        .to_string()
+}
+
+/// Singular/plural label for a digest line, keyed by the `dedup_key` prefix
+/// (everything before the first `:`, or the whole key for `UpdateAvailable`).
+fn digest_label(kind: &str, count: u32) -> String {
+    let (singular, plural) = match kind {
+        "pool_down" => ("pool down", "pools down"),
+        "pool_recovered" => ("pool recovered", "pools recovered"),
+        "hashrate_drop" => ("hashrate drop", "hashrate drops"),
+        "miner_crash" => ("miner crash", "miner crashes"),
+        "miner_stopped" => ("miner stopped", "miners stopped"),
+        "high_reject_rate" => ("session with high reject rate", "sessions with high reject rate"),
+        "remote_offline" => ("remote miner offline", "remote miners offline"),
+        "update_available" => ("update available", "updates available"),
+        _ => ("alert", "alerts"),
+    };
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+/// Coarse "Xh Ym" / "Xm" / "Xs" rendering of a remaining snooze duration.
+fn format_duration(d: Duration) -> String {
+    let total_secs = d.as_secs();
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", total_secs.max(1))
+    }
+}
+
+/// How many delivery attempts (first try included) a webhook gets before
+/// giving up.
+const WEBHOOK_MAX_ATTEMPTS: u32 = 4;
+/// Initial backoff between webhook retries, doubling each time.
+const WEBHOOK_INITIAL_BACKOFF_SECS: u64 = 1;
+
+/// A destination `send()` fans a notification out to. Desktop popups and
+/// the webhook relay both implement this, so adding a new delivery channel
+/// (e.g. a chat bridge) doesn't touch the rate-limiting/dedup logic above.
+trait NotificationSink {
+    fn deliver(&self, title: &str, body: &str, kind: &NotificationType);
+}
+
+/// The original (and still default) sink: a native OS notification.
+struct DesktopSink {
+    app_identifier: String,
+}
+
+impl NotificationSink for DesktopSink {
+    fn deliver(&self, title: &str, body: &str, _kind: &NotificationType) {
+        match Notification::new(&self.app_identifier)
+            .title(title)
+            .body(body)
+            .sound("default") // macOS system sound
+            .show()
+        {
+            Ok(_) => info!("Notification sent: {}", title),
+            Err(e) => warn!("Failed to send notification: {}", e),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WebhookPayload {
+    #[serde(rename = "type")]
+    kind: String,
+    session_id: Option<String>,
+    title: String,
+    body: String,
+    timestamp: u64,
+}
+
+/// POSTs a JSON payload to a user-configured URL - for a headless box, or
+/// for reaching the operator while they're away from the machine.
+struct WebhookSink {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn deliver(&self, title: &str, body: &str, kind: &NotificationType) {
+        let url = self.url.clone();
+        let client = self.client.clone();
+        let payload = WebhookPayload {
+            kind: notification_kind(kind),
+            session_id: session_id_of(kind),
+            title: title.to_string(),
+            body: body.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        };
+
+        // `deliver` must stay sync to match `NotificationSink`, so the
+        // actual HTTP call (with its own retry/backoff) runs detached.
+        tauri::async_runtime::spawn(async move {
+            let mut backoff = Duration::from_secs(WEBHOOK_INITIAL_BACKOFF_SECS);
+            for attempt in 1..=WEBHOOK_MAX_ATTEMPTS {
+                match client.post(&url).json(&payload).send().await {
+                    Ok(resp) if resp.status().is_success() => return,
+                    Ok(resp) => warn!(
+                        "Webhook sink got {} from {} (attempt {}/{})",
+                        resp.status(),
+                        url,
+                        attempt,
+                        WEBHOOK_MAX_ATTEMPTS
+                    ),
+                    Err(e) => warn!(
+                        "Webhook sink delivery failed (attempt {}/{}): {}",
+                        attempt, WEBHOOK_MAX_ATTEMPTS, e
+                    ),
+                }
+                if attempt < WEBHOOK_MAX_ATTEMPTS {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+            warn!("Webhook sink giving up on {} after {} attempts", url, WEBHOOK_MAX_ATTEMPTS);
+        });
+    }
+}
+
+/// Build the sink list implied by `settings` - the desktop sink is always
+/// on, the webhook sink only when opted into and given a URL.
+fn build_sinks(
+    app_identifier: &str,
+    settings: &NotificationSettings,
+) -> Vec<Box<dyn NotificationSink + Send + Sync>> {
+    let mut sinks: Vec<Box<dyn NotificationSink + Send + Sync>> = vec![Box::new(DesktopSink {
+        app_identifier: app_identifier.to_string(),
+    })];
+    if settings.webhook_enabled && !settings.webhook_url.trim().is_empty() {
+        sinks.push(Box::new(WebhookSink::new(settings.webhook_url.clone())));
     }
+    sinks
+}
+
+/// One notification type's accumulated state within the current digest
+/// interval: how many times it recurred, and when it first showed up.
+struct PendingDigestEntry {
+    count: u32,
+    first_seen: Instant,
+}
+
+/// Alerts accumulated since the last digest flush.
+#[derive(Default)]
+struct PendingDigest {
+    entries: HashMap<String, PendingDigestEntry>,
+    /// Keys already folded into `entries` this interval - lets a repeat of
+    /// the same alert (e.g. a pool still down on the next poll tick) bump
+    /// the existing entry's count instead of being treated as new.
+    seen_keys: HashSet<String>,
 }
 
 /// Rate limiter to prevent notification spam
@@ -71,19 +356,76 @@ pub struct NotificationManager {
     last_sent: Mutex<HashMap<String, Instant>>, // dedup_key -> last sent time
     cooldown: Duration,
     app_identifier: String,
+    pending_digest: Mutex<PendingDigest>,
+    /// Deafen-everything snooze, separate from `settings.enabled` so it
+    /// doesn't touch the user's configured preferences.
+    snooze_until: Mutex<Option<Instant>>,
+    /// Per-kind snooze expiries (e.g. mute just `HashrateDrop`), keyed by
+    /// `notification_kind`.
+    type_snoozes: Mutex<HashMap<String, Instant>>,
+    /// Delivery destinations `send()` fans out to - rebuilt whenever
+    /// settings change so a webhook toggle/URL edit takes effect immediately.
+    sinks: Vec<Box<dyn NotificationSink + Send + Sync>>,
 }
 
 impl NotificationManager {
     pub fn new(app_identifier: &str) -> Self {
+        let settings = NotificationSettings::default();
+        let sinks = build_sinks(app_identifier, &settings);
         Self {
-            settings: NotificationSettings::default(),
+            settings,
             last_sent: Mutex::new(HashMap::new()),
             cooldown: Duration::from_secs(300), // 5 minutes
             app_identifier: app_identifier.to_string(),
+            pending_digest: Mutex::new(PendingDigest::default()),
+            snooze_until: Mutex::new(None),
+            type_snoozes: Mutex::new(HashMap::new()),
+            sinks,
         }
     }
 
+    /// Silence every notification for `duration`, regardless of type.
+    pub fn snooze_all(&self, duration: Duration) {
+        *self.snooze_until.lock().unwrap() = Some(Instant::now() + duration);
+    }
+
+    /// Silence just one notification kind (all sessions/endpoints) for
+    /// `duration`, e.g. muting `HashrateDrop` while benchmarking.
+    pub fn snooze_type(&self, notification_type: &NotificationType, duration: Duration) {
+        self.type_snoozes
+            .lock()
+            .unwrap()
+            .insert(notification_kind(notification_type), Instant::now() + duration);
+    }
+
+    /// Cancel any active snooze, both the global one and per-type ones.
+    pub fn clear_snooze(&self) {
+        *self.snooze_until.lock().unwrap() = None;
+        self.type_snoozes.lock().unwrap().clear();
+    }
+
+    /// Time remaining on the global snooze, `None` if it's not active.
+    pub fn remaining_snooze(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.snooze_until
+            .lock()
+            .unwrap()
+            .and_then(|until| until.checked_duration_since(now))
+    }
+
+    fn is_snoozed(&self, notification_type: &NotificationType) -> bool {
+        let now = Instant::now();
+        if let Some(until) = *self.snooze_until.lock().unwrap() {
+            if until > now {
+                return true;
+            }
+        }
+        let kind = notification_kind(notification_type);
+        matches!(self.type_snoozes.lock().unwrap().get(&kind), Some(until) if *until > now)
+    }
+
     pub fn update_settings(&mut self, settings: NotificationSettings) {
+        self.sinks = build_sinks(&self.app_identifier, &settings);
         self.settings = settings;
     }
 
@@ -97,15 +439,20 @@ impl NotificationManager {
             return false;
         }
 
-        let now = chrono::Local::now();
-        let hour = now.hour() as u8;
-
-        if self.settings.quiet_hours_start <= self.settings.quiet_hours_end {
-            // Simple range: e.g., 22-08 means 22:00 to 08:00
-            hour >= self.settings.quiet_hours_start || hour < self.settings.quiet_hours_end
+        let (hour, minute) = SystemClock.local_hour_minute();
+        let m = hour as u32 * 60 + minute as u32;
+        let start_m = self.settings.quiet_hours_start as u32 * 60
+            + self.settings.quiet_hours_start_minute as u32;
+        let end_m = self.settings.quiet_hours_end as u32 * 60
+            + self.settings.quiet_hours_end_minute as u32;
+
+        if start_m <= end_m {
+            // Simple range: e.g., 22:00-08:00 never applies here since
+            // start <= end, so this covers same-day ranges like 08:00-17:00.
+            m >= start_m && m < end_m
         } else {
-            // Wrapping range: e.g., 22-08 means 22:00 to next day 08:00
-            hour >= self.settings.quiet_hours_start || hour < self.settings.quiet_hours_end
+            // Wrapping range: e.g., 22:00-08:00 spans midnight.
+            m >= start_m || m < end_m
         }
     }
 
@@ -119,13 +466,21 @@ impl NotificationManager {
             return false;
         }
 
+        if self.is_snoozed(notification_type) {
+            return false;
+        }
+
         // Check type-specific settings
         let type_enabled = match notification_type {
             NotificationType::PoolDown(_) | NotificationType::PoolRecovered(_) => self.settings.pool_down,
             NotificationType::HashrateDrop(_) => self.settings.hashrate_drop,
             NotificationType::MinerCrash(_) | NotificationType::MinerStopped(_) => self.settings.miner_crash,
+            NotificationType::HighRejectRate(_) => self.settings.high_reject_rate,
             NotificationType::RemoteOffline(_) => self.settings.remote_offline,
             NotificationType::UpdateAvailable => self.settings.update_available,
+            // System messages (test pings, digest rollups) bypass
+            // `should_send` entirely - never actually matched here.
+            NotificationType::System => true,
         };
 
         if !type_enabled {
@@ -142,20 +497,80 @@ impl NotificationManager {
             }
         }
 
-        last_sent.insert(key, Instant::now());
+        last_sent.insert(key.clone(), Instant::now());
+        drop(last_sent);
+
+        if self.settings.digest_enabled && !is_high_severity(notification_type) {
+            self.queue_for_digest(key);
+            return false;
+        }
+
         true
     }
 
-    /// Send a notification with optional sound
-    fn send(&self, title: &str, body: &str) {
-        match Notification::new(&self.app_identifier)
-            .title(title)
-            .body(body)
-            .sound("default") // macOS system sound
-            .show()
-        {
-            Ok(_) => info!("Notification sent: {}", title),
-            Err(e) => warn!("Failed to send notification: {}", e),
+    /// Fold a notification into the pending digest instead of sending it
+    /// right away.
+    fn queue_for_digest(&self, key: String) {
+        let mut digest = self.pending_digest.lock().unwrap();
+        if digest.seen_keys.insert(key.clone()) {
+            digest.entries.insert(
+                key,
+                PendingDigestEntry {
+                    count: 1,
+                    first_seen: Instant::now(),
+                },
+            );
+        } else if let Some(entry) = digest.entries.get_mut(&key) {
+            entry.count += 1;
+        }
+    }
+
+    /// Roll up whatever is pending into a single notification and clear the
+    /// digest. No-op if nothing has accumulated since the last flush.
+    fn flush_digest(&self) {
+        let mut digest = self.pending_digest.lock().unwrap();
+        if digest.entries.is_empty() {
+            return;
+        }
+
+        let mut category_counts: HashMap<String, u32> = HashMap::new();
+        let mut total = 0u32;
+        let mut oldest = Instant::now();
+        for (key, entry) in digest.entries.iter() {
+            let kind = key.split(':').next().unwrap_or(key);
+            *category_counts.entry(kind.to_string()).or_insert(0) += entry.count;
+            total += entry.count;
+            if entry.first_seen < oldest {
+                oldest = entry.first_seen;
+            }
+        }
+
+        let mut lines: Vec<String> = category_counts
+            .iter()
+            .map(|(kind, count)| digest_label(kind, *count))
+            .collect();
+        lines.sort();
+
+        digest.entries.clear();
+        digest.seen_keys.clear();
+        drop(digest);
+
+        info!(
+            "Flushing notification digest: {} alert(s), oldest {:.0}s ago",
+            total,
+            oldest.elapsed().as_secs_f64()
+        );
+        self.send(
+            &format!("{} alert{}", total, if total == 1 { "" } else { "s" }),
+            &lines.join(", "),
+            &NotificationType::System,
+        );
+    }
+
+    /// Fan a notification out across every configured sink.
+    fn send(&self, title: &str, body: &str, kind: &NotificationType) {
+        for sink in &self.sinks {
+            sink.deliver(title, body, kind);
         }
     }
 
@@ -178,19 +593,23 @@ impl NotificationManager {
     // Public notification methods (session-aware)
 
     pub fn notify_pool_down(&self, session_id: &str, symbol: &str, pool: &str) {
-        if self.should_send(&NotificationType::PoolDown(session_id.to_string())) {
+        let kind = NotificationType::PoolDown(session_id.to_string());
+        if self.should_send(&kind) {
             self.send(
                 &format!("{}: Pool Connection Lost", symbol),
                 &format!("Lost connection to {}", pool),
+                &kind,
             );
         }
     }
 
     pub fn notify_pool_recovered(&self, session_id: &str, symbol: &str, pool: &str) {
-        if self.should_send(&NotificationType::PoolRecovered(session_id.to_string())) {
+        let kind = NotificationType::PoolRecovered(session_id.to_string());
+        if self.should_send(&kind) {
             self.send(
                 &format!("{}: Pool Reconnected", symbol),
                 &format!("Connected to {}", pool),
+                &kind,
             );
         }
     }
@@ -198,86 +617,115 @@ impl NotificationManager {
     pub fn notify_hashrate_drop(&self, session_id: &str, symbol: &str, current: f64, average: f64) {
         let drop_pct = ((average - current) / average * 100.0).abs();
         if drop_pct >= self.settings.hashrate_drop_threshold {
-            if self.should_send(&NotificationType::HashrateDrop(session_id.to_string())) {
+            let kind = NotificationType::HashrateDrop(session_id.to_string());
+            if self.should_send(&kind) {
                 self.send(
                     &format!("{}: Hashrate Drop", symbol),
                     &format!("Current: {:.1} H/s (down {:.0}%)", current, drop_pct),
+                    &kind,
                 );
             }
         }
     }
 
-    pub fn notify_miner_crash(&self, session_id: &str, symbol: &str, error: &str) {
-        if self.should_send(&NotificationType::MinerCrash(session_id.to_string())) {
+    /// `reject_rate` is a fraction (0.0-1.0), e.g. from
+    /// `SessionStats::share_reject_rate`.
+    pub fn notify_high_reject_rate(&self, session_id: &str, symbol: &str, reject_rate: f64) {
+        if reject_rate < self.settings.high_reject_rate_threshold {
+            return;
+        }
+        let kind = NotificationType::HighRejectRate(session_id.to_string());
+        if self.should_send(&kind) {
             self.send(
-                &format!("{}: Miner Stopped Unexpectedly", symbol),
-                error,
+                &format!("{}: High Reject Rate", symbol),
+                &format!("{:.0}% of shares rejected", reject_rate * 100.0),
+                &kind,
             );
         }
     }
 
+    pub fn notify_miner_crash(&self, session_id: &str, symbol: &str, error: &str) {
+        let kind = NotificationType::MinerCrash(session_id.to_string());
+        if self.should_send(&kind) {
+            self.send(&format!("{}: Miner Stopped Unexpectedly", symbol), error, &kind);
+        }
+    }
+
     pub fn notify_miner_stopped(&self) {
         // Legacy: no session context
-        if self.should_send(&NotificationType::MinerStopped("legacy".to_string())) {
-            self.send("Mining Stopped", "Mining has been stopped");
+        let kind = NotificationType::MinerStopped("legacy".to_string());
+        if self.should_send(&kind) {
+            self.send("Mining Stopped", "Mining has been stopped", &kind);
         }
     }
 
     pub fn notify_session_stopped(&self, session_id: &str, symbol: &str) {
-        if self.should_send(&NotificationType::MinerStopped(session_id.to_string())) {
+        let kind = NotificationType::MinerStopped(session_id.to_string());
+        if self.should_send(&kind) {
             self.send(
                 &format!("{}: Mining Stopped", symbol),
                 "Session has been stopped",
+                &kind,
             );
         }
     }
 
     pub fn notify_remote_offline(&self, name: &str) {
-        if self.should_send(&NotificationType::RemoteOffline(name.to_string())) {
-            self.send("Remote Miner Offline", &format!("{} is not responding", name));
+        let kind = NotificationType::RemoteOffline(name.to_string());
+        if self.should_send(&kind) {
+            self.send("Remote Miner Offline", &format!("{} is not responding", name), &kind);
         }
     }
 
     pub fn notify_update_available(&self, version: &str) {
         if self.should_send(&NotificationType::UpdateAvailable) {
-            self.send("Update Available", &format!("Version {} is available", version));
+            self.send(
+                "Update Available",
+                &format!("Version {} is available", version),
+                &NotificationType::UpdateAvailable,
+            );
         }
     }
 
     /// Send a test notification (bypasses rate limiting)
     pub fn send_test(&self) {
-        if self.settings.enabled {
-            // Play a pleasant sound
-            self.play_sound("Glass");
-            self.send("Test Notification", "Notifications are working correctly!");
-        } else {
-            // Even if disabled, play sound to confirm it works
-            self.play_sound("Glass");
+        self.play_sound("Glass");
+
+        if let Some(remaining) = self.remaining_snooze() {
+            self.send(
+                "Notifications Snoozed",
+                &format!("Resuming in {}", format_duration(remaining)),
+                &NotificationType::System,
+            );
+        } else if self.settings.enabled {
+            self.send(
+                "Test Notification",
+                "Notifications are working correctly!",
+                &NotificationType::System,
+            );
         }
+        // Even if disabled (and not snoozed), the sound above already
+        // confirmed playback works.
     }
 }
 
-// Simple chrono replacement for hour extraction
-mod chrono {
-    pub struct Local;
-    pub struct DateTime {
-        hour: u8,
-    }
-    impl DateTime {
-        pub fn hour(&self) -> u32 {
-            self.hour as u32
-        }
-    }
-    impl Local {
-        pub fn now() -> DateTime {
-            use std::time::{SystemTime, UNIX_EPOCH};
-            let secs = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            // Rough hour calculation (UTC, not local, but good enough for now)
-            let hour = ((secs % 86400) / 3600) as u8;
-            DateTime { hour }
+/// Tick the digest flush on `settings().digest_interval_secs`, for as long
+/// as the process runs. Picks the interval up fresh each tick so a settings
+/// change takes effect on the next tick rather than requiring a restart.
+/// No-op (just re-checks on the configured interval) while digest mode is off.
+pub fn spawn_digest_loop(manager: Arc<tokio::sync::Mutex<NotificationManager>>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval_secs = {
+                let manager = manager.lock().await;
+                manager.settings().digest_interval_secs.max(1)
+            };
+            tokio::time::sleep(Duration::from_secs(interval_secs)).await;
+
+            let manager = manager.lock().await;
+            if manager.settings().digest_enabled {
+                manager.flush_digest();
+            }
         }
-    }
+    });
 }
@@ -1,6 +1,7 @@
 //! System tray (menu bar) implementation for macOS.
 //! Single source of truth - created only in Rust, not JS.
 
+use openminedash_core::{HashrateSparkline, SessionPriority, WorkerHealth, WorkerSnapshot};
 use tauri::{
     AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
     SystemTrayMenuItem, SystemTraySubmenu,
@@ -14,11 +15,14 @@ pub struct TraySessionInfo {
     pub symbol: String,
     pub hashrate: f64,
     pub status: String,
+    /// Thread-budget priority class, so the tray can flag which sessions
+    /// get squeezed first under `BudgetMode::EnforceLimit`.
+    pub priority: SessionPriority,
 }
 
 /// Build the system tray with initial stopped state
 pub fn create_tray() -> SystemTray {
-    let menu = build_tray_menu(false, 0.0, 0, 0, "balanced", &[]);
+    let menu = build_tray_menu(false, 0.0, 0, 0, "balanced", &[], &[], false);
     SystemTray::new().with_menu(menu)
 }
 
@@ -30,6 +34,8 @@ pub fn build_tray_menu(
     uptime: u64,
     preset: &str,
     sessions: &[TraySessionInfo],
+    workers: &[WorkerSnapshot],
+    scrub_paused: bool,
 ) -> SystemTrayMenu {
     let mut menu = SystemTrayMenu::new();
 
@@ -74,10 +80,16 @@ pub fn build_tray_menu(
             } else {
                 "—".to_string()
             };
-            let label = format!("{} · {} · {}", 
-                session.symbol, 
+            let priority_marker = match session.priority {
+                SessionPriority::Background => " (low priority)",
+                SessionPriority::Normal => "",
+                SessionPriority::Foreground => " (high priority)",
+            };
+            let label = format!("{} · {} · {}{}",
+                session.symbol,
                 hashrate_str,
-                session.status.to_uppercase()
+                session.status.to_uppercase(),
+                priority_marker,
             );
             
             // Session submenu
@@ -106,11 +118,38 @@ pub fn build_tray_menu(
     let preset_menu = SystemTrayMenu::new()
         .add_item(preset_item("eco", "Eco (~25% CPU)", preset))
         .add_item(preset_item("balanced", "Balanced (~50% CPU)", preset))
-        .add_item(preset_item("max", "Max (~75% CPU)", preset));
+        .add_item(preset_item("ludicrous", "Ludicrous (100% CPU)", preset));
     menu = menu.add_submenu(SystemTraySubmenu::new("Performance", preset_menu));
 
     menu = menu.add_native_item(SystemTrayMenuItem::Separator);
 
+    // Background Tasks submenu - lets users confirm at a glance that the
+    // stat-refresh/pool-health/history-flush/scrub loops are actually
+    // alive, and pause/resume the session scrub without opening the
+    // dashboard.
+    {
+        let mut tasks_menu = SystemTrayMenu::new();
+        for worker in workers {
+            let icon = match worker.health {
+                WorkerHealth::Active => "●",
+                WorkerHealth::Idle => "○",
+                WorkerHealth::Dead => "✕",
+            };
+            let label = format!("{} {} - {}", icon, worker.name, worker.status);
+            tasks_menu = tasks_menu.add_item(
+                CustomMenuItem::new(format!("worker_{}", worker.name), label).disabled(),
+            );
+        }
+        if !workers.is_empty() {
+            tasks_menu = tasks_menu.add_native_item(SystemTrayMenuItem::Separator);
+        }
+        let toggle_label = if scrub_paused { "▶ Resume Session Scrub" } else { "⏸ Pause Session Scrub" };
+        tasks_menu = tasks_menu.add_item(CustomMenuItem::new("scrub_toggle", toggle_label));
+
+        menu = menu.add_submenu(SystemTraySubmenu::new("Background Tasks", tasks_menu));
+        menu = menu.add_native_item(SystemTrayMenuItem::Separator);
+    }
+
     // Navigation
     menu = menu
         .add_item(CustomMenuItem::new("dashboard", "Open Dashboard").accelerator("CmdOrCtrl+D"))
@@ -174,6 +213,17 @@ pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
                     // Emit quit event so app can clean up (stop mining)
                     let _ = app.emit_all("tray-action", "quit");
                 }
+                "scrub_toggle" => {
+                    if let Some(scrub) = app.try_state::<openminedash_core::ScrubHandle>() {
+                        let command = if scrub.status().paused {
+                            openminedash_core::ScrubCommand::Start
+                        } else {
+                            openminedash_core::ScrubCommand::Pause
+                        };
+                        scrub.send(command);
+                    }
+                    let _ = app.emit_all("tray-action", "scrub:toggle");
+                }
                 id if id.starts_with("preset_") => {
                     let preset = id.trim_start_matches("preset_");
                     let _ = app.emit_all("tray-action", format!("preset:{}", preset));
@@ -197,6 +247,44 @@ pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
     }
 }
 
+/// Whether the session scrub loop is currently paused, if it's been
+/// `.manage()`d yet - `false` (i.e. "running") before it has.
+fn scrub_paused(app: &AppHandle) -> bool {
+    app.try_state::<openminedash_core::ScrubHandle>()
+        .map(|s| s.status().paused)
+        .unwrap_or(false)
+}
+
+/// Paint a live status readout directly onto the macOS menu-bar item
+/// itself (not just the dropdown), so health is visible without opening
+/// it - a colored dot for stopped/running/degraded plus a tiny sparkline
+/// of the last few aggregate hashrate samples.
+fn apply_live_tray_title(app: &AppHandle, total_hashrate: f64) {
+    let Some(tray) = app.tray_handle_by_id("main") else {
+        return;
+    };
+
+    if total_hashrate <= 0.0 {
+        let _ = tray.set_title("⚪ Stopped");
+        return;
+    }
+
+    let title = match app.try_state::<HashrateSparkline>() {
+        Some(sparkline) => {
+            sparkline.push(total_hashrate);
+            let dot = if sparkline.is_degraded() { "🔴" } else { "🟢" };
+            let history = sparkline.render();
+            if history.is_empty() {
+                format!("{} {:.0} H/s", dot, total_hashrate)
+            } else {
+                format!("{} {:.0} H/s {}", dot, total_hashrate, history)
+            }
+        }
+        None => format!("🟢 {:.0} H/s", total_hashrate),
+    };
+    let _ = tray.set_title(&title);
+}
+
 /// Update tray menu with new state (called from state changes)
 pub fn update_tray(
     app: &AppHandle,
@@ -206,10 +294,11 @@ pub fn update_tray(
     uptime: u64,
     preset: &str,
 ) {
-    let menu = build_tray_menu(is_running, hashrate, accepted, uptime, preset, &[]);
+    let menu = build_tray_menu(is_running, hashrate, accepted, uptime, preset, &[], &[], scrub_paused(app));
     if let Some(tray) = app.tray_handle_by_id("main") {
         let _ = tray.set_menu(menu);
     }
+    apply_live_tray_title(app, if is_running { hashrate } else { 0.0 });
 }
 
 /// Update tray with session info
@@ -220,8 +309,28 @@ pub fn update_tray_with_sessions(
 ) {
     let is_running = !sessions.is_empty();
     let total_hashrate: f64 = sessions.iter().map(|s| s.hashrate).sum();
-    let menu = build_tray_menu(is_running, total_hashrate, 0, 0, preset, &sessions);
+    let menu = build_tray_menu(is_running, total_hashrate, 0, 0, preset, &sessions, &[], scrub_paused(app));
+    if let Some(tray) = app.tray_handle_by_id("main") {
+        let _ = tray.set_menu(menu);
+    }
+    apply_live_tray_title(app, total_hashrate);
+}
+
+/// Update tray menu with the legacy single-session state plus the latest
+/// background worker snapshot, so "Background Tasks" stays current without
+/// a dedicated refresh path of its own.
+pub fn update_tray_with_workers(
+    app: &AppHandle,
+    is_running: bool,
+    hashrate: f64,
+    accepted: u64,
+    uptime: u64,
+    preset: &str,
+    workers: &[WorkerSnapshot],
+) {
+    let menu = build_tray_menu(is_running, hashrate, accepted, uptime, preset, &[], workers, scrub_paused(app));
     if let Some(tray) = app.tray_handle_by_id("main") {
         let _ = tray.set_menu(menu);
     }
+    apply_live_tray_title(app, if is_running { hashrate } else { 0.0 });
 }
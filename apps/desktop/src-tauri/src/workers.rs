@@ -0,0 +1,154 @@
+//! Concrete `Worker` implementations feeding `WorkerManager` - the
+//! stat-refresh, pool-health, and history-retention loops the tray's
+//! "Background Tasks" submenu and the `list_workers` command report on,
+//! instead of each living as its own unsupervised `tokio::spawn`.
+
+use crate::tray;
+use openminedash_core::{
+    AlertStore, AppState, MiningHistory, SessionManager, Worker, WorkerManager, WorkerState,
+};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+/// Refreshes multi-session stats (`SessionManager::refresh_all_stats`) and
+/// the legacy single-session `AppState` stats on a fixed cadence, so they
+/// stay current even while the frontend isn't polling (e.g. window hidden).
+/// Also repaints the tray, since it's already touching every piece of state
+/// (`AppState`, `WorkerManager`) the tray needs to stay in sync.
+pub struct StatsRefreshWorker {
+    state: Arc<Mutex<AppState>>,
+    sessions: Arc<Mutex<SessionManager>>,
+    alerts: Arc<Mutex<AlertStore>>,
+    worker_manager: WorkerManager,
+    app_handle: tauri::AppHandle,
+    last_status: String,
+}
+
+impl StatsRefreshWorker {
+    pub fn new(
+        state: Arc<Mutex<AppState>>,
+        sessions: Arc<Mutex<SessionManager>>,
+        alerts: Arc<Mutex<AlertStore>>,
+        worker_manager: WorkerManager,
+        app_handle: tauri::AppHandle,
+    ) -> Self {
+        Self { state, sessions, alerts, worker_manager, app_handle, last_status: "not yet run".to_string() }
+    }
+}
+
+impl Worker for StatsRefreshWorker {
+    fn name(&self) -> &str {
+        "stat-refresh"
+    }
+
+    fn status(&self) -> String {
+        self.last_status.clone()
+    }
+
+    async fn step(&mut self) -> WorkerState {
+        self.sessions.lock().await.refresh_all_stats(&self.alerts).await;
+        let _ = self.state.lock().await.refresh_stats(&self.app_handle).await;
+        let active = self.sessions.lock().await.active_count().await;
+        self.last_status = format!("refreshed {} active session(s)", active);
+
+        let state = self.state.lock().await;
+        let status = state.status().clone();
+        let preset = state.mining_mode().tray_label();
+        tray::update_tray_with_workers(
+            &self.app_handle,
+            status.is_running,
+            status.hashrate,
+            status.accepted_shares,
+            status.uptime,
+            preset,
+            &self.worker_manager.list_workers(),
+        );
+
+        WorkerState::Idle
+    }
+}
+
+/// Periodically checks the health of every pool referenced by a saved
+/// profile, so a dead pool surfaces in `list_workers`/the tray even when
+/// nothing is actively mining against it.
+pub struct PoolHealthWorker {
+    state: Arc<Mutex<AppState>>,
+    last_status: String,
+}
+
+impl PoolHealthWorker {
+    pub fn new(state: Arc<Mutex<AppState>>) -> Self {
+        Self { state, last_status: "not yet run".to_string() }
+    }
+}
+
+impl Worker for PoolHealthWorker {
+    fn name(&self) -> &str {
+        "pool-health"
+    }
+
+    fn status(&self) -> String {
+        self.last_status.clone()
+    }
+
+    async fn step(&mut self) -> WorkerState {
+        let pools: Vec<String> = {
+            let state = self.state.lock().await;
+            state.profiles().iter().map(|p| p.pool.clone()).collect()
+        };
+
+        if pools.is_empty() {
+            self.last_status = "no saved pools to check".to_string();
+            return WorkerState::Idle;
+        }
+
+        let mut healthy = 0;
+        for pool in &pools {
+            if let Ok(result) = openminedash_pools::check_health(pool).await {
+                if result.status == openminedash_pools::PoolStatus::Ok {
+                    healthy += 1;
+                }
+            }
+        }
+        self.last_status = format!("{healthy}/{} saved pool(s) healthy", pools.len());
+        WorkerState::Idle
+    }
+}
+
+/// Periodically re-applies the mining history retention policy - the same
+/// sweep `MiningHistory::add_record` already triggers on every write, just
+/// also guaranteed on a schedule through long idle stretches with no new
+/// records to trigger it.
+pub struct HistoryFlushWorker {
+    history: Arc<Mutex<MiningHistory>>,
+    last_status: String,
+}
+
+impl HistoryFlushWorker {
+    pub fn new(history: Arc<Mutex<MiningHistory>>) -> Self {
+        Self { history, last_status: "not yet run".to_string() }
+    }
+}
+
+impl Worker for HistoryFlushWorker {
+    fn name(&self) -> &str {
+        "history-flush"
+    }
+
+    fn status(&self) -> String {
+        self.last_status.clone()
+    }
+
+    async fn step(&mut self) -> WorkerState {
+        let mut history = self.history.lock().await;
+        let policy = history.retention_policy().clone();
+        history.set_retention_policy(policy);
+        let count = history.records().len();
+        drop(history);
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.last_status = format!("{count} record(s) persisted as of {now}");
+        WorkerState::Idle
+    }
+}
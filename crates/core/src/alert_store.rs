@@ -1,23 +1,53 @@
 //! Alert inbox for tracking notifications (including suppressed ones).
 //!
 //! Stores alerts in a ring buffer so users can see events that occurred
-//! during quiet hours or were deduplicated.
+//! during quiet hours or were deduplicated. Optionally persisted to disk
+//! (see [`AlertStore::load`]) so that history survives app restarts the
+//! same way the mining history / session-scrub background subsystems do.
 
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
 
 const MAX_ALERTS: usize = 100;
 
+/// Minimum time between persisted writes, so a burst of alerts (e.g. a
+/// flapping pool) doesn't turn into a disk write per event.
+const FLUSH_DEBOUNCE_MS: u64 = 2_000;
+
+/// Default retention window applied to persisted alerts at load time, so
+/// the file can't accumulate stale entries forever even if the inbox is
+/// rarely cleared.
+const DEFAULT_RETENTION_SECS: u64 = 30 * 24 * 60 * 60; // 30 days
+
 static ALERT_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
 fn next_alert_id() -> u64 {
     ALERT_ID_COUNTER.fetch_add(1, Ordering::SeqCst)
 }
 
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 /// Alert severity level
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Variant order is ascending severity (`Info` < `Warning` < `Error`) so
+/// `AlertQuery::min_severity` can filter with a plain `>=` comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AlertSeverity {
     Info,
@@ -49,10 +79,72 @@ pub struct Alert {
     pub suppressed_reason: Option<SuppressedReason>,
 }
 
+/// Structured filter for querying the alert inbox. Every field is optional
+/// and fields combine with AND - an unset field matches everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertQuery {
+    /// Only alerts at or above this severity.
+    pub min_severity: Option<AlertSeverity>,
+    /// Substring match against `alert_type`.
+    pub alert_type: Option<String>,
+    pub session_id: Option<String>,
+    pub coin_symbol: Option<String>,
+    /// `Some(true)` = only shown alerts, `Some(false)` = only suppressed/unshown.
+    pub shown: Option<bool>,
+    /// Unix timestamp, inclusive lower bound.
+    pub since_ts: Option<u64>,
+    /// Unix timestamp, inclusive upper bound.
+    pub until_ts: Option<u64>,
+}
+
+impl AlertQuery {
+    fn matches(&self, alert: &Alert) -> bool {
+        if let Some(min_severity) = self.min_severity {
+            if alert.severity < min_severity {
+                return false;
+            }
+        }
+        if let Some(alert_type) = &self.alert_type {
+            if !alert.alert_type.contains(alert_type.as_str()) {
+                return false;
+            }
+        }
+        if let Some(session_id) = &self.session_id {
+            if alert.session_id.as_deref() != Some(session_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(coin_symbol) = &self.coin_symbol {
+            if alert.coin_symbol.as_deref() != Some(coin_symbol.as_str()) {
+                return false;
+            }
+        }
+        if let Some(shown) = self.shown {
+            if alert.was_shown != shown {
+                return false;
+            }
+        }
+        if let Some(since_ts) = self.since_ts {
+            if alert.timestamp < since_ts {
+                return false;
+            }
+        }
+        if let Some(until_ts) = self.until_ts {
+            if alert.timestamp > until_ts {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Alert store with ring buffer
 pub struct AlertStore {
     alerts: VecDeque<Alert>,
     max_size: usize,
+    persist_path: Option<PathBuf>,
+    retention_secs: u64,
+    last_flush_ms: u64,
 }
 
 impl AlertStore {
@@ -60,9 +152,97 @@ impl AlertStore {
         Self {
             alerts: VecDeque::with_capacity(MAX_ALERTS),
             max_size: MAX_ALERTS,
+            persist_path: None,
+            retention_secs: DEFAULT_RETENTION_SECS,
+            last_flush_ms: 0,
         }
     }
 
+    /// Load the persisted inbox from the default app-data location (see
+    /// `MiningHistory::load`/`ConfigStore::load` for the equivalent
+    /// convention in other subsystems), applying `DEFAULT_RETENTION_SECS`.
+    pub fn load() -> Self {
+        Self::new_persisted(Self::default_path(), DEFAULT_RETENTION_SECS)
+    }
+
+    /// Load (or start fresh) a persisted inbox at `path`, dropping entries
+    /// older than `retention_secs` and seeding `ALERT_ID_COUNTER` from the
+    /// highest persisted id so new alerts never reuse one.
+    pub fn new_persisted(path: PathBuf, retention_secs: u64) -> Self {
+        let mut store = Self {
+            alerts: VecDeque::with_capacity(MAX_ALERTS),
+            max_size: MAX_ALERTS,
+            persist_path: Some(path.clone()),
+            retention_secs,
+            last_flush_ms: 0,
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<Vec<Alert>>(&content) {
+                Ok(alerts) => {
+                    let max_id = alerts.iter().map(|a| a.id).max().unwrap_or(0);
+                    ALERT_ID_COUNTER.store(max_id + 1, Ordering::SeqCst);
+
+                    let cutoff = now_secs().saturating_sub(retention_secs);
+                    store.alerts = alerts
+                        .into_iter()
+                        .filter(|a| a.timestamp >= cutoff)
+                        .collect();
+                }
+                Err(e) => warn!("Failed to parse persisted alert inbox at {:?}: {}", path, e),
+            },
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                warn!("Failed to read persisted alert inbox at {:?}: {}", path, e);
+            }
+            Err(_) => {}
+        }
+
+        store
+    }
+
+    fn default_path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("openminedash")
+            .join("alerts.json")
+    }
+
+    /// Write the ring buffer to disk, unless the last write was within
+    /// `FLUSH_DEBOUNCE_MS` or this store isn't persisted.
+    fn maybe_flush(&mut self) {
+        let Some(path) = self.persist_path.clone() else {
+            return;
+        };
+        let now = now_ms();
+        if now.saturating_sub(self.last_flush_ms) < FLUSH_DEBOUNCE_MS {
+            return;
+        }
+        self.last_flush_ms = now;
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                warn!("Failed to create alert inbox directory: {}", e);
+                return;
+            }
+        }
+        let snapshot: Vec<&Alert> = self.alerts.iter().collect();
+        match serde_json::to_string(&snapshot) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    warn!("Failed to persist alert inbox: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize alert inbox: {}", e),
+        }
+    }
+
+    /// Force an immediate write, bypassing the debounce - e.g. right before
+    /// the app exits, so the last few seconds of alerts aren't lost.
+    pub fn flush_now(&mut self) {
+        self.last_flush_ms = 0;
+        self.maybe_flush();
+    }
+
     /// Record a new alert
     pub fn record(
         &mut self,
@@ -76,10 +256,7 @@ impl AlertStore {
     ) -> Alert {
         let alert = Alert {
             id: next_alert_id(),
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            timestamp: now_secs(),
             alert_type: alert_type.to_string(),
             session_id: session_id.map(|s| s.to_string()),
             coin_symbol: coin_symbol.map(|s| s.to_string()),
@@ -95,6 +272,7 @@ impl AlertStore {
         }
         
         self.alerts.push_back(alert.clone());
+        self.maybe_flush();
         alert
     }
 
@@ -136,14 +314,36 @@ impl AlertStore {
         filtered
     }
 
+    /// List alerts (newest first) matching a structured query, for inbox
+    /// filtering that's too fine-grained for `list`'s `limit`/`since_id`.
+    pub fn query(&self, q: &AlertQuery, limit: usize) -> Vec<Alert> {
+        self.alerts
+            .iter()
+            .rev()
+            .filter(|a| q.matches(a))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
     /// Get unread count (suppressed alerts)
     pub fn unread_count(&self) -> usize {
         self.alerts.iter().filter(|a| !a.was_shown).count()
     }
 
+    /// Unread count restricted to alerts matching `q`, e.g. for a tray badge
+    /// scoped to one coin's unshown warnings.
+    pub fn unread_count_filtered(&self, q: &AlertQuery) -> usize {
+        self.alerts
+            .iter()
+            .filter(|a| !a.was_shown && q.matches(a))
+            .count()
+    }
+
     /// Clear all alerts
     pub fn clear(&mut self) {
         self.alerts.clear();
+        self.maybe_flush();
     }
 
     /// Mark all as read
@@ -151,6 +351,7 @@ impl AlertStore {
         for alert in &mut self.alerts {
             alert.was_shown = true;
         }
+        self.maybe_flush();
     }
 }
 
@@ -233,4 +434,103 @@ mod tests {
         let alerts = store.list(10, Some(a1.id));
         assert_eq!(alerts.len(), 2); // a2 and a3
     }
+
+    #[test]
+    fn test_query_filters_by_min_severity_and_coin() {
+        let mut store = AlertStore::new();
+
+        store.record_shown("pool_down", Some("sess1"), Some("XMR"), "info", AlertSeverity::Info);
+        store.record_shown("pool_down", Some("sess1"), Some("XMR"), "warn", AlertSeverity::Warning);
+        store.record_shown("pool_down", Some("sess2"), Some("VRSC"), "error", AlertSeverity::Error);
+
+        let query = AlertQuery {
+            min_severity: Some(AlertSeverity::Warning),
+            coin_symbol: Some("XMR".to_string()),
+            ..Default::default()
+        };
+        let results = store.query(&query, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "warn");
+    }
+
+    #[test]
+    fn test_query_unshown_only() {
+        let mut store = AlertStore::new();
+
+        store.record_shown("t", None, None, "shown", AlertSeverity::Info);
+        store.record_suppressed("t", None, None, "hidden", AlertSeverity::Info, SuppressedReason::QuietHours);
+
+        let query = AlertQuery {
+            shown: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(store.unread_count_filtered(&query), 1);
+        let results = store.query(&query, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "hidden");
+    }
+
+    #[test]
+    fn test_query_alert_type_substring() {
+        let mut store = AlertStore::new();
+
+        store.record_shown("scrub_pool_health_degraded", None, None, "a", AlertSeverity::Warning);
+        store.record_shown("scrub_hashrate_drift", None, None, "b", AlertSeverity::Warning);
+
+        let query = AlertQuery {
+            alert_type: Some("hashrate".to_string()),
+            ..Default::default()
+        };
+        let results = store.query(&query, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "b");
+    }
+
+    fn temp_alert_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("openminedash_alert_store_test_{}.json", name))
+    }
+
+    #[test]
+    fn test_persist_round_trip_and_seeds_id_counter() {
+        let path = temp_alert_path("round_trip");
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let mut store = AlertStore::new_persisted(path.clone(), DEFAULT_RETENTION_SECS);
+            store.flush_now(); // nothing recorded yet, writes an empty inbox
+            let alert = store.record_shown("t", None, None, "persisted", AlertSeverity::Info);
+            store.flush_now();
+            assert!(alert.id > 0);
+        }
+
+        let reloaded = AlertStore::new_persisted(path.clone(), DEFAULT_RETENTION_SECS);
+        let results = reloaded.query(&AlertQuery::default(), 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message, "persisted");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_persist_prunes_entries_past_retention() {
+        let path = temp_alert_path("retention");
+        let stale = Alert {
+            id: 1,
+            timestamp: now_secs().saturating_sub(1000),
+            alert_type: "t".to_string(),
+            session_id: None,
+            coin_symbol: None,
+            message: "stale".to_string(),
+            severity: AlertSeverity::Info,
+            was_shown: true,
+            suppressed_reason: None,
+        };
+        std::fs::write(&path, serde_json::to_string(&vec![stale]).unwrap()).unwrap();
+
+        // Retention window shorter than how old the fixture is, so it's pruned on load.
+        let store = AlertStore::new_persisted(path.clone(), 10);
+        assert_eq!(store.query(&AlertQuery::default(), 10).len(), 0);
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
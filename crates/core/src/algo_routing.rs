@@ -126,9 +126,17 @@ pub enum MinerType {
     XMRig,
     CpuminerOpt,
     External,
+    StratumV2,
     Unsupported,
 }
 
+/// True if `pool` addresses a Stratum V2 endpoint via the `sv2://` URL
+/// scheme, as opposed to classic Stratum V1 (`stratum+tcp://`, or no scheme
+/// at all).
+pub fn is_stratum_v2_pool(pool: &str) -> bool {
+    pool.to_lowercase().starts_with("sv2://")
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoutingResult {
     pub miner_type: MinerType,
@@ -196,6 +204,44 @@ pub fn route_algorithm(algo: &str, try_anyway: bool) -> RoutingResult {
     }
 }
 
+/// Like `route_algorithm`, but first checks whether the pool should be
+/// reached over Stratum V2 - either via the `sv2://` scheme or an explicit
+/// protocol override - in which case routing goes to the Stratum V2 adapter
+/// instead of whichever miner the algorithm alone would pick. SV2 is a
+/// transport, not a hashing engine: the adapter still runs XMRig locally, so
+/// only XMRig-supported algorithms can be routed this way today.
+pub fn route_with_protocol(
+    algo: &str,
+    try_anyway: bool,
+    pool: &str,
+    explicit_protocol: Option<&str>,
+) -> RoutingResult {
+    let wants_sv2 = is_stratum_v2_pool(pool)
+        || explicit_protocol.map(|p| p.eq_ignore_ascii_case("sv2")).unwrap_or(false);
+    if !wants_sv2 {
+        return route_algorithm(algo, try_anyway);
+    }
+
+    let algo_lower = algo.to_lowercase();
+    if is_xmrig_supported(&algo_lower) {
+        RoutingResult {
+            miner_type: MinerType::StratumV2,
+            algorithm: algo_lower,
+            warning: None,
+            is_practical: true,
+        }
+    } else {
+        RoutingResult {
+            miner_type: MinerType::Unsupported,
+            algorithm: algo_lower,
+            warning: Some(
+                "Stratum V2 pools are only supported for XMRig-supported algorithms right now.".to_string(),
+            ),
+            is_practical: false,
+        }
+    }
+}
+
 fn is_xmrig_supported(algo: &str) -> bool {
     XMRIG_ALGORITHMS.iter().any(|a| {
         a.eq_ignore_ascii_case(algo) || 
@@ -274,4 +320,29 @@ mod tests {
         assert_eq!(result.miner_type, MinerType::XMRig);
         assert!(result.is_practical);
     }
+
+    #[test]
+    fn test_stratum_v2_scheme_routing() {
+        let result = route_with_protocol("randomx", false, "sv2://pool.example:3336", None);
+        assert_eq!(result.miner_type, MinerType::StratumV2);
+        assert!(result.is_practical);
+    }
+
+    #[test]
+    fn test_stratum_v2_explicit_override() {
+        let result = route_with_protocol("rx/0", false, "pool.example:3333", Some("sv2"));
+        assert_eq!(result.miner_type, MinerType::StratumV2);
+    }
+
+    #[test]
+    fn test_stratum_v2_unsupported_algorithm() {
+        let result = route_with_protocol("sha256d", false, "sv2://pool.example:3336", None);
+        assert_eq!(result.miner_type, MinerType::Unsupported);
+    }
+
+    #[test]
+    fn test_non_sv2_pool_falls_back_to_normal_routing() {
+        let result = route_with_protocol("randomx", false, "pool.example:3333", None);
+        assert_eq!(result.miner_type, MinerType::XMRig);
+    }
 }
@@ -0,0 +1,253 @@
+//! Idle-triggered auto-mining.
+//!
+//! Samples global mouse/keyboard activity at ~1Hz via `device_query` (the
+//! same polling approach cross-platform screensavers use - no OS-specific
+//! hooks here). Once `idle_threshold_secs` passes with no activity, any
+//! sessions the user had suspended are resumed; the moment activity
+//! reappears, running sessions are suspended again (or fully stopped, per
+//! `resume_on_activity`) so mining never competes with interactive use.
+
+use crate::{AlertSeverity, AlertStore, SessionManager, SessionStatus};
+use device_query::{DeviceQuery, DeviceState};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// How often the input poller samples mouse position and key state.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Persisted auto-mining settings (lives in `AppConfig` alongside
+/// `thread_budget`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoMiningSettings {
+    pub enabled: bool,
+    pub idle_threshold_secs: u64,
+    /// On activity: suspend sessions (resumable next idle period) if true,
+    /// or fully stop them if false.
+    pub resume_on_activity: bool,
+}
+
+impl Default for AutoMiningSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false, // Opt-in, like notifications/metrics
+            idle_threshold_secs: 300,
+            resume_on_activity: true,
+        }
+    }
+}
+
+/// Live idle/engagement snapshot, polled by `get_auto_mining_status`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutoMiningStatus {
+    pub idle: bool,
+    pub idle_secs: u64,
+    pub auto_mining_active: bool,
+}
+
+struct AutoMinerState {
+    settings: AutoMiningSettings,
+    last_activity: Instant,
+    auto_mining_active: bool,
+}
+
+/// Shared handle to the auto-miner's idle state - cheap to clone, every
+/// clone sees the same underlying state, same as `StatsCollector`.
+#[derive(Clone)]
+pub struct AutoMinerHandle {
+    inner: Arc<StdMutex<AutoMinerState>>,
+}
+
+impl AutoMinerHandle {
+    pub fn new(settings: AutoMiningSettings) -> Self {
+        Self {
+            inner: Arc::new(StdMutex::new(AutoMinerState {
+                settings,
+                last_activity: Instant::now(),
+                auto_mining_active: false,
+            })),
+        }
+    }
+
+    pub fn update_settings(&self, settings: AutoMiningSettings) {
+        self.inner.lock().unwrap().settings = settings;
+    }
+
+    pub fn status(&self) -> AutoMiningStatus {
+        let inner = self.inner.lock().unwrap();
+        let idle_secs = inner.last_activity.elapsed().as_secs();
+        AutoMiningStatus {
+            idle: idle_secs >= inner.settings.idle_threshold_secs,
+            idle_secs,
+            auto_mining_active: inner.auto_mining_active,
+        }
+    }
+}
+
+impl Default for AutoMinerHandle {
+    fn default() -> Self {
+        Self::new(AutoMiningSettings::default())
+    }
+}
+
+/// Spawn the background input poller and idle/activity transition logic.
+/// The poller itself always runs; `settings.enabled` (checked fresh every
+/// tick, so toggling takes effect without a restart) gates whether a
+/// transition actually touches any session.
+pub fn spawn_auto_miner(
+    handle: AutoMinerHandle,
+    sessions: Arc<Mutex<SessionManager>>,
+    alerts: Arc<Mutex<AlertStore>>,
+) {
+    tokio::spawn(async move {
+        let device_state = DeviceState::new();
+        let mut last_mouse = device_state.get_mouse().coords;
+        let mut was_idle = false;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+
+            let mouse = device_state.get_mouse().coords;
+            let keys = device_state.get_keys();
+            let activity = mouse != last_mouse || !keys.is_empty();
+            last_mouse = mouse;
+
+            if activity {
+                handle.inner.lock().unwrap().last_activity = Instant::now();
+            }
+
+            let (enabled, is_idle, auto_mining_active) = {
+                let inner = handle.inner.lock().unwrap();
+                let idle_secs = inner.last_activity.elapsed().as_secs();
+                (
+                    inner.settings.enabled,
+                    idle_secs >= inner.settings.idle_threshold_secs,
+                    inner.auto_mining_active,
+                )
+            };
+
+            if enabled {
+                if is_idle && !was_idle {
+                    engage(&handle, &sessions, &alerts).await;
+                } else if !is_idle && was_idle && auto_mining_active {
+                    disengage(&handle, &sessions, &alerts).await;
+                }
+            }
+            was_idle = is_idle;
+        }
+    });
+}
+
+/// User just went idle: resume every session they'd left suspended.
+async fn engage(handle: &AutoMinerHandle, sessions: &Arc<Mutex<SessionManager>>, alerts: &Arc<Mutex<AlertStore>>) {
+    let manager = sessions.lock().await;
+    let summaries = manager.list_sessions().await;
+    let mut resumed = 0;
+    for summary in summaries {
+        if summary.stats.status == SessionStatus::Suspended && manager.resume_session(&summary.id).await.is_ok() {
+            resumed += 1;
+        }
+    }
+    drop(manager);
+
+    handle.inner.lock().unwrap().auto_mining_active = true;
+    info!("Auto-mining engaged: resumed {} idle-suspended session(s)", resumed);
+    alerts.lock().await.record(
+        "auto_mining_engaged",
+        None,
+        None,
+        &format!("Auto-mining engaged: resumed {} session(s) while the machine is idle", resumed),
+        AlertSeverity::Info,
+        true,
+        None,
+    );
+}
+
+/// User is back: suspend (or fully stop) every session auto-mining is
+/// responsible for.
+async fn disengage(handle: &AutoMinerHandle, sessions: &Arc<Mutex<SessionManager>>, alerts: &Arc<Mutex<AlertStore>>) {
+    let resume_on_activity = handle.inner.lock().unwrap().settings.resume_on_activity;
+
+    let manager = sessions.lock().await;
+    let summaries = manager.list_sessions().await;
+    let mut affected = 0;
+    for summary in summaries {
+        if summary.stats.status != SessionStatus::Running {
+            continue;
+        }
+        let result = if resume_on_activity {
+            manager.suspend_session(&summary.id).await
+        } else {
+            manager.stop_session(&summary.id).await
+        };
+        if result.is_ok() {
+            affected += 1;
+        }
+    }
+    drop(manager);
+
+    handle.inner.lock().unwrap().auto_mining_active = false;
+    let action = if resume_on_activity { "suspended" } else { "stopped" };
+    info!("Auto-mining disengaged: {} {} session(s)", action, affected);
+    alerts.lock().await.record(
+        "auto_mining_disengaged",
+        None,
+        None,
+        &format!("Auto-mining disengaged: {} {} session(s) on user activity", action, affected),
+        AlertSeverity::Info,
+        true,
+        None,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_mining_settings_default() {
+        let settings = AutoMiningSettings::default();
+        assert!(!settings.enabled);
+        assert_eq!(settings.idle_threshold_secs, 300);
+        assert!(settings.resume_on_activity);
+    }
+
+    #[test]
+    fn test_status_not_idle_before_threshold() {
+        let handle = AutoMinerHandle::new(AutoMiningSettings {
+            enabled: true,
+            idle_threshold_secs: 300,
+            resume_on_activity: true,
+        });
+
+        let status = handle.status();
+        assert!(!status.idle);
+        assert!(!status.auto_mining_active);
+    }
+
+    #[test]
+    fn test_status_idle_once_threshold_elapsed() {
+        let handle = AutoMinerHandle::new(AutoMiningSettings {
+            enabled: true,
+            idle_threshold_secs: 0,
+            resume_on_activity: true,
+        });
+
+        let status = handle.status();
+        assert!(status.idle);
+    }
+
+    #[test]
+    fn test_update_settings_replaces_idle_threshold() {
+        let handle = AutoMinerHandle::default();
+        handle.update_settings(AutoMiningSettings {
+            enabled: true,
+            idle_threshold_secs: 0,
+            resume_on_activity: false,
+        });
+
+        assert!(handle.status().idle);
+    }
+}
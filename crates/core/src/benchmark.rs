@@ -1,5 +1,9 @@
 //! In-app benchmark for measuring hashrate on user's hardware.
 
+use crate::algo_routing::{route_algorithm, MinerType};
+use openminedash_miner_adapters::{
+    CpuminerOptAdapter, MinerBackend, MiningConfig as AdapterMiningConfig, XMRigAdapter,
+};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -8,6 +12,10 @@ use tracing::info;
 const BENCHMARK_DURATION_SECS: u64 = 60;
 const SAMPLE_INTERVAL_SECS: u64 = 5;
 
+/// How long to sample each algorithm during a [`sweep`] - short compared to
+/// [`BENCHMARK_DURATION_SECS`] since it runs once per candidate coin.
+const SWEEP_SAMPLE_SECS: u64 = 15;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     pub duration_secs: u64,
@@ -18,6 +26,46 @@ pub struct BenchmarkResult {
     pub recommended_preset: String,
     pub recommended_threads: u32,
     pub hardware_info: HardwareInfo,
+    /// Results of an additional low-priority ("background") sample pass, if
+    /// one was run alongside the normal presets.
+    #[serde(default)]
+    pub background: Option<BackgroundBenchmark>,
+    /// Expected time-to-share/time-to-block at this hardware's hashrate, if
+    /// pool/network difficulty was supplied for the routed coin.
+    #[serde(default)]
+    pub profitability: Option<ProfitabilityEstimate>,
+}
+
+/// A background-mode (`MiningConfig::lower_priority`) sample pass, reported
+/// next to the normal presets so users can see what responsiveness costs
+/// in hashrate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundBenchmark {
+    pub avg_hashrate: f64,
+    pub penalty_pct: f64,
+}
+
+/// A 4-byte compact difficulty target ("nBits"): the high byte is a base-256
+/// exponent, the low three bytes are the mantissa. Standard Bitcoin-family
+/// proof-of-work header encoding, shared by most algorithms `route_algorithm`
+/// understands.
+pub type CompactTarget = u32;
+
+/// Expected time-to-share and time-to-block for the routed coin, given this
+/// hardware's measured hashrate - a realistic "you'd find a share roughly
+/// every N minutes" figure rather than a bare H/s number. Most useful for
+/// the ASIC-dominated algorithms `route_algorithm` already flags as
+/// impractical on CPU.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitabilityEstimate {
+    pub pool_difficulty: f64,
+    pub network_difficulty: f64,
+    /// Expected seconds between shares at pool difficulty. `None` if
+    /// `avg_hashrate` was zero.
+    pub seconds_per_share: Option<f64>,
+    /// Expected seconds between blocks at network difficulty. `None` if
+    /// `avg_hashrate` was zero.
+    pub seconds_per_block: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,9 +99,79 @@ impl BenchmarkResult {
         };
         
         let recommended_threads = ((hardware.cpu_threads as f64) * thread_ratio).max(1.0) as u32;
-        
+
         (preset.to_string(), recommended_threads)
     }
+
+    /// Build a `BackgroundBenchmark` from a normal-priority average hashrate
+    /// and a second sample pass taken with `lower_priority` enabled.
+    pub fn background_result(normal_avg_hashrate: f64, background_avg_hashrate: f64) -> BackgroundBenchmark {
+        BackgroundBenchmark {
+            avg_hashrate: background_avg_hashrate,
+            penalty_pct: Self::background_penalty_pct(normal_avg_hashrate, background_avg_hashrate),
+        }
+    }
+
+    /// How much hashrate background (low-priority) mode costs relative to a
+    /// normal sample pass, as a percentage drop. Zero if background mode
+    /// somehow matched or beat the normal pass.
+    fn background_penalty_pct(normal_avg_hashrate: f64, background_avg_hashrate: f64) -> f64 {
+        if normal_avg_hashrate <= 0.0 {
+            return 0.0;
+        }
+        ((normal_avg_hashrate - background_avg_hashrate) / normal_avg_hashrate * 100.0).max(0.0)
+    }
+
+    /// Build a [`ProfitabilityEstimate`] from this hardware's measured
+    /// hashrate plus the pool's and network's compact difficulty targets.
+    pub fn profitability_estimate(
+        avg_hashrate: f64,
+        pool_nbits: CompactTarget,
+        network_nbits: CompactTarget,
+    ) -> ProfitabilityEstimate {
+        let pool_difficulty = difficulty_from_nbits(pool_nbits);
+        let network_difficulty = difficulty_from_nbits(network_nbits);
+        ProfitabilityEstimate {
+            pool_difficulty,
+            network_difficulty,
+            seconds_per_share: expected_seconds_for_difficulty(avg_hashrate, pool_difficulty),
+            seconds_per_block: expected_seconds_for_difficulty(avg_hashrate, network_difficulty),
+        }
+    }
+}
+
+/// Expand a compact nBits target into its 256-bit target value (as an f64 -
+/// full bignum precision isn't needed for a difficulty estimate, and no
+/// bignum type is otherwise used in this crate).
+fn compact_target_to_f64(nbits: CompactTarget) -> f64 {
+    let exponent = (nbits >> 24) as i32;
+    let mantissa = (nbits & 0x00ff_ffff) as f64;
+    mantissa * 2f64.powi(8 * (exponent - 3))
+}
+
+/// Difficulty-1's exponent/mantissa, as used by the Bitcoin-family coins
+/// `route_algorithm` targets (exponent 0x1d, mantissa 0x00ffff).
+const DIFFICULTY_ONE_NBITS: CompactTarget = 0x1d00_ffff;
+
+/// Convert a compact nBits target into a difficulty value, i.e.
+/// `max_target / current_target` relative to the conventional difficulty-1
+/// target.
+pub fn difficulty_from_nbits(nbits: CompactTarget) -> f64 {
+    let current_target = compact_target_to_f64(nbits);
+    if current_target <= 0.0 {
+        return 0.0;
+    }
+    compact_target_to_f64(DIFFICULTY_ONE_NBITS) / current_target
+}
+
+/// Expected seconds to find a share/block at `difficulty`, assuming
+/// `avg_hashrate` H/s and the conventional "difficulty 1 takes ~2^32
+/// hashes on average" approximation. `None` if `avg_hashrate` is zero.
+fn expected_seconds_for_difficulty(avg_hashrate: f64, difficulty: f64) -> Option<f64> {
+    if avg_hashrate <= 0.0 {
+        return None;
+    }
+    Some(difficulty * 2f64.powi(32) / avg_hashrate)
 }
 
 /// Get hardware info for benchmark context
@@ -82,6 +200,152 @@ fn get_cpu_brand() -> String {
     }
 }
 
+/// A candidate algorithm/coin pairing for a [`sweep`] run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepCandidate {
+    pub coin: String,
+    pub algorithm: String,
+}
+
+/// One coin's result within a [`sweep`] run, annotated with its routing
+/// outcome so impractical ASIC coins are shown but de-ranked rather than
+/// silently dropped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepEntry {
+    pub coin: String,
+    pub algorithm: String,
+    pub miner_type: MinerType,
+    pub warning: Option<String>,
+    pub is_practical: bool,
+    pub result: BenchmarkResult,
+}
+
+/// Benchmark every algorithm in `candidates` that `route_algorithm` can
+/// actually run against the pool in `base_config` (`pool`/`wallet`/`worker`/
+/// `preset` carry over from it; `coin` is overridden per candidate with the
+/// routed algorithm), and return results ranked best-first.
+///
+/// Algorithms `route_algorithm` can't run at all on CPU (`Unsupported`, or
+/// GPU-only ones routed to `External`) are skipped entirely;
+/// ASIC-dominated algorithms it flags as impractical are still benchmarked
+/// but sorted to the bottom, annotated with `route_algorithm`'s warning.
+pub async fn sweep(
+    candidates: &[SweepCandidate],
+    base_config: &AdapterMiningConfig,
+    try_anyway: bool,
+    app_handle: tauri::AppHandle,
+) -> Vec<SweepEntry> {
+    let hardware = get_hardware_info();
+    let mut entries = Vec::new();
+
+    for candidate in candidates {
+        let routing = route_algorithm(&candidate.algorithm, try_anyway);
+        if matches!(routing.miner_type, MinerType::Unsupported | MinerType::External) {
+            continue;
+        }
+
+        let mut config = base_config.clone();
+        config.coin = routing.algorithm.clone();
+
+        let (avg_hashrate, sample_warning) = match routing.miner_type {
+            MinerType::XMRig => sample_xmrig(&config, app_handle.clone()).await,
+            MinerType::CpuminerOpt => sample_cpuminer(&config, app_handle.clone()).await,
+            // route_algorithm (unlike route_with_protocol) never resolves to
+            // StratumV2, and Unsupported/External were already skipped above.
+            MinerType::StratumV2 | MinerType::Unsupported | MinerType::External => unreachable!(),
+        };
+
+        let (recommended_preset, recommended_threads) =
+            BenchmarkResult::generate_recommendation(avg_hashrate, &hardware);
+
+        let result = BenchmarkResult {
+            duration_secs: SWEEP_SAMPLE_SECS,
+            samples: vec![avg_hashrate],
+            avg_hashrate,
+            peak_hashrate: avg_hashrate,
+            min_hashrate: avg_hashrate,
+            recommended_preset,
+            recommended_threads,
+            hardware_info: hardware.clone(),
+            background: None,
+            profitability: None,
+        };
+
+        entries.push(SweepEntry {
+            coin: candidate.coin.clone(),
+            algorithm: routing.algorithm,
+            miner_type: routing.miner_type,
+            warning: sample_warning.or(routing.warning),
+            is_practical: routing.is_practical,
+            result,
+        });
+    }
+
+    rank_sweep_entries(&mut entries);
+    entries
+}
+
+/// Sort sweep entries best-first: practical coins before impractical ones,
+/// each group ordered by descending `avg_hashrate`.
+fn rank_sweep_entries(entries: &mut [SweepEntry]) {
+    entries.sort_by(|a, b| {
+        b.is_practical.cmp(&a.is_practical).then(
+            b.result
+                .avg_hashrate
+                .partial_cmp(&a.result.avg_hashrate)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+}
+
+/// Run XMRig against `config` for [`SWEEP_SAMPLE_SECS`] and return its
+/// measured hashrate, plus a warning if the binary couldn't be run at all.
+async fn sample_xmrig(config: &AdapterMiningConfig, app_handle: tauri::AppHandle) -> (f64, Option<String>) {
+    let mut adapter = XMRigAdapter::new();
+    if let Err(e) = adapter.ensure_binary().await {
+        return (0.0, Some(format!("XMRig unavailable: {}", e)));
+    }
+
+    info!("Sweep: sampling XMRig for algorithm {}", config.coin);
+    let mut child = match adapter.start(config, app_handle).await {
+        Ok(child) => child,
+        Err(e) => return (0.0, Some(format!("Failed to start XMRig: {}", e))),
+    };
+
+    tokio::time::sleep(Duration::from_secs(SWEEP_SAMPLE_SECS)).await;
+    let hashrate = MinerBackend::get_stats(&adapter)
+        .await
+        .map(|s| s.hashrate)
+        .unwrap_or(0.0);
+    adapter.stop(&mut child).await;
+
+    (hashrate, None)
+}
+
+/// Run cpuminer-opt against `config` for [`SWEEP_SAMPLE_SECS`] and return
+/// its measured hashrate, plus a warning if the binary couldn't be run.
+async fn sample_cpuminer(config: &AdapterMiningConfig, app_handle: tauri::AppHandle) -> (f64, Option<String>) {
+    let mut adapter = CpuminerOptAdapter::new();
+    if let Err(e) = adapter.ensure_binary().await {
+        return (0.0, Some(format!("cpuminer-opt unavailable: {}", e)));
+    }
+
+    info!("Sweep: sampling cpuminer-opt for algorithm {}", config.coin);
+    let mut child = match adapter.start(config, app_handle).await {
+        Ok(child) => child,
+        Err(e) => return (0.0, Some(format!("Failed to start cpuminer-opt: {}", e))),
+    };
+
+    tokio::time::sleep(Duration::from_secs(SWEEP_SAMPLE_SECS)).await;
+    let hashrate = MinerBackend::get_stats(&adapter)
+        .await
+        .map(|s| s.hashrate)
+        .unwrap_or(0.0);
+    adapter.stop(&mut child).await;
+
+    (hashrate, None)
+}
+
 /// Expected hashrates for Apple Silicon (for UI display)
 pub fn get_expected_hashrates() -> Vec<(&'static str, u32, u32, u32)> {
     // (chip, eco, balanced, max)
@@ -131,4 +395,86 @@ mod tests {
         assert!(info.cpu_cores > 0);
         assert!(info.cpu_threads >= info.cpu_cores);
     }
+
+    #[test]
+    fn test_background_result_reports_penalty() {
+        let background = BenchmarkResult::background_result(1000.0, 700.0);
+        assert_eq!(background.avg_hashrate, 700.0);
+        assert!((background.penalty_pct - 30.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_background_result_never_negative() {
+        // Background pass matching or beating the normal pass shouldn't
+        // report a negative "penalty".
+        let background = BenchmarkResult::background_result(1000.0, 1200.0);
+        assert_eq!(background.penalty_pct, 0.0);
+    }
+
+    #[test]
+    fn test_difficulty_one_nbits_is_difficulty_one() {
+        assert!((difficulty_from_nbits(DIFFICULTY_ONE_NBITS) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_higher_difficulty_doubles_time_to_share() {
+        // Halving the mantissa (same exponent) doubles the difficulty, and
+        // with it the expected time to find a share.
+        let easy = expected_seconds_for_difficulty(1000.0, difficulty_from_nbits(0x1d00_fffe)).unwrap();
+        let hard = expected_seconds_for_difficulty(1000.0, difficulty_from_nbits(0x1d00_7fff)).unwrap();
+        assert!((hard / easy - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_profitability_estimate_scales_with_hashrate() {
+        let estimate = BenchmarkResult::profitability_estimate(1000.0, DIFFICULTY_ONE_NBITS, DIFFICULTY_ONE_NBITS);
+        assert_eq!(estimate.pool_difficulty, 1.0);
+        assert_eq!(estimate.seconds_per_share, estimate.seconds_per_block);
+        assert!(estimate.seconds_per_share.unwrap() > 0.0);
+
+        let no_hashrate = BenchmarkResult::profitability_estimate(0.0, DIFFICULTY_ONE_NBITS, DIFFICULTY_ONE_NBITS);
+        assert_eq!(no_hashrate.seconds_per_share, None);
+    }
+
+    fn dummy_sweep_entry(coin: &str, avg_hashrate: f64, is_practical: bool) -> SweepEntry {
+        let hardware = HardwareInfo {
+            cpu_brand: "Test CPU".to_string(),
+            cpu_cores: 4,
+            cpu_threads: 8,
+        };
+        SweepEntry {
+            coin: coin.to_string(),
+            algorithm: coin.to_string(),
+            miner_type: MinerType::CpuminerOpt,
+            warning: None,
+            is_practical,
+            result: BenchmarkResult {
+                duration_secs: SWEEP_SAMPLE_SECS,
+                samples: vec![avg_hashrate],
+                avg_hashrate,
+                peak_hashrate: avg_hashrate,
+                min_hashrate: avg_hashrate,
+                recommended_preset: "balanced".to_string(),
+                recommended_threads: 4,
+                hardware_info: hardware,
+                background: None,
+                profitability: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_rank_sweep_entries_orders_practical_first_then_by_hashrate() {
+        let mut entries = vec![
+            dummy_sweep_entry("btc", 50.0, false),
+            dummy_sweep_entry("xmr", 1000.0, true),
+            dummy_sweep_entry("xmr-slow", 500.0, true),
+        ];
+
+        rank_sweep_entries(&mut entries);
+
+        assert_eq!(entries[0].coin, "xmr");
+        assert_eq!(entries[1].coin, "xmr-slow");
+        assert_eq!(entries[2].coin, "btc");
+    }
 }
@@ -1,4 +1,5 @@
-use crate::{Profile, Result, ThreadBudgetSettings};
+use crate::{AutoMiningSettings, HeadlessConfig, IpcConfig, LoggingSettings, MetricsConfig, MiningMode, Profile, Result, ScrubSettings, TelemetrySettings, ThreadBudgetSettings};
+use openminedash_pools::{PoolTemplate, WalletRpcConfig};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -29,7 +30,37 @@ pub struct AppConfig {
     #[serde(default)]
     pub thread_budget: ThreadBudgetSettings,
     #[serde(default)]
+    pub mining_mode: MiningMode,
+    #[serde(default)]
+    pub auto_mining: AutoMiningSettings,
+    #[serde(default)]
     pub behavior: BehaviorSettings,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub wallet_rpc: WalletRpcConfig,
+    /// User-defined pool adapters, for APIs without a built-in fetcher.
+    #[serde(default)]
+    pub pool_templates: Vec<PoolTemplate>,
+    /// Local JSON-RPC control socket for running without the GUI.
+    #[serde(default)]
+    pub headless: HeadlessConfig,
+    /// Local JSON-RPC/IPC socket exposing sessions, history, and crash
+    /// recovery state to external tools and scripts.
+    #[serde(default)]
+    pub ipc: IpcConfig,
+    /// Tick cadence and alerting thresholds for the rolling hashrate/share
+    /// statistics subsystem (see `telemetry::StatsTracker`).
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+    /// Terminal/file split for the structured logging subsystem (see
+    /// `logging::init_logging`).
+    #[serde(default)]
+    pub logging: LoggingSettings,
+    /// Throttle and last-run bookkeeping for the session scrub worker (see
+    /// `session_scrub::spawn_session_scrub`).
+    #[serde(default)]
+    pub scrub: ScrubSettings,
 }
 
 impl Default for AppConfig {
@@ -40,7 +71,17 @@ impl Default for AppConfig {
             profiles: Vec::new(),
             custom_binary_path: None,
             thread_budget: ThreadBudgetSettings::default(),
+            mining_mode: MiningMode::default(),
+            auto_mining: AutoMiningSettings::default(),
             behavior: BehaviorSettings::default(),
+            metrics: MetricsConfig::default(),
+            wallet_rpc: WalletRpcConfig::default(),
+            pool_templates: Vec::new(),
+            headless: HeadlessConfig::default(),
+            ipc: IpcConfig::default(),
+            telemetry: TelemetrySettings::default(),
+            logging: LoggingSettings::default(),
+            scrub: ScrubSettings::default(),
         }
     }
 }
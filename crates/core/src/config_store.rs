@@ -0,0 +1,125 @@
+//! Embedded SQLite-backed `AppConfig` store.
+//!
+//! `get_thread_budget_settings`/`set_thread_budget_settings`/
+//! `get_budget_status`/`export_diagnostics` used to call `AppConfig::load()`
+//! fresh on every invocation - re-reading and re-parsing the config file
+//! each time, and free to race with whatever `AppState` already holds in
+//! memory. `ConfigStore` instead loads the config once at startup from an
+//! embedded SQLite database (`libsqlite3-sys`, bundled) and keeps it behind
+//! a single `tokio::sync::RwLock`: reads take the cached value, writes
+//! update the cache and the database in the same critical section, so no
+//! reader ever observes a write that only made it to one of the two.
+
+use crate::{AppConfig, CoreError, Result};
+use rusqlite::{Connection, OptionalExtension};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::RwLock;
+
+/// Schema version for the `config` table - bump and add a one-time
+/// transform in `migrate` whenever the stored JSON shape changes in a way
+/// that needs more than `#[serde(default)]` to read back cleanly.
+const SCHEMA_VERSION: i32 = 1;
+
+fn sqlite_err(e: rusqlite::Error) -> CoreError {
+    CoreError::Io(std::io::Error::other(e.to_string()))
+}
+
+fn db_path() -> std::path::PathBuf {
+    AppConfig::config_dir().join("config.sqlite3")
+}
+
+fn open_connection() -> Result<Connection> {
+    let path = db_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let conn = Connection::open(path).map_err(sqlite_err)?;
+    migrate(&conn)?;
+    Ok(conn)
+}
+
+fn migrate(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS config (
+            id INTEGER PRIMARY KEY CHECK (id = 0),
+            schema_version INTEGER NOT NULL,
+            data TEXT NOT NULL
+        );",
+    )
+    .map_err(sqlite_err)
+}
+
+fn read_config(conn: &Connection) -> Result<AppConfig> {
+    let data: Option<String> = conn
+        .query_row("SELECT data FROM config WHERE id = 0", [], |row| row.get(0))
+        .optional()
+        .map_err(sqlite_err)?;
+
+    match data {
+        Some(json) => Ok(serde_json::from_str(&json)?),
+        None => {
+            // First run against this database - seed it from the legacy
+            // file-backed config (or defaults) so upgrading doesn't lose
+            // existing settings, then persist under the new store.
+            let config = AppConfig::load().unwrap_or_default();
+            write_config(conn, &config)?;
+            Ok(config)
+        }
+    }
+}
+
+fn write_config(conn: &Connection, config: &AppConfig) -> Result<()> {
+    let json = serde_json::to_string(config)?;
+    conn.execute(
+        "INSERT INTO config (id, schema_version, data) VALUES (0, ?1, ?2)
+         ON CONFLICT(id) DO UPDATE SET schema_version = excluded.schema_version, data = excluded.data",
+        rusqlite::params![SCHEMA_VERSION, json],
+    )
+    .map_err(sqlite_err)?;
+    Ok(())
+}
+
+/// Shared, cached `AppConfig` backed by SQLite. Cheap to clone - every
+/// clone sees the same underlying store, same as `AutoMinerHandle`. Holds
+/// one long-lived `Connection` behind a plain `Mutex` (SQLite writes here
+/// are small and synchronous, so there's no benefit to an async lock)
+/// rather than reopening the database on every `update`.
+#[derive(Clone)]
+pub struct ConfigStore {
+    inner: Arc<RwLock<AppConfig>>,
+    conn: Arc<StdMutex<Connection>>,
+}
+
+impl ConfigStore {
+    /// Open (or create) the SQLite-backed config and load it into memory.
+    /// Call once at startup.
+    pub fn load() -> Result<Self> {
+        let conn = open_connection()?;
+        let config = read_config(&conn)?;
+        Ok(Self {
+            inner: Arc::new(RwLock::new(config)),
+            conn: Arc::new(StdMutex::new(conn)),
+        })
+    }
+
+    /// Snapshot of the cached config.
+    pub async fn get(&self) -> AppConfig {
+        self.inner.read().await.clone()
+    }
+
+    /// Mutate the cached config and persist the result to SQLite within
+    /// the same write-lock critical section, returning the updated config.
+    pub async fn update<F>(&self, f: F) -> Result<AppConfig>
+    where
+        F: FnOnce(&mut AppConfig),
+    {
+        let mut guard = self.inner.write().await;
+        let mut updated = guard.clone();
+        f(&mut updated);
+        let conn = self.conn.lock().unwrap();
+        write_config(&conn, &updated)?;
+        drop(conn);
+        *guard = updated.clone();
+        Ok(updated)
+    }
+}
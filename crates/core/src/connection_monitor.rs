@@ -0,0 +1,149 @@
+//! Per-session pool connection monitoring via live socket inspection.
+//!
+//! `check_pool_health` only probes a pool URL out-of-band, with no relation
+//! to any particular miner process; it tells you the pool is reachable,
+//! not that a given session is actually talking to it. This module instead
+//! asks the OS which TCP sockets a session's own miner process holds open
+//! (via `netstat2`) and checks whether any of them is connected to that
+//! session's configured pool host/port.
+
+use netstat2::{get_sockets_info, AddressFamilyFlags, ProtocolFlags, ProtocolSocketInfo, TcpState};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, ToSocketAddrs};
+use std::time::Instant;
+
+/// Connection state of a session's pool socket, as observed by the OS -
+/// collapsed from `netstat2::TcpState`'s full state machine down to the
+/// three states a user actually cares about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolSocketState {
+    Established,
+    SynSent,
+    Closed,
+}
+
+impl From<TcpState> for PoolSocketState {
+    fn from(state: TcpState) -> Self {
+        match state {
+            TcpState::Established => Self::Established,
+            TcpState::SynSent | TcpState::SynReceived => Self::SynSent,
+            _ => Self::Closed,
+        }
+    }
+}
+
+/// Live socket-level view of a session's connection to its pool, returned
+/// by `get_session_connections`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionConnection {
+    pub session_id: String,
+    pub remote_addr: Option<String>,
+    pub state: PoolSocketState,
+    pub since_last_change_secs: u64,
+}
+
+/// Resolve `pool_host:pool_port` and find the socket `pid` holds open to
+/// one of those addresses, if any.
+fn find_pool_socket(pid: u32, pool_host: &str, pool_port: u16) -> Option<(IpAddr, u16, PoolSocketState)> {
+    let pool_ips: Vec<IpAddr> = (pool_host, pool_port)
+        .to_socket_addrs()
+        .map(|addrs| addrs.map(|a| a.ip()).collect())
+        .unwrap_or_default();
+    if pool_ips.is_empty() {
+        return None;
+    }
+
+    let sockets = get_sockets_info(AddressFamilyFlags::IPV4 | AddressFamilyFlags::IPV6, ProtocolFlags::TCP).ok()?;
+    for socket in sockets {
+        if !socket.associated_pids.contains(&pid) {
+            continue;
+        }
+        if let ProtocolSocketInfo::Tcp(tcp) = socket.protocol_socket_info {
+            if pool_ips.contains(&tcp.remote_addr) {
+                return Some((tcp.remote_addr, tcp.remote_port, tcp.state.into()));
+            }
+        }
+    }
+    None
+}
+
+/// Tracks a single session's pool socket across polls, so callers can tell
+/// when the connection state actually changed (vs. just re-observing the
+/// same state) and raise an `Alert` only on the transition.
+pub struct ConnectionWatcher {
+    state: PoolSocketState,
+    last_change: Instant,
+}
+
+impl Default for ConnectionWatcher {
+    fn default() -> Self {
+        Self {
+            state: PoolSocketState::Closed,
+            last_change: Instant::now(),
+        }
+    }
+}
+
+impl ConnectionWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Poll `pid`'s sockets for one talking to `pool_host:pool_port` and
+    /// update the tracked state. Returns the resulting `SessionConnection`
+    /// plus whether the state just changed from the previous poll - the
+    /// caller's cue to raise an alert.
+    pub fn poll(
+        &mut self,
+        session_id: &str,
+        pid: u32,
+        pool_host: &str,
+        pool_port: u16,
+    ) -> (SessionConnection, bool) {
+        let (remote_addr, new_state) = match find_pool_socket(pid, pool_host, pool_port) {
+            Some((ip, port, state)) => (Some(format!("{}:{}", ip, port)), state),
+            None => (None, PoolSocketState::Closed),
+        };
+
+        let changed = new_state != self.state;
+        if changed {
+            self.state = new_state;
+            self.last_change = Instant::now();
+        }
+
+        (
+            SessionConnection {
+                session_id: session_id.to_string(),
+                remote_addr,
+                state: new_state,
+                since_last_change_secs: self.last_change.elapsed().as_secs(),
+            },
+            changed,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_state_collapses_to_three_states() {
+        assert_eq!(PoolSocketState::from(TcpState::Established), PoolSocketState::Established);
+        assert_eq!(PoolSocketState::from(TcpState::SynSent), PoolSocketState::SynSent);
+        assert_eq!(PoolSocketState::from(TcpState::SynReceived), PoolSocketState::SynSent);
+        assert_eq!(PoolSocketState::from(TcpState::CloseWait), PoolSocketState::Closed);
+    }
+
+    #[test]
+    fn test_watcher_starts_closed_and_does_not_flag_unchanged_first_poll() {
+        let mut watcher = ConnectionWatcher::new();
+        // No process on earth holds this PID open to a bogus pool, so this
+        // always resolves to "closed" - the interesting bit is that a
+        // fresh watcher doesn't report that as a change.
+        let (conn, changed) = watcher.poll("sess1", u32::MAX, "pool.invalid.example", 3333);
+        assert_eq!(conn.state, PoolSocketState::Closed);
+        assert!(!changed);
+    }
+}
@@ -0,0 +1,365 @@
+//! Headless JSON-RPC control server.
+//!
+//! Lets `AppState` be driven without the Tauri GUI - e.g. running the miner
+//! as a long-running service on a headless box. Listens on a localhost-only
+//! TCP socket and speaks line-delimited JSON-RPC 2.0 (`start`, `stop`,
+//! `status`, `set_config`, `get_stats`, `list_coins`, `profiles`,
+//! `pool_health`, `peers`, `metrics`) - the same settings/peers/status/
+//! histogram surface established miner RPCs expose, so external tooling, a
+//! script, or a separate dashboard can drive and observe the miner without
+//! the Tauri frontend. A token is generated on first run and written to an
+//! owner-only-readable file; every request must echo it back, so only the
+//! local user who can read that file can issue commands - XMRig's own HTTP
+//! API has no such gate, which this exists to avoid relying on.
+
+use crate::{AppState, MetricsSnapshot, MiningConfig, Profile, SharedMetrics};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadlessConfig {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    #[serde(default = "default_token_file")]
+    pub token_file: PathBuf,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false, // Opt-in, like metrics
+            bind_address: "127.0.0.1".to_string(),
+            port: 9091,
+            token_file: default_token_file(),
+        }
+    }
+}
+
+fn default_token_file() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join("control.token")
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn ok(id: serde_json::Value, result: serde_json::Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    fn err(id: serde_json::Value, code: i32, message: impl Into<String>) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(RpcError { code, message: message.into() }) }
+    }
+}
+
+/// Load the bearer token, generating and persisting a new one (owner-only
+/// permissions on unix) if none exists yet.
+fn ensure_auth_token(token_file: &std::path::Path) -> std::io::Result<String> {
+    if let Ok(existing) = std::fs::read_to_string(token_file) {
+        let trimmed = existing.trim();
+        if !trimmed.is_empty() {
+            return Ok(trimmed.to_string());
+        }
+    }
+
+    if let Some(dir) = token_file.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let token = generate_token();
+    std::fs::write(token_file, &token)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(token_file)?.permissions();
+        perms.set_mode(0o600);
+        std::fs::set_permissions(token_file, perms)?;
+    }
+
+    Ok(token)
+}
+
+fn generate_token() -> String {
+    let seed = format!(
+        "{:?}-{}-{}",
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default(),
+        std::process::id(),
+        uuid::Uuid::new_v4(),
+    );
+    let mut hasher = Sha256::new();
+    hasher.update(seed.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Spawn a background task serving the control socket on
+/// `config.bind_address:config.port`. No-op if `config.enabled` is false.
+/// Runs until the process exits.
+pub fn spawn_control_server(
+    config: HeadlessConfig,
+    state: Arc<Mutex<AppState>>,
+    app_handle: tauri::AppHandle,
+    metrics: SharedMetrics,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let token = match ensure_auth_token(&config.token_file) {
+            Ok(t) => t,
+            Err(e) => {
+                warn!("Failed to set up control server auth token at {:?}: {}", config.token_file, e);
+                return;
+            }
+        };
+
+        let addr = format!("{}:{}", config.bind_address, config.port);
+        let listener = match TcpListener::bind(&addr).await {
+            Ok(l) => l,
+            Err(e) => {
+                warn!("Failed to bind control endpoint on {}: {}", addr, e);
+                return;
+            }
+        };
+
+        info!("Headless control server listening on {} (token: {:?})", addr, config.token_file);
+
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Control listener accept error: {}", e);
+                    continue;
+                }
+            };
+
+            let state = state.clone();
+            let app_handle = app_handle.clone();
+            let metrics = metrics.clone();
+            let token = token.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, state, app_handle, metrics, token).await {
+                    warn!("Control connection from {} ended with error: {}", peer, e);
+                }
+            });
+        }
+    });
+}
+
+async fn handle_connection(
+    stream: tokio::net::TcpStream,
+    state: Arc<Mutex<AppState>>,
+    app_handle: tauri::AppHandle,
+    metrics: SharedMetrics,
+    token: String,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(req) => handle_request(req, &state, &app_handle, &metrics, &token).await,
+            Err(e) => RpcResponse::err(serde_json::Value::Null, -32700, format!("Parse error: {}", e)),
+        };
+
+        let mut out = serde_json::to_string(&response).unwrap_or_default();
+        out.push('\n');
+        writer.write_all(out.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+async fn handle_request(
+    req: RpcRequest,
+    state: &Arc<Mutex<AppState>>,
+    app_handle: &tauri::AppHandle,
+    metrics: &SharedMetrics,
+    token: &str,
+) -> RpcResponse {
+    if req.token.as_deref() != Some(token) {
+        return RpcResponse::err(req.id, -32600, "Unauthorized: missing or invalid token");
+    }
+
+    match req.method.as_str() {
+        "status" | "get_stats" => {
+            let mut guard = state.lock().await;
+            let _ = guard.refresh_stats(app_handle).await;
+            match serde_json::to_value(guard.status()) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "start" => {
+            let config: MiningConfig = match serde_json::from_value(req.params) {
+                Ok(c) => c,
+                Err(e) => return RpcResponse::err(req.id, -32602, format!("Invalid params: {}", e)),
+            };
+            let mut guard = state.lock().await;
+            match guard.start_mining(config, app_handle.clone()).await {
+                Ok(()) => RpcResponse::ok(req.id, serde_json::json!({"ok": true})),
+                Err(e) => RpcResponse::err(req.id, -32000, e.to_string()),
+            }
+        }
+        "stop" => {
+            let mut guard = state.lock().await;
+            match guard.stop_mining().await {
+                Ok(()) => RpcResponse::ok(req.id, serde_json::json!({"ok": true})),
+                Err(e) => RpcResponse::err(req.id, -32000, e.to_string()),
+            }
+        }
+        "set_config" => {
+            let profile: Profile = match serde_json::from_value(req.params) {
+                Ok(p) => p,
+                Err(e) => return RpcResponse::err(req.id, -32602, format!("Invalid params: {}", e)),
+            };
+            let mut guard = state.lock().await;
+            guard.save_profile(profile);
+            match guard.save_config() {
+                Ok(()) => RpcResponse::ok(req.id, serde_json::json!({"ok": true})),
+                Err(e) => RpcResponse::err(req.id, -32000, e.to_string()),
+            }
+        }
+        "list_coins" => {
+            let guard = state.lock().await;
+            match guard.list_coins().and_then(|coins| serde_json::to_value(coins).map_err(Into::into)) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32000, e.to_string()),
+            }
+        }
+        "profiles" => {
+            let guard = state.lock().await;
+            match serde_json::to_value(guard.profiles()) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "pool_health" => {
+            let url = match req.params.get("url").and_then(|v| v.as_str()) {
+                Some(url) => url.to_string(),
+                None => return RpcResponse::err(req.id, -32602, "Invalid params: expected {\"url\": \"...\"}"),
+            };
+            match openminedash_pools::check_health(&url).await {
+                Ok(result) => match serde_json::to_value(result) {
+                    Ok(value) => RpcResponse::ok(req.id, value),
+                    Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+                },
+                Err(e) => RpcResponse::err(req.id, -32000, e.to_string()),
+            }
+        }
+        "peers" | "remote_endpoints" => {
+            let guard = state.lock().await;
+            match serde_json::to_value(guard.remote_endpoints()) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "metrics" => {
+            let snapshot: MetricsSnapshot = metrics.read().await.clone();
+            match serde_json::to_value(snapshot_for_rpc(&snapshot)) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        other => RpcResponse::err(req.id, -32601, format!("Unknown method: {}", other)),
+    }
+}
+
+/// `MetricsSnapshot` itself isn't `Serialize` (it exists to feed the
+/// Prometheus text renderer, not JSON), so the `metrics` RPC method reports
+/// the same numbers in a plain JSON shape instead.
+fn snapshot_for_rpc(snapshot: &MetricsSnapshot) -> serde_json::Value {
+    serde_json::json!({
+        "local_miner": {
+            "hashrate": snapshot.local_miner.hashrate,
+            "accepted_shares": snapshot.local_miner.accepted_shares,
+            "rejected_shares": snapshot.local_miner.rejected_shares,
+        },
+        "remote": snapshot.remote.iter().map(|(id, (name, stats))| {
+            serde_json::json!({ "id": id, "name": name, "stats": stats })
+        }).collect::<Vec<_>>(),
+        "pools": snapshot.pools.values().collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headless_config_default_disabled() {
+        let config = HeadlessConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.port, 9091);
+    }
+
+    #[test]
+    fn test_ensure_auth_token_persists_and_reuses() {
+        let dir = std::env::temp_dir().join(format!("openminedash-test-token-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let token_file = dir.join("control.token");
+
+        let first = ensure_auth_token(&token_file).unwrap();
+        let second = ensure_auth_token(&token_file).unwrap();
+        assert_eq!(first, second);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rpc_error_response_has_error_and_no_result() {
+        let resp = RpcResponse::err(serde_json::json!(1), -32600, "Unauthorized: missing or invalid token");
+        assert!(resp.error.is_some());
+        assert!(resp.result.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_for_rpc_reports_local_miner_fields() {
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.local_miner.hashrate = 500.0;
+        snapshot.local_miner.accepted_shares = 3;
+
+        let value = snapshot_for_rpc(&snapshot);
+        assert_eq!(value["local_miner"]["hashrate"], 500.0);
+        assert_eq!(value["local_miner"]["accepted_shares"], 3);
+    }
+}
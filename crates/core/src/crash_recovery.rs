@@ -1,6 +1,7 @@
 //! Crash recovery: detect unclean shutdown and offer to resume.
 //! IMPORTANT: This does NOT auto-start mining. User must explicitly confirm.
 
+use openminedash_pools::parse_pool_url;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tracing::{info, warn};
@@ -34,6 +35,10 @@ pub struct SessionSnapshot {
     pub started_at: u64,
     #[serde(default)]
     pub config_hash: String,
+    /// PID of the miner process, used to tell a genuine crash apart from a
+    /// lock file left behind by a still-running instance.
+    #[serde(default)]
+    pub pid: u32,
 }
 
 /// Lock file content for multi-session
@@ -49,6 +54,11 @@ pub struct CrashRecoveryState {
     pub had_unclean_shutdown: bool,
     pub last_session: Option<MiningSession>,
     pub sessions: Vec<SessionSnapshot>,
+    /// True if the lock file's PID still belongs to a live process - a
+    /// second instance is probably already mining, so the app should warn
+    /// instead of offering a resume that would double-start it.
+    pub already_running: bool,
+    pub running_sessions: Vec<SessionSnapshot>,
 }
 
 impl Default for CrashRecoveryState {
@@ -57,10 +67,32 @@ impl Default for CrashRecoveryState {
             had_unclean_shutdown: false,
             last_session: None,
             sessions: Vec::new(),
+            already_running: false,
+            running_sessions: Vec::new(),
         }
     }
 }
 
+/// Test whether a process with the given PID is still alive, without
+/// sending it a real signal (`kill(pid, 0)` semantics).
+#[cfg(unix)]
+fn is_pid_alive(pid: u32) -> bool {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    if pid == 0 {
+        return false;
+    }
+    kill(Pid::from_raw(pid as i32), None).is_ok()
+}
+
+#[cfg(not(unix))]
+fn is_pid_alive(_pid: u32) -> bool {
+    // TODO: check liveness via OpenProcess on Windows. Until then, assume
+    // the process has exited so crash recovery isn't blocked forever.
+    false
+}
+
 fn lock_file_path() -> PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -89,22 +121,53 @@ pub fn check_crash_recovery() -> CrashRecoveryState {
                 // Try multi-session format first
                 if let Ok(multi) = serde_json::from_str::<MultiSessionLock>(&content) {
                     let _ = std::fs::remove_file(&lock_path);
-                    info!("Found unclean shutdown with {} sessions", multi.sessions.len());
+                    let (dead, alive): (Vec<_>, Vec<_>) = multi
+                        .sessions
+                        .into_iter()
+                        .partition(|s| !is_pid_alive(s.pid));
+                    if !alive.is_empty() {
+                        warn!(
+                            "{} session(s) from the lock file still have a live process; not offering resume for them",
+                            alive.len()
+                        );
+                    }
+                    info!(
+                        "Found unclean shutdown with {} session(s) ({} still running)",
+                        dead.len(),
+                        alive.len()
+                    );
                     return CrashRecoveryState {
-                        had_unclean_shutdown: true,
+                        had_unclean_shutdown: !dead.is_empty(),
                         last_session: None,
-                        sessions: multi.sessions,
+                        sessions: dead,
+                        already_running: !alive.is_empty(),
+                        running_sessions: alive,
                     };
                 }
-                
+
                 // Fallback: legacy format
                 if let Ok(session) = serde_json::from_str::<MiningSession>(&content) {
                     let _ = std::fs::remove_file(&lock_path);
+                    if is_pid_alive(session.pid) {
+                        warn!(
+                            "Lock file PID {} still has a live process; not offering resume",
+                            session.pid
+                        );
+                        return CrashRecoveryState {
+                            had_unclean_shutdown: false,
+                            last_session: None,
+                            sessions: Vec::new(),
+                            already_running: true,
+                            running_sessions: Vec::new(),
+                        };
+                    }
                     info!("Found unclean shutdown (legacy): {:?}", session);
                     return CrashRecoveryState {
                         had_unclean_shutdown: true,
                         last_session: Some(session),
                         sessions: Vec::new(),
+                        already_running: false,
+                        running_sessions: Vec::new(),
                     };
                 }
                 
@@ -161,8 +224,14 @@ pub fn create_mining_lock(session: &MiningSession) -> std::io::Result<()> {
 
 /// Create lock file with multi-session snapshot
 pub fn create_sessions_lock(sessions: &[SessionSnapshot]) -> std::io::Result<()> {
+    // Catch a bad pool URL at save time rather than at connect time.
+    for session in sessions {
+        parse_pool_url(&session.pool_url)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    }
+
     let lock_path = lock_file_path();
-    
+
     let content = MultiSessionLock {
         version: 1,
         sessions: sessions.to_vec(),
@@ -251,18 +320,56 @@ mod tests {
                 status: "running".to_string(),
                 started_at: 123,
                 config_hash: "abc123".to_string(),
+                pid: 0,
             },
         ];
         create_sessions_lock(&sessions).unwrap();
         let state = check_crash_recovery();
         assert!(state.had_unclean_shutdown);
+        assert!(!state.already_running);
         assert_eq!(state.sessions.len(), 1);
-        
+
         // Clean after check
         let state = check_crash_recovery();
         assert!(!state.had_unclean_shutdown);
     }
 
+    #[test]
+    fn test_already_running_session_not_offered_for_resume() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+
+        let lock_path = lock_file_path();
+        if let Some(parent) = lock_path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        remove_mining_lock();
+
+        // Our own PID is definitely alive - simulates a second instance
+        // finding a lock file written by a still-running miner.
+        let sessions = vec![SessionSnapshot {
+            session_id: "s1".to_string(),
+            coin_id: "xmr".to_string(),
+            symbol: "XMR".to_string(),
+            algorithm: "randomx".to_string(),
+            pool_url: "pool1:3333".to_string(),
+            wallet: "w1".to_string(),
+            worker: "w1".to_string(),
+            preset: "balanced".to_string(),
+            threads_hint: 4,
+            status: "running".to_string(),
+            started_at: 123,
+            config_hash: "abc123".to_string(),
+            pid: std::process::id(),
+        }];
+        create_sessions_lock(&sessions).unwrap();
+
+        let state = check_crash_recovery();
+        assert!(!state.had_unclean_shutdown);
+        assert!(state.already_running);
+        assert_eq!(state.running_sessions.len(), 1);
+        assert!(state.sessions.is_empty());
+    }
+
     #[test]
     fn test_corrupted_lock_handling() {
         let _guard = TEST_MUTEX.lock().unwrap();
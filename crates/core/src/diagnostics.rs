@@ -1,5 +1,8 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
 
 const MAX_LOG_LINES: usize = 2000;
 
@@ -73,6 +76,30 @@ pub fn mask_wallet(wallet: &str) -> String {
     format!("{}...{}", &wallet[..6], &wallet[wallet.len() - 4..])
 }
 
+/// Scrub a single log line of anything resembling a Monero address or an
+/// IPv4 host, so raw miner/pool log output can't leak them even when a
+/// user forgets to enable `mask_wallets`.
+fn scrub_log_line(line: &str) -> String {
+    let mut scrubbed = line.to_string();
+
+    if let Ok(address_re) = Regex::new(r"\b[48][0-9A-Za-z]{94}\b") {
+        scrubbed = address_re
+            .replace_all(&scrubbed, |caps: &regex::Captures| mask_wallet(&caps[0]))
+            .into_owned();
+    }
+
+    if let Ok(ipv4_re) = Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b") {
+        scrubbed = ipv4_re.replace_all(&scrubbed, "[REDACTED-IP]").into_owned();
+    }
+
+    scrubbed
+}
+
+/// Scrub an entire captured log buffer before it's embedded in an export.
+pub fn scrub_logs(logs: Vec<String>) -> Vec<String> {
+    logs.iter().map(|line| scrub_log_line(line)).collect()
+}
+
 /// IMPORTANT: Diagnostics export may contain sensitive metadata.
 /// Wallet addresses are masked by default for privacy.
 pub fn create_diagnostics_export(
@@ -108,11 +135,94 @@ pub fn create_diagnostics_export(
             profiles,
             custom_binary_path: config.custom_binary_path.as_ref().map(|p| p.display().to_string()),
         },
-        logs,
+        logs: scrub_logs(logs),
         timestamp: chrono_lite_timestamp(),
     }
 }
 
+/// Bundles the recent rotating log files together with the last known
+/// `MiningStatus`, `CrashRecoveryState`, and pool health results into a
+/// single zip archive at `bundle_path`, so a support report is one
+/// attachment instead of several separately-requested pastes. Only the
+/// newest `max_log_files` entries under `log_dir` are included, matching
+/// the retention `logging::init_logging` already prunes to.
+pub fn create_diagnostics_bundle(
+    bundle_path: &Path,
+    log_dir: &Path,
+    max_log_files: usize,
+    status: &crate::MiningStatus,
+    crash_recovery: &crate::CrashRecoveryState,
+    pool_health: &[openminedash_pools::PoolHealthResult],
+) -> crate::Result<()> {
+    let file = std::fs::File::create(bundle_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("mining_status.json", options)
+        .map_err(|e| crate::CoreError::Diagnostics(e.to_string()))?;
+    zip.write_all(serde_json::to_string_pretty(status)?.as_bytes())?;
+
+    zip.start_file("crash_recovery.json", options)
+        .map_err(|e| crate::CoreError::Diagnostics(e.to_string()))?;
+    zip.write_all(serde_json::to_string_pretty(crash_recovery)?.as_bytes())?;
+
+    zip.start_file("pool_health.json", options)
+        .map_err(|e| crate::CoreError::Diagnostics(e.to_string()))?;
+    zip.write_all(serde_json::to_string_pretty(pool_health)?.as_bytes())?;
+
+    if let Ok(entries) = std::fs::read_dir(log_dir) {
+        let mut log_files: Vec<_> = entries.filter_map(|e| e.ok()).filter(|e| e.path().is_file()).collect();
+        log_files.sort_by_key(|e| e.metadata().and_then(|m| m.modified()).unwrap_or(std::time::SystemTime::UNIX_EPOCH));
+        for entry in log_files.iter().rev().take(max_log_files) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let contents = std::fs::read(entry.path())?;
+            zip.start_file(format!("logs/{name}"), options)
+                .map_err(|e| crate::CoreError::Diagnostics(e.to_string()))?;
+            zip.write_all(&contents)?;
+        }
+    }
+
+    zip.finish().map_err(|e| crate::CoreError::Diagnostics(e.to_string()))?;
+    Ok(())
+}
+
+impl DiagnosticsExport {
+    /// Seal this export to `recipient_pubkey` (an age/X25519 public key,
+    /// `age1...`) and return the armored ciphertext. Use this instead of the
+    /// plaintext JSON whenever the export leaves the machine, so "review
+    /// before sharing" is enforced rather than advisory.
+    pub fn to_encrypted(&self, recipient_pubkey: &str) -> std::result::Result<String, String> {
+        use age::armor::{ArmoredWriter, Format};
+        use std::io::Write;
+
+        let recipient: age::x25519::Recipient = recipient_pubkey
+            .parse()
+            .map_err(|e| format!("invalid recipient public key: {}", e))?;
+
+        let plaintext =
+            serde_json::to_vec(self).map_err(|e| format!("failed to serialize export: {}", e))?;
+
+        let encryptor = age::Encryptor::with_recipients(vec![Box::new(recipient)])
+            .ok_or_else(|| "failed to build encryptor".to_string())?;
+
+        let mut output = vec![];
+        let armored = ArmoredWriter::wrap_output(&mut output, Format::AsciiArmor)
+            .map_err(|e| format!("failed to start armored output: {}", e))?;
+        let mut writer = encryptor
+            .wrap_output(armored)
+            .map_err(|e| format!("failed to start encryption: {}", e))?;
+        writer
+            .write_all(&plaintext)
+            .map_err(|e| format!("failed to write ciphertext: {}", e))?;
+        writer
+            .finish()
+            .and_then(|armor| armor.finish())
+            .map_err(|e| format!("failed to finalize ciphertext: {}", e))?;
+
+        String::from_utf8(output).map_err(|e| format!("ciphertext was not valid UTF-8: {}", e))
+    }
+}
+
 fn get_os_version() -> String {
     #[cfg(target_os = "macos")]
     {
@@ -149,6 +259,20 @@ mod tests {
         assert_eq!(mask_wallet("short"), "***");
     }
 
+    #[test]
+    fn test_scrub_log_line_masks_monero_address() {
+        let line = "Payment sent to 48edfHu7V9Z84YzzMa6fUueoELZ9ZRXq9VetWzYGzKt52XU5xvqgzYnDK9URnRoJMk1j8nLwEVsaSWJ4fhdUyZijBGUicoD";
+        let scrubbed = scrub_log_line(line);
+        assert!(scrubbed.contains("48edfH...icoD"));
+        assert!(!scrubbed.contains("VetWzYGzKt52XU5xvqgzYnDK9URnRoJMk1j8nLwEVsaSWJ4fhdUyZijBGUicoD"));
+    }
+
+    #[test]
+    fn test_scrub_log_line_masks_ipv4() {
+        let scrubbed = scrub_log_line("connected to pool at 203.0.113.42:3333");
+        assert_eq!(scrubbed, "connected to pool at [REDACTED-IP]:3333");
+    }
+
     #[test]
     fn test_log_buffer() {
         let mut buffer = LogBuffer::new();
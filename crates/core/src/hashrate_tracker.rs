@@ -0,0 +1,317 @@
+//! Rolling-window hashrate & share tracking, feeding sustained-drop
+//! detection.
+//!
+//! `SessionManager::refresh_all_stats` polls each session's adapter at a
+//! roughly 1Hz cadence. Comparing two raw samples is noisy - a single poll
+//! that lands on a slow interval will look like a "drop" even though
+//! nothing changed. `HashrateTracker::record` instead folds each poll into
+//! a capped ring buffer (evicted by both count and max age) and derives an
+//! exponential moving average plus short/long window means, so
+//! `hashrate_drop_pct` reflects a sustained change rather than a blip.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
+/// Max number of samples kept regardless of age.
+const RING_CAPACITY: usize = 120;
+/// Samples older than this are evicted regardless of count.
+const MAX_SAMPLE_AGE_SECS: u64 = 600;
+/// Smoothing factor for the exponential moving average (0-1, higher reacts faster).
+const EMA_ALPHA: f64 = 0.1;
+/// Short window used as the "current" side of a drop comparison.
+const SHORT_WINDOW_SECS: u64 = 30;
+/// Long window used as the "baseline" side of a drop comparison.
+const LONG_WINDOW_SECS: u64 = 300;
+/// Default window for `share_reject_rate` when the caller has no opinion.
+pub const DEFAULT_REJECT_RATE_WINDOW_SECS: u64 = 300;
+
+struct Sample {
+    at: Instant,
+    hashrate: f64,
+    accepted_delta: u64,
+    rejected_delta: u64,
+}
+
+/// Per-session rolling hashrate and share-accept window. Lives in
+/// `SessionRuntime` (not serialized) - `SessionStats` carries the derived
+/// numbers that actually go to the UI and notifications.
+pub struct HashrateTracker {
+    samples: VecDeque<Sample>,
+    ema: Option<f64>,
+    last_accepted_seen: u64,
+    last_rejected_seen: u64,
+}
+
+impl HashrateTracker {
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(RING_CAPACITY),
+            ema: None,
+            last_accepted_seen: 0,
+            last_rejected_seen: 0,
+        }
+    }
+
+    /// Ingest one poll. `hashrate` is `None` while the miner hasn't reported
+    /// a reading yet (e.g. still warming up) and is skipped entirely rather
+    /// than counted as a zero, which would otherwise look like a drop to
+    /// zero the moment a session starts. `accepted`/`rejected` are the
+    /// adapter's cumulative counters; only the delta since the last
+    /// `record` call is stored.
+    pub fn record(&mut self, hashrate: Option<f64>, accepted: u64, rejected: u64) {
+        let accepted_delta = accepted.saturating_sub(self.last_accepted_seen);
+        let rejected_delta = rejected.saturating_sub(self.last_rejected_seen);
+        self.last_accepted_seen = accepted;
+        self.last_rejected_seen = rejected;
+
+        let Some(hashrate) = hashrate else {
+            return;
+        };
+
+        self.ema = Some(match self.ema {
+            Some(prev) => prev + EMA_ALPHA * (hashrate - prev),
+            None => hashrate,
+        });
+
+        self.samples.push_back(Sample {
+            at: Instant::now(),
+            hashrate,
+            accepted_delta,
+            rejected_delta,
+        });
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.samples.len() > RING_CAPACITY {
+            self.samples.pop_front();
+        }
+        let cutoff = Instant::now().checked_sub(Duration::from_secs(MAX_SAMPLE_AGE_SECS));
+        if let Some(cutoff) = cutoff {
+            while self.samples.front().map(|s| s.at < cutoff).unwrap_or(false) {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// Exponential moving average of hashrate, or `None` before the first
+    /// recorded sample.
+    pub fn ema_hashrate(&self) -> Option<f64> {
+        self.ema
+    }
+
+    fn mean_over(&self, window: Duration) -> Option<f64> {
+        let cutoff = Instant::now().checked_sub(window)?;
+        let mut sum = 0.0;
+        let mut count = 0u32;
+        for sample in self.samples.iter().rev().take_while(|s| s.at >= cutoff) {
+            sum += sample.hashrate;
+            count += 1;
+        }
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f64)
+        }
+    }
+
+    /// Percentage drop of the short-window mean against the long-window
+    /// baseline, `None` until both windows have at least one sample.
+    /// Negative values (hashrate went up) are clamped to zero.
+    pub fn hashrate_drop_pct(&self) -> Option<f64> {
+        let short = self.mean_over(Duration::from_secs(SHORT_WINDOW_SECS))?;
+        let long = self.mean_over(Duration::from_secs(LONG_WINDOW_SECS))?;
+        if long <= 0.0 {
+            return None;
+        }
+        Some(((long - short) / long * 100.0).max(0.0))
+    }
+
+    /// Fraction of shares rejected within `window`, `None` if no shares
+    /// were observed in that window at all.
+    pub fn share_reject_rate(&self, window: Duration) -> Option<f64> {
+        let cutoff = Instant::now().checked_sub(window)?;
+        let mut accepted = 0u64;
+        let mut rejected = 0u64;
+        for sample in self.samples.iter().rev().take_while(|s| s.at >= cutoff) {
+            accepted += sample.accepted_delta;
+            rejected += sample.rejected_delta;
+        }
+        let total = accepted + rejected;
+        if total == 0 {
+            None
+        } else {
+            Some(rejected as f64 / total as f64)
+        }
+    }
+
+    /// Clear all accumulated state. A session restart creates a fresh
+    /// `HashrateTracker` via `new()` anyway, but this is exposed so the
+    /// baseline can be reset in place without dropping the tracker itself.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.ema = None;
+        self.last_accepted_seen = 0;
+        self.last_rejected_seen = 0;
+    }
+}
+
+impl Default for HashrateTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Number of aggregate samples kept for the tray's menu-bar sparkline.
+const SPARKLINE_CAPACITY: usize = 12;
+/// Drop from the sparkline's own peak (in percent) that flips the tray
+/// indicator to "degraded" - same threshold as
+/// `session_scrub::HASHRATE_DROP_ALERT_THRESHOLD_PCT`, since both are
+/// flagging the same kind of sustained drop, just at different scopes
+/// (fleet-wide here vs. per-session there).
+const SPARKLINE_DEGRADED_DROP_PCT: f64 = 20.0;
+
+const SPARKLINE_BARS: [char; 8] = ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+
+/// Tiny shared ring buffer of fleet-wide aggregate hashrate samples, used
+/// to draw the tray's menu-bar sparkline (see
+/// `tray::update_tray_with_sessions`) and as a coarse, dependency-free
+/// drift signal the session scrub worker can check alongside its own
+/// per-session `HashrateTracker`-based one. Cheap to clone - every clone
+/// shares the same buffer, the same "one shared handle" shape as
+/// `WorkerManager`/`ScrubHandle`.
+#[derive(Debug, Clone, Default)]
+pub struct HashrateSparkline {
+    samples: Arc<StdMutex<VecDeque<f64>>>,
+}
+
+impl HashrateSparkline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the latest fleet-wide total hashrate.
+    pub fn push(&self, total_hashrate: f64) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() >= SPARKLINE_CAPACITY {
+            samples.pop_front();
+        }
+        samples.push_back(total_hashrate);
+    }
+
+    /// Render the buffer as a compact unicode sparkline, scaled so the
+    /// tallest bar represents the highest sample seen so far. Empty (no
+    /// samples yet, or they're all zero) renders as an empty string.
+    pub fn render(&self) -> String {
+        let samples = self.samples.lock().unwrap();
+        let peak = samples.iter().cloned().fold(0.0_f64, f64::max);
+        if peak <= 0.0 {
+            return String::new();
+        }
+        samples
+            .iter()
+            .map(|&v| {
+                let idx = ((v / peak) * (SPARKLINE_BARS.len() - 1) as f64).round() as usize;
+                SPARKLINE_BARS[idx.min(SPARKLINE_BARS.len() - 1)]
+            })
+            .collect()
+    }
+
+    /// Whether the most recent sample has dropped at least
+    /// `SPARKLINE_DEGRADED_DROP_PCT` below the buffer's peak.
+    pub fn is_degraded(&self) -> bool {
+        let samples = self.samples.lock().unwrap();
+        let Some(&latest) = samples.back() else {
+            return false;
+        };
+        let peak = samples.iter().cloned().fold(0.0_f64, f64::max);
+        if peak <= 0.0 {
+            return false;
+        }
+        ((peak - latest) / peak * 100.0) >= SPARKLINE_DEGRADED_DROP_PCT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none_samples_are_skipped_not_zeroed() {
+        let mut tracker = HashrateTracker::new();
+        tracker.record(None, 0, 0);
+        tracker.record(None, 0, 0);
+        assert_eq!(tracker.ema_hashrate(), None);
+        assert_eq!(tracker.hashrate_drop_pct(), None);
+    }
+
+    #[test]
+    fn test_ema_tracks_toward_latest_sample() {
+        let mut tracker = HashrateTracker::new();
+        tracker.record(Some(1000.0), 0, 0);
+        for _ in 0..50 {
+            tracker.record(Some(2000.0), 0, 0);
+        }
+        let ema = tracker.ema_hashrate().unwrap();
+        assert!(ema > 1900.0 && ema <= 2000.0);
+    }
+
+    #[test]
+    fn test_share_reject_rate_uses_deltas_not_cumulative_totals() {
+        let mut tracker = HashrateTracker::new();
+        tracker.record(Some(1000.0), 10, 1);
+        tracker.record(Some(1000.0), 20, 3); // +10 accepted, +2 rejected
+        let rate = tracker.share_reject_rate(Duration::from_secs(3600)).unwrap();
+        assert!((rate - (3.0 / 23.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_share_reject_rate_none_with_no_shares() {
+        let tracker = HashrateTracker::new();
+        assert_eq!(tracker.share_reject_rate(Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn test_reset_clears_baseline() {
+        let mut tracker = HashrateTracker::new();
+        tracker.record(Some(1000.0), 5, 1);
+        tracker.reset();
+        assert_eq!(tracker.ema_hashrate(), None);
+        assert_eq!(tracker.share_reject_rate(Duration::from_secs(60)), None);
+    }
+
+    #[test]
+    fn test_sparkline_empty_renders_nothing_and_is_not_degraded() {
+        let sparkline = HashrateSparkline::new();
+        assert_eq!(sparkline.render(), "");
+        assert!(!sparkline.is_degraded());
+    }
+
+    #[test]
+    fn test_sparkline_renders_one_bar_per_sample() {
+        let sparkline = HashrateSparkline::new();
+        sparkline.push(100.0);
+        sparkline.push(50.0);
+        sparkline.push(200.0);
+        assert_eq!(sparkline.render().chars().count(), 3);
+    }
+
+    #[test]
+    fn test_sparkline_flags_sustained_drop_from_peak() {
+        let sparkline = HashrateSparkline::new();
+        sparkline.push(1000.0);
+        sparkline.push(1000.0);
+        sparkline.push(700.0); // 30% below peak
+        assert!(sparkline.is_degraded());
+    }
+
+    #[test]
+    fn test_sparkline_evicts_beyond_capacity() {
+        let sparkline = HashrateSparkline::new();
+        for i in 0..(SPARKLINE_CAPACITY + 5) {
+            sparkline.push(i as f64);
+        }
+        assert_eq!(sparkline.render().chars().count(), SPARKLINE_CAPACITY);
+    }
+}
@@ -0,0 +1,288 @@
+//! Local JSON-RPC/IPC control surface for sessions, history, and crash state.
+//!
+//! Mirrors OpenEthereum's `json-ipc-server` model: instead of a network
+//! socket, this binds a Unix domain socket (on non-Unix platforms the
+//! server is not started at all) next
+//! to the crash-recovery lock file under `openminedash/`, so only local
+//! tools and scripts can reach it - no token needed, the filesystem's own
+//! permissions (owner-only on the socket file) are the access gate. This
+//! complements [`crate::control_server`], which drives a single active
+//! miner over TCP; this endpoint instead exposes the data that today is
+//! only reachable through the embedding GUI: the live session list, the
+//! crash-recovery snapshot, and mining history.
+//!
+//! The recovery contract from [`crate::crash_recovery`] is preserved:
+//! `resume_sessions` only records intent and never starts mining itself.
+
+use crate::{AppState, HistorySummary, MiningHistory, SessionManager, SessionSummary};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcConfig {
+    pub enabled: bool,
+    #[serde(default = "default_socket_path")]
+    pub socket_path: PathBuf,
+}
+
+impl Default for IpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false, // Opt-in, like the headless control server
+            socket_path: default_socket_path(),
+        }
+    }
+}
+
+fn default_socket_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join("control.sock")
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn ok(id: serde_json::Value, result: serde_json::Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    fn err(id: serde_json::Value, code: i32, message: impl Into<String>) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(RpcError { code, message: message.into() }) }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ResumeSessionsParams {
+    session_ids: Vec<String>,
+}
+
+/// Shared handles the IPC server reads from and writes to. Grouped into one
+/// struct (rather than threading three separate `Arc`s through every
+/// function) since every method needs at most these three.
+#[derive(Clone)]
+pub struct IpcContext {
+    pub state: Arc<Mutex<AppState>>,
+    pub sessions: Arc<Mutex<SessionManager>>,
+    pub history: Arc<Mutex<MiningHistory>>,
+}
+
+/// Spawn a background task serving the control socket at
+/// `config.socket_path`. No-op if `config.enabled` is false.
+pub fn spawn_ipc_server(config: IpcConfig, ctx: IpcContext) {
+    if !config.enabled {
+        return;
+    }
+
+    tokio::spawn(async move {
+        #[cfg(unix)]
+        {
+            run_unix(config.socket_path, ctx).await;
+        }
+
+        #[cfg(not(unix))]
+        {
+            warn!(
+                "Local IPC control socket at {:?} not started: named pipe support is not wired up on this platform yet",
+                config.socket_path
+            );
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn run_unix(socket_path: PathBuf, ctx: IpcContext) {
+    use tokio::net::UnixListener;
+
+    if let Some(parent) = socket_path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            warn!("Failed to create directory for IPC socket {:?}: {}", parent, e);
+            return;
+        }
+    }
+
+    // A stale socket file from a previous unclean shutdown would otherwise
+    // make the bind fail with "address in use".
+    let _ = std::fs::remove_file(&socket_path);
+
+    let listener = match UnixListener::bind(&socket_path) {
+        Ok(l) => l,
+        Err(e) => {
+            warn!("Failed to bind IPC socket at {:?}: {}", socket_path, e);
+            return;
+        }
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(metadata) = std::fs::metadata(&socket_path) {
+            let mut perms = metadata.permissions();
+            perms.set_mode(0o600);
+            let _ = std::fs::set_permissions(&socket_path, perms);
+        }
+    }
+
+    info!("Local IPC control socket listening at {:?}", socket_path);
+
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("IPC listener accept error: {}", e);
+                continue;
+            }
+        };
+
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, ctx).await {
+                warn!("IPC connection ended with error: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(unix)]
+async fn handle_connection(stream: tokio::net::UnixStream, ctx: IpcContext) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(req) => handle_request(req, &ctx).await,
+            Err(e) => RpcResponse::err(serde_json::Value::Null, -32700, format!("Parse error: {}", e)),
+        };
+
+        let mut out = serde_json::to_string(&response).unwrap_or_default();
+        out.push('\n');
+        writer.write_all(out.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+async fn handle_request(req: RpcRequest, ctx: &IpcContext) -> RpcResponse {
+    match req.method.as_str() {
+        "list_sessions" => {
+            let sessions: Vec<SessionSummary> = ctx.sessions.lock().await.list_sessions().await;
+            match serde_json::to_value(sessions) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "get_crash_recovery_state" => {
+            let guard = ctx.state.lock().await;
+            match serde_json::to_value(guard.crash_recovery_state()) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "acknowledge_recovery" => {
+            ctx.state.lock().await.clear_crash_recovery();
+            RpcResponse::ok(req.id, serde_json::json!({"ok": true}))
+        }
+        "resume_sessions" => {
+            // Records intent only - never starts mining. Starting a
+            // session remains an explicit, separate action the caller
+            // must take via the normal session-management surface.
+            let params: ResumeSessionsParams = match serde_json::from_value(req.params) {
+                Ok(p) => p,
+                Err(e) => return RpcResponse::err(req.id, -32602, format!("Invalid params: {}", e)),
+            };
+            info!("Recovery acknowledged for sessions: {:?} (not auto-started)", params.session_ids);
+            RpcResponse::ok(req.id, serde_json::json!({"acknowledged": true, "session_ids": params.session_ids}))
+        }
+        "get_history_summary" => {
+            let summary: HistorySummary = ctx.history.lock().await.get_summary();
+            match serde_json::to_value(summary) {
+                Ok(value) => RpcResponse::ok(req.id, value),
+                Err(e) => RpcResponse::err(req.id, -32603, e.to_string()),
+            }
+        }
+        "clear_history" => {
+            ctx.history.lock().await.clear();
+            RpcResponse::ok(req.id, serde_json::json!({"ok": true}))
+        }
+        other => RpcResponse::err(req.id, -32601, format!("Unknown method: {}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipc_config_default_disabled() {
+        let config = IpcConfig::default();
+        assert!(!config.enabled);
+        assert!(config.socket_path.ends_with("control.sock"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_sessions_never_starts_mining() {
+        let ctx = IpcContext {
+            state: Arc::new(Mutex::new(AppState::new())),
+            sessions: Arc::new(Mutex::new(SessionManager::new())),
+            history: Arc::new(Mutex::new(MiningHistory::default())),
+        };
+
+        let req = RpcRequest {
+            id: serde_json::json!(1),
+            method: "resume_sessions".to_string(),
+            params: serde_json::json!({"session_ids": ["s1", "s2"]}),
+        };
+        let resp = handle_request(req, &ctx).await;
+        assert!(resp.error.is_none());
+        // No session should have been started as a side effect.
+        assert_eq!(ctx.sessions.lock().await.list_sessions().await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_method_errors() {
+        let ctx = IpcContext {
+            state: Arc::new(Mutex::new(AppState::new())),
+            sessions: Arc::new(Mutex::new(SessionManager::new())),
+            history: Arc::new(Mutex::new(MiningHistory::default())),
+        };
+
+        let req = RpcRequest {
+            id: serde_json::json!(1),
+            method: "does_not_exist".to_string(),
+            params: serde_json::Value::Null,
+        };
+        let resp = handle_request(req, &ctx).await;
+        assert!(resp.error.is_some());
+    }
+}
@@ -1,33 +1,63 @@
 mod algo_routing;
 mod alert_store;
+mod auto_miner;
 mod benchmark;
 mod config;
+mod config_store;
+mod connection_monitor;
+mod control_server;
 mod crash_recovery;
 mod diagnostics;
+mod hashrate_tracker;
+mod ipc_server;
+mod logging;
+mod metrics;
+mod mining_history;
+mod mining_mode;
 mod plugin;
 mod process;
 mod remote;
 mod session_manager;
+mod session_persistence;
+mod session_scrub;
+mod stratum_proxy;
 mod telemetry;
 mod thread_budget;
+mod worker_manager;
 
 pub use algo_routing::*;
 pub use alert_store::*;
+pub use auto_miner::*;
 pub use benchmark::*;
 pub use config::*;
+pub use config_store::*;
+pub use connection_monitor::*;
+pub use control_server::*;
 pub use crash_recovery::*;
 pub use diagnostics::*;
+pub use hashrate_tracker::*;
+pub use ipc_server::*;
+pub use logging::*;
+pub use metrics::*;
+pub use mining_history::*;
+pub use mining_mode::*;
 pub use plugin::*;
 pub use process::*;
 pub use remote::*;
 pub use session_manager::*;
+pub use session_persistence::*;
+pub use session_scrub::*;
+pub use stratum_proxy::*;
 pub use telemetry::*;
 pub use thread_budget::*;
+pub use worker_manager::*;
 
 use openminedash_miner_adapters::{
-    CpuminerOptAdapter, MinerState, MiningConfig as AdapterMiningConfig, PerformancePreset,
+    validate_config as validate_adapter_config, CpuminerOptAdapter, MinerState,
+    MiningConfig as AdapterMiningConfig, PerformancePreset, PoolFailoverStatus, StratumV2Adapter,
     XMRigAdapter,
 };
+use openminedash_pools::{parse_pool_url, StratumClient, StratumStats};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -51,6 +81,10 @@ pub enum CoreError {
     InvalidState,
     #[error("Plugin validation failed: {0}")]
     PluginValidation(String),
+    #[error("Stratum error: {0}")]
+    Stratum(String),
+    #[error("Diagnostics error: {0}")]
+    Diagnostics(String),
 }
 
 pub type Result<T> = std::result::Result<T, CoreError>;
@@ -71,6 +105,36 @@ pub struct MiningConfig {
     /// Enable "Try Mining Anyway" mode for non-CPU-optimized coins
     #[serde(default)]
     pub try_anyway: bool,
+    /// Additional pools to fail over to, in order, after `pool`.
+    #[serde(default)]
+    pub failover_pools: Vec<String>,
+    /// Force a mining protocol (currently only `"sv2"` has any effect).
+    /// Normally left unset - a `sv2://` scheme on `pool` is enough to route
+    /// to the Stratum V2 adapter.
+    #[serde(default)]
+    pub protocol: Option<String>,
+    /// Run the miner at reduced OS scheduling priority so the machine stays
+    /// responsive while mining in the background.
+    #[serde(default)]
+    pub lower_priority: bool,
+    /// Pool password - a literal, `env:VAR`, or `file:/path`. See
+    /// `AdapterMiningConfig::credential` for the full contract. `None`
+    /// means "no password needed".
+    #[serde(default)]
+    pub credential: Option<String>,
+}
+
+impl MiningConfig {
+    /// Rewrite `threads`, `preset`, and `lower_priority` to whatever the
+    /// given `MiningMode` prescribes, using `budget` to size Eco/Balanced
+    /// thread counts. Called right before launch so every entry point
+    /// (legacy single session, multi-session) launches under the same
+    /// active mode.
+    pub fn apply_mode(&mut self, mode: MiningMode, budget: &ThreadBudgetSettings) {
+        self.threads = mode.resolve_threads(budget, 0);
+        self.preset = mode.preset();
+        self.lower_priority = mode.lower_priority();
+    }
 }
 
 /// Which miner is currently active
@@ -80,6 +144,7 @@ pub enum ActiveMiner {
     None,
     XMRig,
     CpuminerOpt,
+    StratumV2,
 }
 
 impl From<MiningConfig> for AdapterMiningConfig {
@@ -93,6 +158,9 @@ impl From<MiningConfig> for AdapterMiningConfig {
             worker: c.worker,
             threads: c.threads,
             preset: c.preset,
+            failover_pools: c.failover_pools,
+            lower_priority: c.lower_priority,
+            credential: c.credential,
         }
     }
 }
@@ -118,6 +186,14 @@ pub struct MiningStatus {
     /// Timestamp when mining started (for elapsed time calculation)
     #[serde(default)]
     pub started_at: u64,
+    /// Multi-pool failover status, when failover pools are configured
+    #[serde(default)]
+    pub failover: Option<PoolFailoverStatus>,
+    /// Latest rolling-window statistics tick (EMA hashrate, shares/min,
+    /// rejection ratio), `None` until the first tick elapses after mining
+    /// starts. See `telemetry::StatsTracker`.
+    #[serde(default)]
+    pub telemetry: Option<TelemetrySnapshot>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,9 +241,20 @@ pub struct AppState {
     miner_process: Option<Child>,
     xmrig_adapter: XMRigAdapter,
     cpuminer_adapter: CpuminerOptAdapter,
+    stratum_v2_adapter: StratumV2Adapter,
     active_miner: ActiveMiner,
     crash_recovery: CrashRecoveryState,
     remote_endpoints: Vec<RemoteEndpoint>,
+    /// Adapter config last used to start mining, kept so a pool-failover
+    /// rotation can restart the miner against the next pool.
+    last_adapter_config: Option<AdapterMiningConfig>,
+    /// Direct Stratum connection used to mine without shelling out to an
+    /// external miner binary - see `connect_stratum`/`stratum_connect`.
+    stratum_client: Option<StratumClient>,
+    /// Rolling hashrate/share statistics for the active session, ticked
+    /// from `refresh_stats`. Not persisted - a fresh tracker is created on
+    /// every app start, same as the miner adapters.
+    stats_tracker: StatsTracker,
 }
 
 impl AppState {
@@ -184,9 +271,13 @@ impl AppState {
             miner_process: None,
             xmrig_adapter: XMRigAdapter::new(),
             cpuminer_adapter: CpuminerOptAdapter::new(),
+            stratum_v2_adapter: StratumV2Adapter::new(),
             active_miner: ActiveMiner::None,
             crash_recovery,
             remote_endpoints: Vec::new(),
+            last_adapter_config: None,
+            stratum_client: None,
+            stats_tracker: StatsTracker::new(),
         }
     }
 
@@ -315,9 +406,54 @@ impl AppState {
         PathBuf::from("assets/coins")
     }
 
+    /// Validate a `MiningConfig` without starting anything - lets the UI
+    /// surface a malformed pool URL or an unresolvable `credential` before
+    /// the user commits to `start_mining`.
+    pub fn validate_mining_config(&self, config: &MiningConfig) -> Result<()> {
+        let adapter_config: AdapterMiningConfig = config.clone().into();
+        validate_adapter_config(&adapter_config).map_err(|e| CoreError::Miner(e.to_string()))
+    }
+
+    /// Connect directly to a pool over Stratum, bypassing the external
+    /// miner binary entirely. Replaces any existing connection.
+    pub async fn connect_stratum(&mut self, pool: &str, wallet: &str, worker: &str) -> Result<()> {
+        let endpoint = parse_pool_url(pool).map_err(|e| CoreError::Stratum(e.to_string()))?;
+        let client = StratumClient::connect(endpoint, wallet.to_string(), worker.to_string())
+            .await
+            .map_err(|e| CoreError::Stratum(e.to_string()))?;
+        if let Some(previous) = self.stratum_client.replace(client) {
+            previous.disconnect();
+        }
+        Ok(())
+    }
+
+    /// Tear down the active Stratum connection, if any.
+    pub fn disconnect_stratum(&mut self) {
+        if let Some(client) = self.stratum_client.take() {
+            client.disconnect();
+        }
+    }
+
+    /// Live stats for the active Stratum connection, if any.
+    pub fn stratum_stats(&self) -> Option<StratumStats> {
+        self.stratum_client.as_ref().map(|c| c.stats())
+    }
+
+    /// Active Eco/Balanced/Ludicrous preset.
+    pub fn mining_mode(&self) -> MiningMode {
+        self.config.mining_mode
+    }
+
+    /// Persist a new mining mode. Takes effect on the next `start_mining`/
+    /// `start_session`; does not touch already-running sessions.
+    pub fn set_mining_mode(&mut self, mode: MiningMode) -> Result<()> {
+        self.config.mining_mode = mode;
+        self.config.save()
+    }
+
     pub async fn start_mining(
         &mut self,
-        config: MiningConfig,
+        mut config: MiningConfig,
         app_handle: tauri::AppHandle,
     ) -> Result<()> {
         if !self.config.consent {
@@ -329,14 +465,24 @@ impl AppState {
             return Err(CoreError::InvalidState);
         }
 
+        config.apply_mode(self.config.mining_mode, &self.config.thread_budget);
+
+        self.validate_mining_config(&config)?;
+
         self.status.state = "starting".to_string();
 
-        // Route to appropriate miner based on algorithm
-        let routing = route_algorithm(&config.algorithm, config.try_anyway);
-        
+        // Route to appropriate miner based on algorithm and pool protocol
+        let routing = route_with_protocol(
+            &config.algorithm,
+            config.try_anyway,
+            &config.pool,
+            config.protocol.as_deref(),
+        );
+
         let (miner_name, warning) = match routing.miner_type {
             MinerType::XMRig => ("xmrig".to_string(), routing.warning),
             MinerType::CpuminerOpt => ("cpuminer-opt".to_string(), routing.warning),
+            MinerType::StratumV2 => ("stratum-v2".to_string(), routing.warning),
             MinerType::External | MinerType::Unsupported => {
                 self.status.state = "stopped".to_string();
                 return Err(CoreError::Miner(
@@ -360,7 +506,7 @@ impl AppState {
         let _ = create_mining_lock(&session);
 
         let adapter_config: AdapterMiningConfig = config.clone().into();
-        
+
         // Start the appropriate miner
         let child = match routing.miner_type {
             MinerType::XMRig => {
@@ -377,9 +523,17 @@ impl AppState {
                     .await
                     .map_err(|e| CoreError::Miner(e.to_string()))?
             }
+            MinerType::StratumV2 => {
+                self.active_miner = ActiveMiner::StratumV2;
+                self.stratum_v2_adapter
+                    .start(&adapter_config, app_handle)
+                    .await
+                    .map_err(|e| CoreError::Miner(e.to_string()))?
+            }
             _ => unreachable!(),
         };
 
+        self.last_adapter_config = Some(adapter_config);
         self.miner_process = Some(child);
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -397,6 +551,13 @@ impl AppState {
             ..Default::default()
         };
 
+        tracing::info!(
+            target: "miner_lifecycle",
+            pool = self.status.pool.as_deref().unwrap_or(""),
+            active_miner = %self.status.active_miner,
+            "mining started"
+        );
+
         Ok(())
     }
 
@@ -404,17 +565,26 @@ impl AppState {
         // Check if any miner is running
         let xmrig_running = self.xmrig_adapter.state() == MinerState::Running;
         let cpuminer_running = self.cpuminer_adapter.state() == MinerState::Running;
-        
-        if !xmrig_running && !cpuminer_running {
+        let stratum_v2_running = self.stratum_v2_adapter.state() == MinerState::Running;
+
+        if !xmrig_running && !cpuminer_running && !stratum_v2_running {
             return Ok(());
         }
 
         self.status.state = "stopping".to_string();
 
+        tracing::info!(
+            target: "miner_lifecycle",
+            pool = self.status.pool.as_deref().unwrap_or(""),
+            active_miner = %self.status.active_miner,
+            "mining stopped"
+        );
+
         if let Some(mut child) = self.miner_process.take() {
             match self.active_miner {
                 ActiveMiner::XMRig => self.xmrig_adapter.stop(&mut child).await,
                 ActiveMiner::CpuminerOpt => self.cpuminer_adapter.stop(&mut child).await,
+                ActiveMiner::StratumV2 => self.stratum_v2_adapter.stop(&mut child).await,
                 ActiveMiner::None => {}
             }
         }
@@ -423,6 +593,8 @@ impl AppState {
         remove_mining_lock();
 
         self.active_miner = ActiveMiner::None;
+        self.last_adapter_config = None;
+        self.stats_tracker.reset();
         self.status = MiningStatus {
             state: "stopped".to_string(),
             ..Default::default()
@@ -430,7 +602,10 @@ impl AppState {
         Ok(())
     }
 
-    pub async fn refresh_stats(&mut self) -> Result<()> {
+    pub async fn refresh_stats(&mut self, app_handle: &tauri::AppHandle) -> Result<()> {
+        let prev_accepted = self.status.accepted_shares;
+        let prev_rejected = self.status.rejected_shares;
+
         // Calculate elapsed time since mining started (independent of pool connection)
         if self.status.is_running && self.status.started_at > 0 {
             let now = std::time::SystemTime::now()
@@ -471,6 +646,31 @@ impl AppState {
                         tracing::warn!("Failed to get XMRig stats: {}", e);
                     }
                 }
+
+                if let Some(next_pool) = self.xmrig_adapter.check_failover(app_handle).await {
+                    if let Some(mut adapter_config) = self.last_adapter_config.clone() {
+                        tracing::warn!("Pool failover: restarting XMRig against {}", next_pool);
+                        if let Some(mut child) = self.miner_process.take() {
+                            self.xmrig_adapter.stop(&mut child).await;
+                        }
+                        adapter_config.pool = next_pool.clone();
+                        match self.xmrig_adapter.start(&adapter_config, app_handle.clone()).await {
+                            Ok(child) => {
+                                self.miner_process = Some(child);
+                                self.last_adapter_config = Some(adapter_config);
+                                self.status.pool = Some(next_pool);
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to restart XMRig after pool failover: {}", e);
+                                self.status.state = "stopped".to_string();
+                                self.status.is_running = false;
+                                self.active_miner = ActiveMiner::None;
+                            }
+                        }
+                    }
+                }
+
+                self.status.failover = self.xmrig_adapter.failover_status();
             }
             ActiveMiner::CpuminerOpt => {
                 if self.cpuminer_adapter.state() != MinerState::Running {
@@ -482,10 +682,90 @@ impl AppState {
                 self.status.accepted_shares = stats.accepted;
                 self.status.rejected_shares = stats.rejected;
                 self.status.uptime = stats.uptime;
+
+                if let Some(next_pool) = self.cpuminer_adapter.check_failover(app_handle).await {
+                    if let Some(mut adapter_config) = self.last_adapter_config.clone() {
+                        tracing::warn!("Pool failover: restarting cpuminer-opt against {}", next_pool);
+                        if let Some(mut child) = self.miner_process.take() {
+                            self.cpuminer_adapter.stop(&mut child).await;
+                        }
+                        adapter_config.pool = next_pool.clone();
+                        match self.cpuminer_adapter.start(&adapter_config, app_handle.clone()).await {
+                            Ok(child) => {
+                                self.miner_process = Some(child);
+                                self.last_adapter_config = Some(adapter_config);
+                                self.status.pool = Some(next_pool);
+                            }
+                            Err(e) => {
+                                tracing::error!("Failed to restart cpuminer-opt after pool failover: {}", e);
+                                self.status.state = "stopped".to_string();
+                                self.status.is_running = false;
+                                self.active_miner = ActiveMiner::None;
+                            }
+                        }
+                    }
+                }
+
+                self.status.failover = self.cpuminer_adapter.failover_status();
+            }
+            ActiveMiner::StratumV2 => {
+                if self.stratum_v2_adapter.state() != MinerState::Running {
+                    return Ok(());
+                }
+                match self.stratum_v2_adapter.get_stats().await {
+                    Ok(stats) => {
+                        self.status.hashrate = stats.hashrate;
+                        self.status.accepted_shares = stats.accepted_shares;
+                        self.status.rejected_shares = stats.rejected_shares;
+                        self.status.uptime = stats.uptime_secs;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to get Stratum V2 stats: {}", e);
+                    }
+                }
             }
             ActiveMiner::None => {}
         }
 
+        let accepted_delta = self.status.accepted_shares.saturating_sub(prev_accepted);
+        let rejected_delta = self.status.rejected_shares.saturating_sub(prev_rejected);
+        if accepted_delta > 0 {
+            tracing::info!(
+                target: "miner_shares",
+                pool = self.status.pool.as_deref().unwrap_or(""),
+                active_miner = %self.status.active_miner,
+                count = accepted_delta,
+                "share(s) accepted"
+            );
+        }
+        if rejected_delta > 0 {
+            tracing::warn!(
+                target: "miner_shares",
+                pool = self.status.pool.as_deref().unwrap_or(""),
+                active_miner = %self.status.active_miner,
+                count = rejected_delta,
+                "share(s) rejected"
+            );
+        }
+
+        if self.active_miner != ActiveMiner::None {
+            if let Some(snapshot) = self.stats_tracker.record(
+                &self.config.telemetry,
+                self.status.hashrate,
+                self.status.accepted_shares,
+                self.status.rejected_shares,
+            ) {
+                if snapshot.reject_warning {
+                    tracing::warn!(
+                        "Rejection ratio {:.1}% over the last tick exceeds the {:.1}% threshold",
+                        snapshot.reject_ratio * 100.0,
+                        self.config.telemetry.reject_ratio_warning_threshold * 100.0,
+                    );
+                }
+                self.status.telemetry = Some(snapshot);
+            }
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,132 @@
+//! Structured, split-target logging: a concise terminal layer and a verbose
+//! rotating file layer, each filtered independently rather than sharing one
+//! global subscriber/level. Miner lifecycle transitions and share
+//! accept/reject events are logged under dedicated `tracing` targets
+//! (`miner_lifecycle`, `miner_shares`) so the file layer can capture the
+//! full detail (pool URL, active miner, job id, rejection reason) while the
+//! terminal layer stays readable.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingSettings {
+    /// Level shown on the terminal - `info` by default, which still shows
+    /// accepted shares (logged at info) and rejected shares (logged at warn).
+    pub terminal_level: String,
+    /// Whether the rotating file target is active at all [DEFAULT: true]
+    pub file_enabled: bool,
+    pub file_dir: PathBuf,
+    /// Level captured to the file target - `debug` by default, since this
+    /// is the target a support bundle actually reads.
+    pub file_level: String,
+    /// Daily log files older than this count are pruned on startup.
+    pub max_log_files: usize,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            terminal_level: "info".to_string(),
+            file_enabled: true,
+            file_dir: default_log_dir(),
+            file_level: "debug".to_string(),
+            max_log_files: 14,
+        }
+    }
+}
+
+fn default_log_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join("logs")
+}
+
+fn parse_level(level: &str) -> LevelFilter {
+    level.parse().unwrap_or(LevelFilter::INFO)
+}
+
+/// Keeps the file target's non-blocking writer thread alive - hold this for
+/// the process lifetime (`main` binds it to a local), dropping it tears the
+/// writer down and any buffered lines are lost.
+pub struct LoggingGuard {
+    _file_guard: Option<tracing_appender::non_blocking::WorkerGuard>,
+}
+
+/// Installs the global tracing subscriber with two independently-filtered
+/// layers and returns the guard that must outlive the process. Call this
+/// once at startup in place of `tracing_subscriber::fmt::init()`.
+pub fn init_logging(settings: &LoggingSettings) -> LoggingGuard {
+    let terminal_layer = tracing_subscriber::fmt::layer()
+        .with_target(false)
+        .compact()
+        .with_filter(parse_level(&settings.terminal_level));
+
+    let (file_layer, file_guard) = if settings.file_enabled {
+        let _ = std::fs::create_dir_all(&settings.file_dir);
+        prune_old_logs(&settings.file_dir, settings.max_log_files);
+
+        let file_appender = tracing_appender::rolling::daily(&settings.file_dir, "openminedash.log");
+        let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+        let layer = tracing_subscriber::fmt::layer()
+            .with_target(true)
+            .with_ansi(false)
+            .with_writer(non_blocking)
+            .with_filter(parse_level(&settings.file_level));
+        (Some(layer), Some(guard))
+    } else {
+        (None, None)
+    };
+
+    tracing_subscriber::registry()
+        .with(terminal_layer)
+        .with(file_layer)
+        .init();
+
+    LoggingGuard { _file_guard: file_guard }
+}
+
+/// Deletes the oldest daily log files beyond `max_files`, since
+/// `tracing_appender`'s daily rotation creates new files forever but never
+/// deletes old ones on its own.
+fn prune_old_logs(dir: &std::path::Path, max_files: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut files: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .collect();
+    if files.len() <= max_files {
+        return;
+    }
+    files.sort_by_key(|e| e.metadata().and_then(|m| m.modified()).unwrap_or(std::time::SystemTime::UNIX_EPOCH));
+    for entry in files.iter().take(files.len() - max_files) {
+        let _ = std::fs::remove_file(entry.path());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_falls_back_to_info_on_garbage() {
+        assert_eq!(parse_level("not-a-level"), LevelFilter::INFO);
+        assert_eq!(parse_level("debug"), LevelFilter::DEBUG);
+    }
+
+    #[test]
+    fn test_prune_old_logs_keeps_only_newest_n() {
+        let dir = std::env::temp_dir().join(format!("openminedash-log-prune-test-{}", std::process::id()));
+        let _ = std::fs::create_dir_all(&dir);
+        for i in 0..5 {
+            std::fs::write(dir.join(format!("openminedash.log.{i}")), b"x").unwrap();
+        }
+        prune_old_logs(&dir, 2);
+        let remaining = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(remaining, 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
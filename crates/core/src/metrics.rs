@@ -0,0 +1,223 @@
+//! Prometheus text-exposition metrics endpoint.
+//!
+//! Publishes what the crate already collects - local miner hashrate/shares,
+//! per-endpoint `RemoteMinerStats`, and per-pool `PoolBalance` - as gauges on
+//! a plain `/metrics` HTTP endpoint, so a Grafana/alertmanager setup can
+//! scrape this process instead of polling each pool API directly.
+
+use crate::RemoteMinerStats;
+use openminedash_pools::PoolBalance;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub bind_address: String,
+    pub port: u16,
+    pub scrape_interval_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false, // Opt-in, like notifications
+            bind_address: "127.0.0.1".to_string(),
+            port: 9090,
+            scrape_interval_secs: 15,
+        }
+    }
+}
+
+/// Local miner snapshot (xmrig/cpuminer-opt), keyed by nothing since there's
+/// a single active local miner at a time.
+#[derive(Debug, Clone, Default)]
+pub struct LocalMinerMetrics {
+    pub hashrate: f64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+}
+
+/// Shared snapshot of everything the exporter should publish. Updated by
+/// whichever task already computes these values (refresh_stats, remote
+/// polling, pool balance fetches) and read by the HTTP handler.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub local_miner: LocalMinerMetrics,
+    pub remote: HashMap<String, (String, RemoteMinerStats)>, // id -> (name, stats)
+    pub pools: HashMap<String, PoolBalance>,                 // pool_name -> balance
+}
+
+pub type SharedMetrics = Arc<RwLock<MetricsSnapshot>>;
+
+pub fn new_shared_metrics() -> SharedMetrics {
+    Arc::new(RwLock::new(MetricsSnapshot::default()))
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render the current snapshot as Prometheus text exposition format.
+fn render_prometheus_text(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP openmine_local_hashrate Local miner hashrate in H/s\n");
+    out.push_str("# TYPE openmine_local_hashrate gauge\n");
+    out.push_str(&format!("openmine_local_hashrate {}\n", snapshot.local_miner.hashrate));
+
+    out.push_str("# HELP openmine_local_accepted_shares Total accepted shares for the local miner\n");
+    out.push_str("# TYPE openmine_local_accepted_shares counter\n");
+    out.push_str(&format!("openmine_local_accepted_shares {}\n", snapshot.local_miner.accepted_shares));
+
+    out.push_str("# HELP openmine_local_rejected_shares Total rejected shares for the local miner\n");
+    out.push_str("# TYPE openmine_local_rejected_shares counter\n");
+    out.push_str(&format!("openmine_local_rejected_shares {}\n", snapshot.local_miner.rejected_shares));
+
+    out.push_str("# HELP openmine_remote_hashrate Hashrate reported by a remote endpoint, in H/s\n");
+    out.push_str("# TYPE openmine_remote_hashrate gauge\n");
+    for (id, (name, stats)) in &snapshot.remote {
+        out.push_str(&format!(
+            "openmine_remote_hashrate{{id=\"{}\",name=\"{}\"}} {}\n",
+            escape_label(id),
+            escape_label(name),
+            stats.hashrate
+        ));
+    }
+
+    out.push_str("# HELP openmine_remote_online Whether a remote endpoint responded to the last poll (1/0)\n");
+    out.push_str("# TYPE openmine_remote_online gauge\n");
+    for (id, (name, stats)) in &snapshot.remote {
+        out.push_str(&format!(
+            "openmine_remote_online{{id=\"{}\",name=\"{}\"}} {}\n",
+            escape_label(id),
+            escape_label(name),
+            if stats.online { 1 } else { 0 }
+        ));
+    }
+
+    out.push_str("# HELP openmine_pool_pending Pending (unpaid) pool balance\n");
+    out.push_str("# TYPE openmine_pool_pending gauge\n");
+    for (pool_name, balance) in &snapshot.pools {
+        out.push_str(&format!(
+            "openmine_pool_pending{{pool=\"{}\",symbol=\"{}\"}} {}\n",
+            escape_label(pool_name),
+            escape_label(&balance.symbol),
+            balance.pending_balance
+        ));
+    }
+
+    out.push_str("# HELP openmine_pool_total_paid Total paid out by the pool to date\n");
+    out.push_str("# TYPE openmine_pool_total_paid gauge\n");
+    for (pool_name, balance) in &snapshot.pools {
+        out.push_str(&format!(
+            "openmine_pool_total_paid{{pool=\"{}\",symbol=\"{}\"}} {}\n",
+            escape_label(pool_name),
+            escape_label(&balance.symbol),
+            balance.total_paid
+        ));
+    }
+
+    out
+}
+
+/// Spawn a background task serving `/metrics` on `config.bind_address:config.port`.
+/// No-op if `config.enabled` is false. Runs until the process exits.
+pub fn spawn_metrics_server(config: MetricsConfig, metrics: SharedMetrics) {
+    if !config.enabled {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let addr = format!("{}:{}", config.bind_address, config.port);
+        let listener = match TcpListener::bind(&addr).await {
+            Ok(l) => l,
+            Err(e) => {
+                warn!("Failed to bind metrics endpoint on {}: {}", addr, e);
+                return;
+            }
+        };
+
+        info!("Prometheus metrics endpoint listening on http://{}/metrics", addr);
+
+        loop {
+            let (mut stream, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Metrics listener accept error: {}", e);
+                    continue;
+                }
+            };
+
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                let mut buf = [0u8; 1024];
+                // We only need to know a request arrived; ignore method/path parsing
+                // since this endpoint serves exactly one resource.
+                let _ = stream.read(&mut buf).await;
+
+                let body = render_prometheus_text(&*metrics.read().await);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus_text_includes_local_miner() {
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.local_miner.hashrate = 1234.5;
+        snapshot.local_miner.accepted_shares = 10;
+
+        let text = render_prometheus_text(&snapshot);
+        assert!(text.contains("openmine_local_hashrate 1234.5"));
+        assert!(text.contains("openmine_local_accepted_shares 10"));
+    }
+
+    #[test]
+    fn test_render_prometheus_text_includes_remote_and_pool_labels() {
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.remote.insert(
+            "rig1".to_string(),
+            ("Garage Rig".to_string(), RemoteMinerStats { online: true, hashrate: 1.2e13, ..Default::default() }),
+        );
+        snapshot.pools.insert(
+            "SupportXMR".to_string(),
+            PoolBalance {
+                pool_name: "SupportXMR".to_string(),
+                pending_balance: 0.05,
+                total_paid: 1.2,
+                min_payout: 0.1,
+                symbol: "XMR".to_string(),
+                last_payment: None,
+                hashrate: None,
+            },
+        );
+
+        let text = render_prometheus_text(&snapshot);
+        assert!(text.contains("openmine_remote_hashrate{id=\"rig1\",name=\"Garage Rig\"} 12000000000000"));
+        assert!(text.contains("openmine_pool_pending{pool=\"SupportXMR\",symbol=\"XMR\"} 0.05"));
+    }
+
+    #[test]
+    fn test_metrics_config_default_disabled() {
+        let config = MetricsConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.port, 9090);
+    }
+}
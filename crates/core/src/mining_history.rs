@@ -1,7 +1,26 @@
-//! Mining history persistence
-//! Stores mining session history to disk for tracking earnings over time
+//! Mining history persistence.
+//!
+//! Records are appended one-per-line to `mining_history.jsonl` instead of
+//! rewriting the whole store on every save, the way the old single-file
+//! `mining_history.json` format did: an append is O(1) regardless of how
+//! much history already exists, and a torn write only risks the last
+//! unfinished line rather than the entire history. Running totals live in
+//! their own small `mining_history_totals.json` that's still rewritten in
+//! full on every update, but it's a handful of integers rather than
+//! thousands of records. `started_at`/`coin` indices are rebuilt in memory
+//! on load so `records_by_coin`/`records_in_range` are lookups, not full
+//! scans.
+//!
+//! History growth is bounded by a [`RetentionPolicy`] (borrowing the idea
+//! of journaldb's selectable pruning `Algorithm`), applied automatically
+//! whenever a record is added. Evicted records don't just vanish: their
+//! contribution is folded into `retired` totals first, so [`HistorySummary`]
+//! stays accurate for the full lifetime of the history even once the
+//! underlying records are gone.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 use std::path::PathBuf;
 use tracing::{info, warn};
 
@@ -23,84 +42,288 @@ pub struct MiningRecord {
     pub algorithm: String,
 }
 
-/// Mining history store
+/// How long mining history is kept around before old records are pruned.
+/// Modeled on journaldb's selectable pruning `Algorithm`: pick whichever
+/// shape fits how long-lived the rig is expected to be.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RetentionPolicy {
+    /// Never prune (the original, unbounded behavior).
+    KeepAll,
+    /// Keep only the most recent `N` records.
+    MaxRecords(usize),
+    /// Keep only records started within the last `N` days.
+    MaxAgeDays(u64),
+    /// Apply both: drop anything older than `max_age_days`, then cap what's
+    /// left to `max_records`.
+    MaxRecordsAndAge { max_records: usize, max_age_days: u64 },
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        RetentionPolicy::KeepAll
+    }
+}
+
+impl RetentionPolicy {
+    fn thresholds(&self) -> (Option<usize>, Option<u64>) {
+        match self {
+            RetentionPolicy::KeepAll => (None, None),
+            RetentionPolicy::MaxRecords(n) => (Some(*n), None),
+            RetentionPolicy::MaxAgeDays(days) => (None, Some(*days)),
+            RetentionPolicy::MaxRecordsAndAge { max_records, max_age_days } => {
+                (Some(*max_records), Some(*max_age_days))
+            }
+        }
+    }
+}
+
+/// Lifetime per-coin contribution of records that have been pruned away.
+/// Folded into before the records themselves are dropped, so
+/// [`HistorySummary::by_coin`] doesn't lose history to retention.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RetiredCoinTotals {
+    symbol: String,
+    total_time_secs: u64,
+    total_accepted: u64,
+    total_rejected: u64,
+    session_count: usize,
+    wallets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RetiredTotals {
+    by_coin: HashMap<String, RetiredCoinTotals>,
+}
+
+/// Running totals across the whole history, including records retention
+/// has since pruned. Kept in their own small file so updating them doesn't
+/// require rewriting the record log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HistoryTotals {
+    total_sessions: usize,
+    total_time_secs: u64,
+    total_accepted_shares: u64,
+    total_rejected_shares: u64,
+    retired: RetiredTotals,
+}
+
+/// Mining history store: an append-only log of `MiningRecord`s, plus
+/// in-memory indices for fast coin/time-range lookups.
+#[derive(Debug, Clone, Default)]
 pub struct MiningHistory {
-    pub records: Vec<MiningRecord>,
-    pub total_time_secs: u64,
-    pub total_accepted_shares: u64,
-    pub total_rejected_shares: u64,
+    records: Vec<MiningRecord>,
+    totals: HistoryTotals,
+    by_coin: HashMap<String, Vec<usize>>,
+    by_started_at: BTreeMap<u64, Vec<usize>>,
+    retention: RetentionPolicy,
 }
 
 impl MiningHistory {
-    /// Load history from disk
+    /// Load history from disk, migrating a legacy `mining_history.json`
+    /// full-file store into the append-only log on first run if one is
+    /// found.
     pub fn load() -> Self {
-        let path = Self::history_path();
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => {
-                    match serde_json::from_str(&content) {
-                        Ok(history) => {
-                            info!("Loaded mining history with {} records", 
-                                  Self::record_count(&history));
-                            return history;
-                        }
+        if !Self::log_path().exists() {
+            if let Some(migrated) = Self::migrate_legacy_json() {
+                return migrated;
+            }
+        }
+
+        let mut history = Self {
+            totals: Self::load_totals(),
+            ..Self::default()
+        };
+
+        match std::fs::read_to_string(Self::log_path()) {
+            Ok(content) => {
+                for (line_no, line) in content.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    match serde_json::from_str::<MiningRecord>(line) {
+                        Ok(record) => history.index_record(record),
                         Err(e) => {
-                            warn!("Failed to parse mining history: {}", e);
+                            // A torn write only ever corrupts the last line;
+                            // skip it instead of losing everything before it.
+                            warn!("Skipping unreadable history record at line {}: {}", line_no + 1, e);
                         }
                     }
                 }
-                Err(e) => {
-                    warn!("Failed to read mining history: {}", e);
-                }
             }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read mining history log: {}", e),
         }
-        Self::default()
+
+        info!("Loaded mining history with {} records", history.records.len());
+        history
     }
 
-    fn record_count(history: &MiningHistory) -> usize {
-        history.records.len()
+    /// Set the retention policy, pruning immediately if the new policy is
+    /// stricter than the history's current size/age.
+    pub fn set_retention_policy(&mut self, policy: RetentionPolicy) {
+        self.retention = policy;
+        self.apply_retention();
     }
 
-    /// Save history to disk
-    pub fn save(&self) -> Result<(), std::io::Error> {
-        let path = Self::history_path();
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        let content = serde_json::to_string_pretty(self)?;
-        std::fs::write(&path, content)?;
-        info!("Saved mining history with {} records", self.records.len());
-        Ok(())
+    pub fn retention_policy(&self) -> &RetentionPolicy {
+        &self.retention
     }
 
-    /// Add a completed mining session
+    /// Append a completed mining session. O(1) in the common case: one line
+    /// appended to the log plus a rewrite of the small totals file. Applies
+    /// the retention policy afterward, which rewrites the log only on the
+    /// (infrequent) ticks where something is actually pruned.
     pub fn add_record(&mut self, record: MiningRecord) {
-        self.total_time_secs += record.duration_secs;
-        self.total_accepted_shares += record.accepted_shares;
-        self.total_rejected_shares += record.rejected_shares;
-        self.records.push(record);
-        let _ = self.save();
+        self.totals.total_sessions += 1;
+        self.totals.total_time_secs += record.duration_secs;
+        self.totals.total_accepted_shares += record.accepted_shares;
+        self.totals.total_rejected_shares += record.rejected_shares;
+
+        if let Err(e) = Self::append_record(&record) {
+            warn!("Failed to append mining history record: {}", e);
+        }
+
+        self.index_record(record);
+        self.apply_retention();
+
+        if let Err(e) = Self::save_totals(&self.totals) {
+            warn!("Failed to save mining history totals: {}", e);
+        }
+    }
+
+    /// All records, in the order they were recorded.
+    pub fn records(&self) -> &[MiningRecord] {
+        &self.records
+    }
+
+    pub fn total_time_secs(&self) -> u64 {
+        self.totals.total_time_secs
     }
 
-    /// Get records for a specific coin
+    pub fn total_accepted_shares(&self) -> u64 {
+        self.totals.total_accepted_shares
+    }
+
+    pub fn total_rejected_shares(&self) -> u64 {
+        self.totals.total_rejected_shares
+    }
+
+    /// Get records for a specific coin - an index lookup, not a full scan.
+    /// Only covers records retention hasn't pruned yet; see
+    /// [`Self::get_summary`] for lifetime per-coin totals.
     pub fn records_by_coin(&self, coin: &str) -> Vec<&MiningRecord> {
-        self.records.iter().filter(|r| r.coin == coin).collect()
+        self.by_coin
+            .get(coin)
+            .map(|indices| indices.iter().map(|&i| &self.records[i]).collect())
+            .unwrap_or_default()
     }
 
-    /// Get records within a time range
+    /// Get records within a time range - a `BTreeMap` range scan, not a
+    /// full filter over every record.
     pub fn records_in_range(&self, start: u64, end: u64) -> Vec<&MiningRecord> {
-        self.records.iter()
-            .filter(|r| r.started_at >= start && r.started_at <= end)
+        self.by_started_at
+            .range(start..=end)
+            .flat_map(|(_, indices)| indices.iter().map(|&i| &self.records[i]))
+            .collect()
+    }
+
+    /// Group records into day/week buckets (by `started_at`), like a block
+    /// explorer's earnings/hashrate chart. Each bucket reports summed
+    /// shares and duration plus a duration-weighted average hashrate (each
+    /// record contributes `avg_hashrate * duration_secs`, so a long steady
+    /// session outweighs a short spiky one). Only covers records retention
+    /// hasn't pruned yet, same as [`Self::records_by_coin`].
+    pub fn timeline(&self, bucket: Bucket, coin: Option<&str>) -> Vec<TimelineBucket> {
+        let bucket_secs = bucket.secs();
+        let mut buckets: BTreeMap<u64, TimelineBucket> = BTreeMap::new();
+
+        let records: Box<dyn Iterator<Item = &MiningRecord>> = match coin {
+            Some(c) => Box::new(self.records_by_coin(c).into_iter()),
+            None => Box::new(self.records.iter()),
+        };
+
+        for record in records {
+            let bucket_start = (record.started_at / bucket_secs) * bucket_secs;
+            let entry = buckets.entry(bucket_start).or_insert_with(|| TimelineBucket {
+                bucket_start,
+                accepted_shares: 0,
+                rejected_shares: 0,
+                duration_secs: 0,
+                avg_hashrate: 0.0,
+            });
+
+            entry.accepted_shares += record.accepted_shares;
+            entry.rejected_shares += record.rejected_shares;
+            entry.duration_secs += record.duration_secs;
+            // Stash the duration-weighted sum here; divided down to a true
+            // average once every record in the bucket has been folded in.
+            entry.avg_hashrate += record.avg_hashrate * record.duration_secs as f64;
+        }
+
+        buckets
+            .into_values()
+            .map(|mut b| {
+                if b.duration_secs > 0 {
+                    b.avg_hashrate /= b.duration_secs as f64;
+                }
+                b
+            })
             .collect()
     }
 
-    /// Get summary statistics
+    /// Stream all records as CSV rows (header plus one row per record), so
+    /// users can analyze earnings history in external tools.
+    pub fn export_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "id,coin,symbol,pool,wallet,worker,started_at,ended_at,duration_secs,accepted_shares,rejected_shares,avg_hashrate,algorithm"
+        )?;
+
+        for record in &self.records {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&record.id),
+                csv_field(&record.coin),
+                csv_field(&record.symbol),
+                csv_field(&record.pool),
+                csv_field(&record.wallet),
+                csv_field(&record.worker),
+                record.started_at,
+                record.ended_at,
+                record.duration_secs,
+                record.accepted_shares,
+                record.rejected_shares,
+                record.avg_hashrate,
+                csv_field(&record.algorithm),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Get summary statistics, including the lifetime contribution of
+    /// records retention has since pruned.
     pub fn get_summary(&self) -> HistorySummary {
-        let mut by_coin: std::collections::HashMap<String, CoinSummary> = std::collections::HashMap::new();
-        
+        let mut by_coin: HashMap<String, CoinSummary> = HashMap::new();
+
+        for (coin, retired) in &self.totals.retired.by_coin {
+            by_coin.insert(
+                coin.clone(),
+                CoinSummary {
+                    coin: coin.clone(),
+                    symbol: retired.symbol.clone(),
+                    total_time_secs: retired.total_time_secs,
+                    total_accepted: retired.total_accepted,
+                    total_rejected: retired.total_rejected,
+                    session_count: retired.session_count,
+                    wallets: retired.wallets.clone(),
+                },
+            );
+        }
+
         for record in &self.records {
-            let entry = by_coin.entry(record.coin.clone()).or_insert(CoinSummary {
+            let entry = by_coin.entry(record.coin.clone()).or_insert_with(|| CoinSummary {
                 coin: record.coin.clone(),
                 symbol: record.symbol.clone(),
                 total_time_secs: 0,
@@ -109,41 +332,275 @@ impl MiningHistory {
                 session_count: 0,
                 wallets: Vec::new(),
             });
-            
+
+            entry.symbol = record.symbol.clone();
             entry.total_time_secs += record.duration_secs;
             entry.total_accepted += record.accepted_shares;
             entry.total_rejected += record.rejected_shares;
             entry.session_count += 1;
-            
+
             if !entry.wallets.contains(&record.wallet) {
                 entry.wallets.push(record.wallet.clone());
             }
         }
 
         HistorySummary {
-            total_sessions: self.records.len(),
-            total_time_secs: self.total_time_secs,
-            total_accepted_shares: self.total_accepted_shares,
-            total_rejected_shares: self.total_rejected_shares,
+            total_sessions: self.totals.total_sessions,
+            total_time_secs: self.totals.total_time_secs,
+            total_accepted_shares: self.totals.total_accepted_shares,
+            total_rejected_shares: self.totals.total_rejected_shares,
             by_coin: by_coin.into_values().collect(),
         }
     }
 
-    /// Clear all history
+    /// Clear all history, including retained lifetime totals.
     pub fn clear(&mut self) {
         self.records.clear();
-        self.total_time_secs = 0;
-        self.total_accepted_shares = 0;
-        self.total_rejected_shares = 0;
-        let _ = self.save();
+        self.by_coin.clear();
+        self.by_started_at.clear();
+        self.totals = HistoryTotals::default();
+
+        if let Err(e) = std::fs::remove_file(Self::log_path()) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove mining history log: {}", e);
+            }
+        }
+        if let Err(e) = Self::save_totals(&self.totals) {
+            warn!("Failed to save mining history totals: {}", e);
+        }
     }
 
-    fn history_path() -> PathBuf {
+    /// Evict records that fall outside the current retention policy,
+    /// folding their contribution into `retired` totals first and
+    /// rewriting the log if anything was actually dropped.
+    fn apply_retention(&mut self) {
+        let (max_records, max_age_days) = self.retention.thresholds();
+        if max_records.is_none() && max_age_days.is_none() {
+            return;
+        }
+
+        let cutoff = max_age_days.map(|days| now_secs().saturating_sub(days.saturating_mul(86_400)));
+        let before = self.records.len();
+
+        let records = std::mem::take(&mut self.records);
+        let (aged_out, mut kept): (Vec<MiningRecord>, Vec<MiningRecord>) =
+            records.into_iter().partition(|r| cutoff.map_or(false, |c| r.started_at < c));
+
+        let mut retired = aged_out;
+        if let Some(max) = max_records {
+            if kept.len() > max {
+                let overflow = kept.len() - max;
+                retired.extend(kept.drain(0..overflow));
+            }
+        }
+
+        if retired.is_empty() {
+            self.records = kept;
+            return;
+        }
+
+        for record in retired {
+            let entry = self.totals.retired.by_coin.entry(record.coin.clone()).or_default();
+            entry.symbol = record.symbol.clone();
+            entry.total_time_secs += record.duration_secs;
+            entry.total_accepted += record.accepted_shares;
+            entry.total_rejected += record.rejected_shares;
+            entry.session_count += 1;
+            if !entry.wallets.contains(&record.wallet) {
+                entry.wallets.push(record.wallet.clone());
+            }
+        }
+
+        self.by_coin.clear();
+        self.by_started_at.clear();
+        for (idx, record) in kept.iter().enumerate() {
+            self.by_coin.entry(record.coin.clone()).or_default().push(idx);
+            self.by_started_at.entry(record.started_at).or_default().push(idx);
+        }
+        self.records = kept;
+
+        info!("Retention policy pruned {} mining history record(s)", before - self.records.len());
+        if let Err(e) = Self::rewrite_log(&self.records) {
+            warn!("Failed to rewrite mining history log after pruning: {}", e);
+        }
+    }
+
+    /// Add `record` to the in-memory indices. Must run exactly once per
+    /// record, in the same order it was (or will be) appended to the log.
+    fn index_record(&mut self, record: MiningRecord) {
+        let idx = self.records.len();
+        self.by_coin.entry(record.coin.clone()).or_default().push(idx);
+        self.by_started_at.entry(record.started_at).or_default().push(idx);
+        self.records.push(record);
+    }
+
+    fn append_record(record: &MiningRecord) -> std::io::Result<()> {
+        let path = Self::log_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let line = serde_json::to_string(record).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)?;
+        file.flush()
+    }
+
+    /// Rewrite the whole log from `records`. Only used by retention
+    /// pruning, which is infrequent relative to `add_record`'s appends.
+    fn rewrite_log(records: &[MiningRecord]) -> std::io::Result<()> {
+        let path = Self::log_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for record in records {
+            let line = serde_json::to_string(record).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        std::fs::write(&path, content)
+    }
+
+    fn load_totals() -> HistoryTotals {
+        match std::fs::read_to_string(Self::totals_path()) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => HistoryTotals::default(),
+        }
+    }
+
+    fn save_totals(totals: &HistoryTotals) -> std::io::Result<()> {
+        let path = Self::totals_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(totals).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&path, content)
+    }
+
+    /// One-time import of the old single-file `mining_history.json` store
+    /// into the append-only log, if one exists. The legacy file is renamed
+    /// rather than deleted, so a failed or partial migration can't lose
+    /// data.
+    fn migrate_legacy_json() -> Option<Self> {
+        let legacy_path = Self::legacy_json_path();
+        if !legacy_path.exists() {
+            return None;
+        }
+
+        #[derive(Deserialize)]
+        struct LegacyHistory {
+            records: Vec<MiningRecord>,
+            #[serde(default)]
+            total_time_secs: u64,
+            #[serde(default)]
+            total_accepted_shares: u64,
+            #[serde(default)]
+            total_rejected_shares: u64,
+        }
+
+        let content = match std::fs::read_to_string(&legacy_path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to read legacy mining history for migration: {}", e);
+                return None;
+            }
+        };
+        let legacy: LegacyHistory = match serde_json::from_str(&content) {
+            Ok(legacy) => legacy,
+            Err(e) => {
+                warn!("Failed to parse legacy mining history for migration: {}", e);
+                return None;
+            }
+        };
+
+        let mut history = Self::default();
+        let session_count = legacy.records.len();
+        for record in legacy.records {
+            if let Err(e) = Self::append_record(&record) {
+                warn!("Failed to migrate mining history record: {}", e);
+            }
+            history.index_record(record);
+        }
+        history.totals = HistoryTotals {
+            total_sessions: session_count,
+            total_time_secs: legacy.total_time_secs,
+            total_accepted_shares: legacy.total_accepted_shares,
+            total_rejected_shares: legacy.total_rejected_shares,
+            retired: RetiredTotals::default(),
+        };
+        if let Err(e) = Self::save_totals(&history.totals) {
+            warn!("Failed to save migrated mining history totals: {}", e);
+        }
+
+        let migrated_path = legacy_path.with_extension("json.migrated");
+        if let Err(e) = std::fs::rename(&legacy_path, &migrated_path) {
+            warn!("Failed to rename legacy mining history after migration: {}", e);
+        }
+
+        info!("Migrated {} legacy mining history records into the append-only log", history.records.len());
+        Some(history)
+    }
+
+    fn data_dir() -> PathBuf {
         dirs::data_local_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("openminer")
-            .join("mining_history.json")
     }
+
+    fn log_path() -> PathBuf {
+        Self::data_dir().join("mining_history.jsonl")
+    }
+
+    fn totals_path() -> PathBuf {
+        Self::data_dir().join("mining_history_totals.json")
+    }
+
+    fn legacy_json_path() -> PathBuf {
+        Self::data_dir().join("mining_history.json")
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Escape a CSV field: wrap in quotes (doubling any embedded quotes) only
+/// when the value actually needs it.
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Bucket granularity for [`MiningHistory::timeline`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Bucket {
+    Day,
+    Week,
+}
+
+impl Bucket {
+    fn secs(self) -> u64 {
+        match self {
+            Bucket::Day => 86_400,
+            Bucket::Week => 7 * 86_400,
+        }
+    }
+}
+
+/// One time bucket of a [`MiningHistory::timeline`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    pub bucket_start: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub duration_secs: u64,
+    pub avg_hashrate: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,3 +622,73 @@ pub struct CoinSummary {
     pub session_count: usize,
     pub wallets: Vec<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(coin: &str, started_at: u64, duration_secs: u64, avg_hashrate: f64) -> MiningRecord {
+        MiningRecord {
+            id: format!("{}-{}", coin, started_at),
+            coin: coin.to_string(),
+            symbol: coin.to_uppercase(),
+            pool: "pool.example.com:3333".to_string(),
+            wallet: "wallet1".to_string(),
+            worker: "worker1".to_string(),
+            started_at,
+            ended_at: started_at + duration_secs,
+            duration_secs,
+            accepted_shares: 10,
+            rejected_shares: 1,
+            avg_hashrate,
+            algorithm: "randomx".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_timeline_groups_by_day() {
+        let history = MiningHistory {
+            records: vec![
+                record("xmr", 0, 3_600, 100.0),
+                record("xmr", 3_600, 3_600, 200.0),
+                record("xmr", 90_000, 3_600, 50.0),
+            ],
+            ..MiningHistory::default()
+        };
+
+        let buckets = history.timeline(Bucket::Day, None);
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].bucket_start, 0);
+        assert_eq!(buckets[0].duration_secs, 7_200);
+        assert_eq!(buckets[0].accepted_shares, 20);
+        // Duration-weighted average: (100*3600 + 200*3600) / 7200 = 150
+        assert!((buckets[0].avg_hashrate - 150.0).abs() < f64::EPSILON);
+        assert_eq!(buckets[1].bucket_start, 86_400);
+    }
+
+    #[test]
+    fn test_timeline_filters_by_coin() {
+        let mut history = MiningHistory::default();
+        history.index_record(record("xmr", 0, 60, 10.0));
+        history.index_record(record("rvn", 0, 60, 20.0));
+
+        let buckets = history.timeline(Bucket::Day, Some("rvn"));
+        assert_eq!(buckets.len(), 1);
+        assert!((buckets[0].avg_hashrate - 20.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_export_csv_escapes_commas() {
+        let mut history = MiningHistory::default();
+        let mut rec = record("xmr", 0, 60, 10.0);
+        rec.pool = "pool, with, commas".to_string();
+        history.index_record(rec);
+
+        let mut out = Vec::new();
+        history.export_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        assert!(csv.starts_with("id,coin,symbol,pool,"));
+        assert!(csv.contains("\"pool, with, commas\""));
+    }
+}
@@ -0,0 +1,113 @@
+//! Eco/Balanced/Ludicrous mining-mode presets.
+//!
+//! A `MiningMode` is a single user-facing knob that rewrites the handful of
+//! `MiningConfig`/`SessionConfig` fields that actually affect CPU load
+//! before a miner launches - thread count, the adapter-level performance
+//! preset, and OS scheduling priority - so switching modes always launches
+//! the miner the same way, regardless of which UI surface (legacy single
+//! session vs multi-session) triggered it.
+
+use crate::{suggest_threads_for_new_session, BudgetPreset, ThreadBudgetSettings};
+use openminedash_miner_adapters::PerformancePreset;
+use serde::{Deserialize, Serialize};
+
+/// Mining-mode preset, persisted in `AppConfig` alongside `thread_budget`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MiningMode {
+    /// Caps threads to a tight fraction of logical cores and lowers CPU
+    /// priority, so mining stays in the background of whatever else the
+    /// machine is doing.
+    Eco,
+    /// Uses the thread budget as configured - no additional capping.
+    #[default]
+    Balanced,
+    /// Every logical core, at max intensity. Not for daily driving.
+    Ludicrous,
+}
+
+impl MiningMode {
+    /// Resolve how many threads a session should launch with under this
+    /// mode. Eco ignores the user's configured `ThreadBudgetSettings`
+    /// preset and always applies the tighter `BudgetPreset::Eco` cap;
+    /// Balanced defers to the thread budget as-is; Ludicrous claims every
+    /// logical core.
+    pub fn resolve_threads(&self, settings: &ThreadBudgetSettings, active_sessions: u32) -> u32 {
+        match self {
+            Self::Eco => {
+                let eco_settings = ThreadBudgetSettings {
+                    preset: BudgetPreset::Eco,
+                    ..settings.clone()
+                };
+                suggest_threads_for_new_session(&eco_settings, active_sessions)
+            }
+            Self::Balanced => suggest_threads_for_new_session(settings, active_sessions),
+            Self::Ludicrous => num_cpus::get() as u32,
+        }
+    }
+
+    /// Adapter-level performance preset this mode maps to.
+    pub fn preset(&self) -> PerformancePreset {
+        match self {
+            Self::Eco => PerformancePreset::Eco,
+            Self::Balanced => PerformancePreset::Balanced,
+            Self::Ludicrous => PerformancePreset::Max,
+        }
+    }
+
+    /// Whether the miner process should run at reduced OS scheduling
+    /// priority under this mode.
+    pub fn lower_priority(&self) -> bool {
+        matches!(self, Self::Eco)
+    }
+
+    /// Stable string id used by the tray's Performance submenu and by
+    /// `tray::update_tray` to show the active mode.
+    pub fn tray_label(&self) -> &'static str {
+        match self {
+            Self::Eco => "eco",
+            Self::Balanced => "balanced",
+            Self::Ludicrous => "ludicrous",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_balanced() {
+        assert_eq!(MiningMode::default(), MiningMode::Balanced);
+    }
+
+    #[test]
+    fn test_eco_caps_tighter_than_balanced() {
+        let settings = ThreadBudgetSettings {
+            preset: BudgetPreset::Max,
+            ..ThreadBudgetSettings::default()
+        };
+        let eco_threads = MiningMode::Eco.resolve_threads(&settings, 0);
+        let balanced_threads = MiningMode::Balanced.resolve_threads(&settings, 0);
+        assert!(eco_threads <= balanced_threads);
+    }
+
+    #[test]
+    fn test_ludicrous_uses_all_logical_cores() {
+        assert_eq!(MiningMode::Ludicrous.resolve_threads(&ThreadBudgetSettings::default(), 0), num_cpus::get() as u32);
+    }
+
+    #[test]
+    fn test_lower_priority_only_for_eco() {
+        assert!(MiningMode::Eco.lower_priority());
+        assert!(!MiningMode::Balanced.lower_priority());
+        assert!(!MiningMode::Ludicrous.lower_priority());
+    }
+
+    #[test]
+    fn test_tray_labels() {
+        assert_eq!(MiningMode::Eco.tray_label(), "eco");
+        assert_eq!(MiningMode::Balanced.tray_label(), "balanced");
+        assert_eq!(MiningMode::Ludicrous.tray_label(), "ludicrous");
+    }
+}
@@ -3,8 +3,14 @@
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
 use tracing::{info, warn};
 
+/// Default RAPI port for CGMiner/BFGMiner (distinct from the stratum pool port)
+const CGMINER_DEFAULT_PORT: u16 = 4028;
+
 /// Remote miner endpoint configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteEndpoint {
@@ -57,35 +63,151 @@ pub async fn fetch_remote_stats(endpoint: &RemoteEndpoint) -> RemoteMinerStats {
     }
 }
 
-async fn fetch_cgminer_stats(client: &reqwest::Client, url: &str) -> RemoteMinerStats {
-    // CGMiner uses a simple JSON-RPC over TCP, but for HTTP wrapper:
+/// Split a `RemoteEndpoint::url` into (host, port) for the CGMiner RAPI.
+/// Accepts `host:port`, bare `host` (defaults to 4028), or an `http(s)://host[:port]`
+/// value left over from the generic JSON-stats config.
+fn parse_cgminer_host_port(url: &str) -> (String, u16) {
+    let stripped = url
+        .trim_start_matches("http://")
+        .trim_start_matches("https://");
+    let stripped = stripped.trim_end_matches('/');
+
+    match stripped.rsplit_once(':') {
+        Some((host, port_str)) => match port_str.parse::<u16>() {
+            Ok(port) => (host.to_string(), port),
+            Err(_) => (stripped.to_string(), CGMINER_DEFAULT_PORT),
+        },
+        None => (stripped.to_string(), CGMINER_DEFAULT_PORT),
+    }
+}
+
+/// CGMiner's RAPI terminates replies with a NUL byte and some firmwares emit
+/// trailing commas before a closing brace/bracket - strip both before parsing.
+/// Tracks JSON string context (including escape sequences) so a comma inside
+/// a quoted value - e.g. a worker or pool name containing `",]"` - is never
+/// mistaken for a structural one.
+fn sanitize_cgminer_response(raw: &[u8]) -> String {
+    let text = String::from_utf8_lossy(raw);
+    let trimmed = text.trim_matches(|c| c == '\0' || c == '\n' || c == '\r');
+    let mut cleaned = String::with_capacity(trimmed.len());
+    let mut chars = trimmed.chars().peekable();
+    let mut in_string = false;
+    let mut escaped = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            cleaned.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        if c == '"' {
+            in_string = true;
+            cleaned.push(c);
+            continue;
+        }
+
+        if c == ',' {
+            // Look ahead past whitespace for a closing brace/bracket
+            let mut lookahead = chars.clone();
+            let mut next_is_close = false;
+            while let Some(&peek) = lookahead.peek() {
+                if peek.is_whitespace() {
+                    lookahead.next();
+                    continue;
+                }
+                next_is_close = peek == '}' || peek == ']';
+                break;
+            }
+            if next_is_close {
+                continue;
+            }
+        }
+        cleaned.push(c);
+    }
+    cleaned
+}
+
+/// Send a single RAPI command (e.g. `{"command":"summary"}`) to a CGMiner/BFGMiner
+/// socket and return the parsed, sanitized JSON response.
+async fn cgminer_rpc(host: &str, port: u16, command: &str) -> Result<serde_json::Value, String> {
+    let request = serde_json::json!({ "command": command }).to_string();
+
+    let mut stream = timeout(Duration::from_secs(10), TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| "Connection timed out".to_string())?
+        .map_err(|e| format!("Connection failed: {}", e))?;
+
+    timeout(Duration::from_secs(10), stream.write_all(request.as_bytes()))
+        .await
+        .map_err(|_| "Write timed out".to_string())?
+        .map_err(|e| format!("Write failed: {}", e))?;
+
+    let mut raw = Vec::new();
+    timeout(Duration::from_secs(10), stream.read_to_end(&mut raw))
+        .await
+        .map_err(|_| "Read timed out".to_string())?
+        .map_err(|e| format!("Read failed: {}", e))?;
+
+    let sanitized = sanitize_cgminer_response(&raw);
+    serde_json::from_str(&sanitized).map_err(|e| format!("Failed to parse RAPI response: {}", e))
+}
+
+async fn fetch_cgminer_stats(_client: &reqwest::Client, url: &str) -> RemoteMinerStats {
+    // CGMiner/BFGMiner expose their RAPI as line-based JSON-RPC over a raw TCP
+    // socket, not HTTP - so we talk to it directly instead of through reqwest.
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
 
-    match client.get(format!("{}/summary", url)).send().await {
-        Ok(resp) => {
-            if let Ok(data) = resp.json::<serde_json::Value>().await {
-                RemoteMinerStats {
-                    online: true,
-                    hashrate: data["SUMMARY"][0]["GHS 5s"].as_f64().unwrap_or(0.0) * 1_000_000_000.0,
-                    hashrate_unit: "H/s".to_string(),
-                    accepted_shares: data["SUMMARY"][0]["Accepted"].as_u64().unwrap_or(0),
-                    rejected_shares: data["SUMMARY"][0]["Rejected"].as_u64().unwrap_or(0),
-                    uptime_secs: data["SUMMARY"][0]["Elapsed"].as_u64().unwrap_or(0),
-                    pool: data["SUMMARY"][0]["Pool URL"].as_str().map(|s| s.to_string()),
-                    worker: None,
-                    temperature: None,
-                    fan_speed: None,
-                    error: None,
-                    last_updated: now,
-                }
-            } else {
-                error_stats("Failed to parse response", now)
-            }
+    let (host, port) = parse_cgminer_host_port(url);
+
+    let summary = match cgminer_rpc(&host, port, "summary").await {
+        Ok(data) => data,
+        Err(e) => return error_stats(&e, now),
+    };
+
+    let ghs_5s = summary["SUMMARY"][0]["GHS 5s"].as_f64();
+    let mhs_5s = summary["SUMMARY"][0]["MHS 5s"].as_f64();
+    let hashrate = ghs_5s
+        .map(|v| v * 1_000_000_000.0)
+        .or_else(|| mhs_5s.map(|v| v * 1_000_000.0))
+        .unwrap_or(0.0);
+
+    let mut pool = summary["SUMMARY"][0]["Pool URL"].as_str().map(|s| s.to_string());
+    let mut worker = None;
+    let mut temperature = None;
+
+    if let Ok(pools) = cgminer_rpc(&host, port, "pools").await {
+        if pool.is_none() {
+            pool = pools["POOLS"][0]["URL"].as_str().map(|s| s.to_string());
         }
-        Err(e) => error_stats(&e.to_string(), now),
+        worker = pools["POOLS"][0]["User"].as_str().map(|s| s.to_string());
+    }
+
+    if let Ok(devs) = cgminer_rpc(&host, port, "devs").await {
+        temperature = devs["DEVS"][0]["Temperature"].as_f64();
+    }
+
+    RemoteMinerStats {
+        online: true,
+        hashrate,
+        hashrate_unit: "H/s".to_string(),
+        accepted_shares: summary["SUMMARY"][0]["Accepted"].as_u64().unwrap_or(0),
+        rejected_shares: summary["SUMMARY"][0]["Rejected"].as_u64().unwrap_or(0),
+        uptime_secs: summary["SUMMARY"][0]["Elapsed"].as_u64().unwrap_or(0),
+        pool,
+        worker,
+        temperature,
+        fan_speed: None,
+        error: None,
+        last_updated: now,
     }
 }
 
@@ -160,3 +282,38 @@ fn error_stats(error: &str, timestamp: u64) -> RemoteMinerStats {
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgminer_host_port() {
+        assert_eq!(parse_cgminer_host_port("192.168.1.50:4028"), ("192.168.1.50".to_string(), 4028));
+        assert_eq!(parse_cgminer_host_port("192.168.1.50"), ("192.168.1.50".to_string(), CGMINER_DEFAULT_PORT));
+        assert_eq!(parse_cgminer_host_port("http://rig1:4028/"), ("rig1".to_string(), 4028));
+    }
+
+    #[test]
+    fn test_sanitize_cgminer_response_strips_nul() {
+        let raw = b"{\"STATUS\":[{\"STATUS\":\"S\"}]}\0";
+        let cleaned = sanitize_cgminer_response(raw);
+        assert!(!cleaned.contains('\0'));
+        assert!(serde_json::from_str::<serde_json::Value>(&cleaned).is_ok());
+    }
+
+    #[test]
+    fn test_sanitize_cgminer_response_strips_trailing_comma() {
+        let raw = b"{\"SUMMARY\":[{\"Accepted\":5,}]}";
+        let cleaned = sanitize_cgminer_response(raw);
+        assert!(serde_json::from_str::<serde_json::Value>(&cleaned).is_ok());
+    }
+
+    #[test]
+    fn test_sanitize_cgminer_response_leaves_comma_inside_string_value_alone() {
+        let raw = br#"{"SUMMARY":[{"Pool":"a,]b"}]}"#;
+        let cleaned = sanitize_cgminer_response(raw);
+        let value: serde_json::Value = serde_json::from_str(&cleaned).unwrap();
+        assert_eq!(value["SUMMARY"][0]["Pool"], "a,]b");
+    }
+}
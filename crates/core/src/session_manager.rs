@@ -6,26 +6,43 @@
 //! - Event emission for UI updates (throttled 1Hz stats, batched logs)
 //! - Crash recovery support
 
-use crate::{route_algorithm, CoreError, MinerType, Result};
+use crate::{
+    route_algorithm, session_persistence, AlertSeverity, AlertStore, ConnectionWatcher, CoreError,
+    HashrateTracker, MinerType, MiningMode, PersistedSession, PoolSocketState, Result,
+    SessionConnection, SessionPriority, StratumProxy, ThreadBudgetSettings,
+};
 use openminedash_miner_adapters::{
     CpuminerOptAdapter, MiningConfig as AdapterMiningConfig, PerformancePreset,
     XMRigAdapter,
 };
+use openminedash_pools::parse_pool_url;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use tauri::Manager;
 use tokio::process::Child;
-use tokio::sync::RwLock;
-use tracing::{error, info};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::Duration;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Event throttling constants
 const STATS_THROTTLE_MS: u64 = 1000; // 1Hz stats updates
 const LOG_BATCH_SIZE: usize = 20;    // Batch logs in chunks
 
+/// Sliding window for pool-confirmed effective hashrate
+const EFFECTIVE_HASHRATE_WINDOW_SECS: u64 = 600;
+
+/// How often `graceful_shutdown` polls a Stratum proxy's in-flight
+/// `mining.submit` count while waiting for it to drain.
+const SHUTDOWN_DRAIN_POLL_MS: u64 = 100;
+
+/// Deadline `stop_all` gives every session to shut down cleanly before
+/// force-killing whatever's left.
+const DEFAULT_SHUTDOWN_DEADLINE_SECS: u64 = 10;
+
 pub type SessionId = String;
 
 /// Session configuration (user-provided, non-secret)
@@ -44,6 +61,12 @@ pub struct SessionConfig {
     /// Stable identity hash for this config
     #[serde(default)]
     pub config_hash: String,
+    /// Priority class used by `BudgetMode::EnforceLimit` to weight this
+    /// session's share of the thread budget relative to others. Not part of
+    /// `compute_hash` - it doesn't change what's being mined, just how much
+    /// of the CPU it gets.
+    #[serde(default)]
+    pub priority: SessionPriority,
 }
 
 impl SessionConfig {
@@ -75,6 +98,14 @@ impl SessionConfig {
         hex::encode(&hasher.finalize()[..8]) // First 8 bytes = 16 hex chars
     }
     
+    /// Rewrite `threads_hint` and `preset` to whatever the given
+    /// `MiningMode` prescribes - see `MiningConfig::apply_mode` for the
+    /// legacy single-session equivalent.
+    pub fn apply_mode(&mut self, mode: MiningMode, budget: &ThreadBudgetSettings, active_sessions: u32) {
+        self.threads_hint = mode.resolve_threads(budget, active_sessions);
+        self.preset = mode.preset();
+    }
+
     /// Get pool host for display
     pub fn pool_host(&self) -> String {
         self.pool_url
@@ -171,6 +202,18 @@ pub struct SessionStats {
     /// Overcommit ratio (1.0 = at budget, >1.0 = over)
     #[serde(default)]
     pub overcommit_ratio: f32,
+    /// Pool-confirmed effective hashrate, derived from accepted shares and
+    /// their difficulty over a sliding window - `None` until the miner has a
+    /// known difficulty and at least one accepted share in that window.
+    #[serde(default)]
+    pub hashrate_effective: Option<f64>,
+    /// Short-window vs. long-window hashrate drop, as a percentage, from
+    /// `HashrateTracker` - `None` until enough samples have accumulated.
+    #[serde(default)]
+    pub hashrate_drop_pct: Option<f64>,
+    /// Rejected-share fraction over `HashrateTracker`'s default window.
+    #[serde(default)]
+    pub share_reject_rate: Option<f64>,
 }
 
 /// Summary for list_sessions
@@ -279,12 +322,55 @@ struct SessionRuntime {
     child: Option<Child>,
     xmrig_adapter: Option<XMRigAdapter>,
     cpuminer_adapter: Option<CpuminerOptAdapter>,
+    /// Local Stratum proxy inserted for cpuminer-opt sessions, giving them
+    /// XMRig-grade telemetry without relying on log parsing.
+    stratum_proxy: Option<StratumProxy>,
     logs: LogBuffer,
     start_time: u64,
     /// Last stats emit timestamp (for throttling)
     last_stats_emit: u64,
     /// Pending log lines (for batching)
     pending_logs: Vec<String>,
+    /// Sliding window of (timestamp_ms, accepted-share difficulty sum)
+    /// samples for pool-confirmed effective hashrate. Accepted shares are
+    /// only available as a cumulative counter, so each poll's delta is
+    /// attributed to the difficulty observed at that poll.
+    share_window: Vec<(u64, f64)>,
+    /// Accepted-share count as of the last poll, to compute this poll's delta.
+    last_accepted_seen: u64,
+    /// Rolling hashrate/share-accept window feeding sustained-drop detection.
+    hashrate_tracker: HashrateTracker,
+    /// Tracks this session's live pool socket across polls - see
+    /// `connection_monitor`.
+    connection_watcher: ConnectionWatcher,
+}
+
+impl SessionRuntime {
+    /// Record this poll's accepted-share delta into the sliding window and
+    /// return the recomputed effective hashrate, or `None` if the window
+    /// holds no samples (no known difficulty yet, or no shares accepted
+    /// within `EFFECTIVE_HASHRATE_WINDOW_SECS`).
+    fn update_effective_hashrate(&mut self, now_ms: u64, accepted: u64, difficulty: f64) -> Option<f64> {
+        // saturating_sub also guards against a counter reset or clock skew
+        // producing a negative delta.
+        let delta = accepted.saturating_sub(self.last_accepted_seen);
+        self.last_accepted_seen = accepted;
+
+        if delta > 0 && difficulty > 0.0 {
+            self.share_window.push((now_ms, delta as f64 * difficulty));
+        }
+
+        let window_start = now_ms.saturating_sub(EFFECTIVE_HASHRATE_WINDOW_SECS * 1000);
+        self.share_window.retain(|(ts, _)| *ts >= window_start);
+
+        if self.share_window.is_empty() {
+            return None;
+        }
+
+        let total_difficulty: f64 = self.share_window.iter().map(|(_, d)| d).sum();
+        // Each unit of difficulty represents ~2^32 hashes on average.
+        Some((total_difficulty * 4_294_967_296.0) / EFFECTIVE_HASHRATE_WINDOW_SECS as f64)
+    }
 }
 
 /// A mining session
@@ -309,10 +395,15 @@ impl MiningSession {
                 child: None,
                 xmrig_adapter: None,
                 cpuminer_adapter: None,
+                stratum_proxy: None,
                 logs: LogBuffer::new(),
                 start_time: 0,
                 last_stats_emit: 0,
                 pending_logs: Vec::new(),
+                share_window: Vec::new(),
+                last_accepted_seen: 0,
+                hashrate_tracker: HashrateTracker::new(),
+                connection_watcher: ConnectionWatcher::new(),
             },
         }
     }
@@ -334,10 +425,19 @@ impl MiningSession {
     }
 }
 
+/// Per-session lock, so one slow/unresponsive miner can't block operations
+/// on other sessions or on the outer session map.
+type SessionHandle = Arc<RwLock<MiningSession>>;
+
 /// Thread-safe session manager
 pub struct SessionManager {
-    sessions: Arc<RwLock<HashMap<SessionId, MiningSession>>>,
+    sessions: Arc<RwLock<HashMap<SessionId, SessionHandle>>>,
     app_handle: Option<tauri::AppHandle>,
+    /// Handle to the Tokio runtime driving session shutdowns concurrently.
+    /// Captured lazily on first use - `SessionManager::new()` runs before
+    /// Tauri brings its async runtime up, so there's no handle to grab yet
+    /// at construction time.
+    executor: OnceLock<tokio::runtime::Handle>,
 }
 
 impl SessionManager {
@@ -345,9 +445,18 @@ impl SessionManager {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             app_handle: None,
+            executor: OnceLock::new(),
         }
     }
 
+    /// Shared executor handle, captured on first call from within an async
+    /// context.
+    fn executor(&self) -> tokio::runtime::Handle {
+        self.executor
+            .get_or_init(tokio::runtime::Handle::current)
+            .clone()
+    }
+
     pub fn set_app_handle(&mut self, handle: tauri::AppHandle) {
         self.app_handle = Some(handle);
     }
@@ -383,15 +492,42 @@ impl SessionManager {
         session.stats.status = SessionStatus::Starting;
 
         // Create adapter config
-        let adapter_config = AdapterMiningConfig {
+        let mut adapter_config = AdapterMiningConfig {
             coin: session_config.algorithm.clone(),
             pool: session_config.pool_url.clone(),
             wallet: session_config.wallet.clone(),
             worker: session_config.worker.clone(),
             threads: session_config.threads_hint,
             preset: session_config.preset,
+            failover_pools: Vec::new(),
+            lower_priority: false,
+            credential: None,
         };
 
+        // cpuminer-opt has no HTTP API like XMRig, so route it through a
+        // local Stratum proxy for XMRig-grade share/difficulty telemetry
+        // instead of relying on log parsing. Only plain-TCP pools are
+        // supported today; TLS pools keep using log parsing.
+        if miner_kind == MinerKind::CpuminerOpt {
+            match parse_pool_url(&session_config.pool_url) {
+                Ok(endpoint) if !endpoint.tls => {
+                    match StratumProxy::start(endpoint.host, endpoint.port).await {
+                        Ok(proxy) => {
+                            adapter_config.pool = format!("127.0.0.1:{}", proxy.local_addr().port());
+                            session.runtime.stratum_proxy = Some(proxy);
+                        }
+                        Err(e) => {
+                            warn!("Failed to start Stratum proxy, falling back to log parsing: {}", e);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Could not parse pool URL for Stratum proxy: {}", e);
+                }
+            }
+        }
+
         // Start the miner
         let app_handle = self.app_handle.clone()
             .ok_or_else(|| CoreError::Miner("App handle not set".to_string()))?;
@@ -424,7 +560,7 @@ impl SessionManager {
         // Store session
         {
             let mut sessions = self.sessions.write().await;
-            sessions.insert(id.clone(), session);
+            sessions.insert(id.clone(), Arc::new(RwLock::new(session)));
         }
 
         // Emit event
@@ -433,18 +569,22 @@ impl SessionManager {
             "config": session_config,
         }));
 
+        self.persist_sessions().await;
+
         info!("Started session {} for {}", id, session_config.symbol);
         Ok(id)
     }
 
     /// Stop a session
     pub async fn stop_session(&self, session_id: &str) -> Result<()> {
-        let mut sessions = self.sessions.write().await;
-        
-        let session = sessions.get_mut(session_id)
-            .ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        }.ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
 
-        if session.stats.status != SessionStatus::Running && 
+        let mut session = handle.write().await;
+
+        if session.stats.status != SessionStatus::Running &&
            session.stats.status != SessionStatus::Suspended {
             return Ok(());
         }
@@ -470,13 +610,16 @@ impl SessionManager {
         session.stats.connected = false;
 
         let symbol = session.config.symbol.clone();
-        
+        drop(session);
+
         // Emit event
         self.emit_event("session://stopped", serde_json::json!({
             "session_id": session_id,
             "symbol": symbol,
         }));
 
+        self.persist_sessions().await;
+
         info!("Stopped session {} ({})", session_id, symbol);
         Ok(())
     }
@@ -487,9 +630,12 @@ impl SessionManager {
         use nix::sys::signal::{kill, Signal};
         use nix::unistd::Pid;
 
-        let mut sessions = self.sessions.write().await;
-        let session = sessions.get_mut(session_id)
-            .ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        }.ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
+
+        let mut session = handle.write().await;
 
         if session.stats.status != SessionStatus::Running {
             return Err(CoreError::InvalidState);
@@ -500,12 +646,15 @@ impl SessionManager {
                 kill(Pid::from_raw(pid as i32), Signal::SIGSTOP)
                     .map_err(|e| CoreError::Miner(format!("Failed to suspend: {}", e)))?;
                 session.stats.status = SessionStatus::Suspended;
-                
+                drop(session);
+
                 self.emit_event("session://updated", serde_json::json!({
                     "session_id": session_id,
                     "status": "suspended",
                 }));
-                
+
+                self.persist_sessions().await;
+
                 info!("Suspended session {}", session_id);
             }
         }
@@ -519,9 +668,12 @@ impl SessionManager {
         use nix::sys::signal::{kill, Signal};
         use nix::unistd::Pid;
 
-        let mut sessions = self.sessions.write().await;
-        let session = sessions.get_mut(session_id)
-            .ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        }.ok_or_else(|| CoreError::Miner(format!("Session not found: {}", session_id)))?;
+
+        let mut session = handle.write().await;
 
         if session.stats.status != SessionStatus::Suspended {
             return Err(CoreError::InvalidState);
@@ -532,12 +684,15 @@ impl SessionManager {
                 kill(Pid::from_raw(pid as i32), Signal::SIGCONT)
                     .map_err(|e| CoreError::Miner(format!("Failed to resume: {}", e)))?;
                 session.stats.status = SessionStatus::Running;
-                
+                drop(session);
+
                 self.emit_event("session://updated", serde_json::json!({
                     "session_id": session_id,
                     "status": "running",
                 }));
-                
+
+                self.persist_sessions().await;
+
                 info!("Resumed session {}", session_id);
             }
         }
@@ -547,14 +702,25 @@ impl SessionManager {
 
     /// List all sessions
     pub async fn list_sessions(&self) -> Vec<SessionSummary> {
-        let sessions = self.sessions.read().await;
-        sessions.values().map(|s| s.to_summary()).collect()
+        let handles: Vec<SessionHandle> = {
+            let sessions = self.sessions.read().await;
+            sessions.values().cloned().collect()
+        };
+
+        let mut summaries = Vec::with_capacity(handles.len());
+        for handle in handles {
+            summaries.push(handle.read().await.to_summary());
+        }
+        summaries
     }
 
     /// Get session details
     pub async fn get_session(&self, session_id: &str) -> Option<SessionDetails> {
-        let sessions = self.sessions.read().await;
-        sessions.get(session_id).map(|s| s.to_details())
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        }?;
+        Some(handle.read().await.to_details())
     }
 
     /// Get session logs
@@ -564,105 +730,299 @@ impl SessionManager {
         cursor: Option<u64>,
         limit: Option<usize>,
     ) -> Option<LogsResponse> {
-        let sessions = self.sessions.read().await;
-        sessions.get(session_id).map(|s| {
-            let mut response = s.runtime.logs.get_logs(cursor, limit.unwrap_or(100));
-            response.session_id = session_id.to_string();
-            response
-        })
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        }?;
+        let session = handle.read().await;
+        let mut response = session.runtime.logs.get_logs(cursor, limit.unwrap_or(100));
+        response.session_id = session_id.to_string();
+        Some(response)
     }
 
-    /// Stop all sessions
+    /// Stop all sessions, concurrently and with a bounded deadline.
     pub async fn stop_all(&self) -> Result<()> {
-        let session_ids: Vec<String> = {
+        self.graceful_shutdown(Duration::from_secs(DEFAULT_SHUTDOWN_DEADLINE_SECS)).await
+    }
+
+    /// Stop every session concurrently, waiting up to `deadline` for each to
+    /// shut down cleanly before force-killing whatever is left.
+    ///
+    /// Every session's shutdown runs concurrently on the shared executor
+    /// (`stop_all` delegates here with a default deadline), so the
+    /// total time is bounded by the slowest session plus `deadline`, not the
+    /// sum of all of them. Stratum-proxied sessions get a chance to let any
+    /// in-flight `mining.submit` land before their miner is torn down.
+    /// `session://all_stopped` is only emitted once every child has
+    /// actually exited (gracefully or via the deadline's SIGKILL fallback).
+    pub async fn graceful_shutdown(&self, deadline: Duration) -> Result<()> {
+        let handles: Vec<(SessionId, SessionHandle)> = {
             let sessions = self.sessions.read().await;
-            sessions.keys().cloned().collect()
+            sessions.iter().map(|(id, handle)| (id.clone(), handle.clone())).collect()
         };
 
-        for id in session_ids {
-            if let Err(e) = self.stop_session(&id).await {
-                error!("Failed to stop session {}: {}", id, e);
+        let drain_until = Instant::now() + deadline;
+        let executor = self.executor();
+        let tasks: Vec<_> = handles
+            .into_iter()
+            .map(|(session_id, handle)| executor.spawn(Self::shutdown_one(session_id, handle, drain_until)))
+            .collect();
+
+        // Never wait past the overall deadline - whatever hasn't exited by
+        // then gets force-killed below, independent of what its shutdown
+        // task is still doing.
+        let _ = tokio::time::timeout(deadline, futures::future::join_all(tasks)).await;
+
+        {
+            let sessions = self.sessions.read().await;
+            for handle in sessions.values() {
+                if let Ok(mut session) = handle.try_write() {
+                    if let Some(mut child) = session.runtime.child.take() {
+                        warn!("Session did not stop within deadline, forcing SIGKILL");
+                        let _ = child.start_kill();
+                        let _ = child.wait().await;
+                    }
+                    if session.stats.status != SessionStatus::Stopped {
+                        session.stats.status = SessionStatus::Stopped;
+                        session.stats.connected = false;
+                    }
+                }
             }
         }
 
         self.emit_event("session://all_stopped", serde_json::json!({}));
-        info!("Stopped all sessions");
+        info!("Graceful shutdown complete");
         Ok(())
     }
 
-    /// Refresh stats for all running sessions (throttled 1Hz per session)
-    pub async fn refresh_all_stats(&self) {
-        let mut sessions = self.sessions.write().await;
+    /// Shut down a single session: mark it `Stopping`, let its Stratum proxy
+    /// (if any) drain in-flight submits, then run the normal adapter
+    /// stop/child-termination sequence. Runs as its own task so
+    /// `graceful_shutdown` can shut down many sessions concurrently.
+    async fn shutdown_one(session_id: SessionId, handle: SessionHandle, drain_until: Instant) {
+        let mut session = handle.write().await;
+        if session.stats.status == SessionStatus::Stopped {
+            return;
+        }
+        session.stats.status = SessionStatus::Stopping;
+
+        if let Some(proxy) = session.runtime.stratum_proxy.clone() {
+            // Drop the lock while draining so other operations (and the
+            // deadline's force-kill fallback) aren't blocked on this wait.
+            drop(session);
+            while Instant::now() < drain_until {
+                if proxy.get_stats().await.pending_submits == 0 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(SHUTDOWN_DRAIN_POLL_MS)).await;
+            }
+            session = handle.write().await;
+        }
+
+        if let Some(mut child) = session.runtime.child.take() {
+            match session.config.miner_kind {
+                MinerKind::XMRig => {
+                    if let Some(adapter) = &mut session.runtime.xmrig_adapter {
+                        adapter.stop(&mut child).await;
+                    }
+                }
+                MinerKind::CpuminerOpt => {
+                    if let Some(adapter) = &mut session.runtime.cpuminer_adapter {
+                        adapter.stop(&mut child).await;
+                    }
+                }
+            }
+        }
+
+        session.stats.status = SessionStatus::Stopped;
+        session.stats.connected = false;
+        info!("Stopped session {} via graceful shutdown", session_id);
+    }
+
+    /// Refresh stats for all running sessions (throttled 1Hz per session).
+    ///
+    /// Each session is locked independently: the outer map is only
+    /// read-locked long enough to clone the per-session handles, adapter
+    /// polling happens with no lock held at all, and each session's own
+    /// lock is re-acquired only to write the results back. This way one
+    /// hung miner's HTTP API can't stall stats collection (or anything
+    /// else) for every other session.
+    pub async fn refresh_all_stats(&self, alerts: &Arc<Mutex<AlertStore>>) {
         let now_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
-        
+
+        let handles: Vec<SessionHandle> = {
+            let sessions = self.sessions.read().await;
+            sessions.values().cloned().collect()
+        };
+
         let mut updated_sessions: Vec<SessionSummary> = Vec::new();
-        
-        for session in sessions.values_mut() {
-            if session.stats.status != SessionStatus::Running {
-                continue;
-            }
 
-            // Throttle: skip if last emit was < 1s ago
-            if now_ms.saturating_sub(session.runtime.last_stats_emit) < STATS_THROTTLE_MS {
+        for handle in handles {
+            let snapshot = {
+                let session = handle.read().await;
+                if session.stats.status != SessionStatus::Running {
+                    None
+                } else if now_ms.saturating_sub(session.runtime.last_stats_emit) < STATS_THROTTLE_MS {
+                    // Throttle: skip if last emit was < 1s ago
+                    None
+                } else {
+                    Some((
+                        session.config.miner_kind,
+                        session.runtime.start_time,
+                        session.runtime.xmrig_adapter.clone(),
+                        session.runtime.cpuminer_adapter.as_ref().map(|a| a.get_stats()),
+                        session.runtime.stratum_proxy.clone(),
+                        session.runtime.child.as_ref().and_then(|c| c.id()),
+                        session.config.pool_url.clone(),
+                        session.config.symbol.clone(),
+                    ))
+                }
+            };
+
+            let Some((
+                miner_kind,
+                start_time,
+                xmrig_adapter,
+                cpuminer_stats,
+                stratum_proxy,
+                pid,
+                pool_url,
+                coin_symbol,
+            )) = snapshot else {
                 continue;
-            }
+            };
+
+            // Poll outside any lock, so a slow/unresponsive miner HTTP API
+            // only delays this session's own update.
+            let xmrig_stats = match &xmrig_adapter {
+                Some(adapter) => adapter.get_stats().await.ok(),
+                None => None,
+            };
+            let proxy_stats = match &stratum_proxy {
+                Some(proxy) => Some(proxy.get_stats().await),
+                None => None,
+            };
+            let pool_endpoint = pid.and_then(|_| parse_pool_url(&pool_url).ok());
+
+            // Re-acquire the per-session lock only to write results back.
+            let mut session = handle.write().await;
 
-            // Update uptime
             let now_secs = now_ms / 1000;
-            session.stats.uptime_secs = now_secs.saturating_sub(session.runtime.start_time);
+            session.stats.uptime_secs = now_secs.saturating_sub(start_time);
 
-            // Get stats from adapter
-            match session.config.miner_kind {
+            let mut raw_hashrate: Option<f64> = None;
+
+            match miner_kind {
                 MinerKind::XMRig => {
-                    if let Some(adapter) = &session.runtime.xmrig_adapter {
-                        if let Ok(stats) = adapter.get_stats().await {
-                            session.stats.hashrate_current = stats.current_hashrate();
-                            session.stats.hashrate_avg60 = stats.avg_hashrate();
-                            session.stats.accepted = stats.accepted_shares();
-                            session.stats.rejected = stats.rejected_shares();
-                            session.stats.stats_confidence = 1.0;
-                            session.stats.telemetry_confidence = TelemetryConfidence::High;
-                            session.stats.telemetry_reason = "XMRig HTTP API".to_string();
-                            session.stats.connection_state = ConnectionState::Authorized;
-                        }
+                    if let Some(stats) = xmrig_stats {
+                        // Raw sample (may be `None` while XMRig is still
+                        // warming up), kept separate from `current_hashrate()`'s
+                        // 0.0 fallback so the tracker doesn't mistake "no
+                        // reading yet" for "hashrate dropped to zero".
+                        raw_hashrate = stats.hashrate.total.first().copied().flatten();
+                        session.stats.hashrate_current = stats.current_hashrate();
+                        session.stats.hashrate_avg60 = stats.avg_hashrate();
+                        session.stats.accepted = stats.accepted_shares();
+                        session.stats.rejected = stats.rejected_shares();
+                        session.stats.stats_confidence = 1.0;
+                        session.stats.telemetry_confidence = TelemetryConfidence::High;
+                        session.stats.telemetry_reason = "XMRig HTTP API".to_string();
+                        session.stats.connection_state = ConnectionState::Authorized;
                     }
                 }
                 MinerKind::CpuminerOpt => {
-                    if let Some(adapter) = &session.runtime.cpuminer_adapter {
-                        let stats = adapter.get_stats();
+                    // Hashrate isn't part of the Stratum protocol, so it
+                    // always comes from log parsing regardless of proxy use.
+                    if let Some(stats) = &cpuminer_stats {
+                        raw_hashrate = (stats.hashrate > 0.0).then_some(stats.hashrate);
                         session.stats.hashrate_current = stats.hashrate;
                         session.stats.hashrate_avg60 = stats.avg_hashrate;
-                        session.stats.accepted = stats.accepted;
-                        session.stats.rejected = stats.rejected;
-                        
-                        // Set confidence based on parsed data
-                        if stats.hashrate > 0.0 {
-                            session.stats.stats_confidence = 0.7;
-                            session.stats.telemetry_confidence = TelemetryConfidence::Medium;
-                            session.stats.telemetry_reason = "Log parsing".to_string();
-                        } else {
-                            session.stats.stats_confidence = 0.0;
-                            session.stats.telemetry_confidence = TelemetryConfidence::Low;
-                            session.stats.telemetry_reason = "No telemetry from miner output".to_string();
-                        }
-                        
-                        // Connection state from shares
-                        if stats.accepted > 0 {
-                            session.stats.connection_state = ConnectionState::Authorized;
-                        } else {
-                            session.stats.connection_state = ConnectionState::Connecting;
+
+                        if proxy_stats.is_none() {
+                            session.stats.accepted = stats.accepted;
+                            session.stats.rejected = stats.rejected;
+
+                            // Set confidence based on parsed data
+                            if stats.hashrate > 0.0 {
+                                session.stats.stats_confidence = 0.7;
+                                session.stats.telemetry_confidence = TelemetryConfidence::Medium;
+                                session.stats.telemetry_reason = "Log parsing".to_string();
+                            } else {
+                                session.stats.stats_confidence = 0.0;
+                                session.stats.telemetry_confidence = TelemetryConfidence::Low;
+                                session.stats.telemetry_reason = "No telemetry from miner output".to_string();
+                            }
+
+                            // Connection state from shares
+                            if stats.accepted > 0 {
+                                session.stats.connection_state = ConnectionState::Authorized;
+                            } else {
+                                session.stats.connection_state = ConnectionState::Connecting;
+                            }
                         }
                     }
+
+                    if let Some(proxy_stats) = proxy_stats {
+                        session.stats.difficulty = proxy_stats.difficulty;
+                        session.stats.accepted = proxy_stats.accepted;
+                        session.stats.rejected = proxy_stats.rejected;
+                        session.stats.connection_state = proxy_stats.connection_state;
+                        session.stats.stats_confidence = 1.0;
+                        session.stats.telemetry_confidence = TelemetryConfidence::High;
+                        session.stats.telemetry_reason = "Stratum proxy".to_string();
+                    }
+                }
+            }
+
+            // Pool-confirmed effective hashrate, derived from accepted
+            // shares and whatever difficulty was observed this poll.
+            let accepted = session.stats.accepted;
+            let difficulty = session.stats.difficulty;
+            session.stats.hashrate_effective =
+                session.runtime.update_effective_hashrate(now_ms, accepted, difficulty);
+
+            // Rolling-window drop/reject detection, fed the same raw sample
+            // and cumulative counters as the effective-hashrate calculation
+            // above.
+            let rejected = session.stats.rejected;
+            session.runtime.hashrate_tracker.record(raw_hashrate, accepted, rejected);
+            session.stats.hashrate_drop_pct = session.runtime.hashrate_tracker.hashrate_drop_pct();
+            session.stats.share_reject_rate = session
+                .runtime
+                .hashrate_tracker
+                .share_reject_rate(Duration::from_secs(crate::DEFAULT_REJECT_RATE_WINDOW_SECS));
+
+            // Live socket check: is this session's miner process actually
+            // connected to its pool, independent of whatever the miner's
+            // own API/logs report?
+            if let (Some(pid), Some(endpoint)) = (pid, pool_endpoint) {
+                let session_id = session.id.clone();
+                let (connection, changed) = session.runtime.connection_watcher.poll(
+                    &session_id,
+                    pid,
+                    &endpoint.host,
+                    endpoint.port,
+                );
+                if changed && connection.state != PoolSocketState::Established {
+                    alerts.lock().await.record(
+                        "pool_connection_dropped",
+                        Some(&session_id),
+                        Some(&coin_symbol),
+                        &format!("Lost the pool connection for {} ({:?})", coin_symbol, connection.state),
+                        AlertSeverity::Warning,
+                        true,
+                        None,
+                    );
                 }
             }
-            
+
             session.runtime.last_stats_emit = now_ms;
             updated_sessions.push(session.to_summary());
         }
-        
+
         // Emit batch update if any sessions updated
         if !updated_sessions.is_empty() {
             if let Some(handle) = &self.app_handle {
@@ -673,57 +1033,243 @@ impl SessionManager {
         }
     }
 
+    /// On-demand live socket check for every running session - the same
+    /// OS-level inspection `refresh_all_stats` does, exposed directly so
+    /// the UI can ask "am I actually connected to my pool?" without
+    /// waiting for the next stats tick.
+    pub async fn session_connections(&self) -> Vec<SessionConnection> {
+        let handles: Vec<(SessionId, SessionHandle)> = {
+            let sessions = self.sessions.read().await;
+            sessions.iter().map(|(id, h)| (id.clone(), h.clone())).collect()
+        };
+
+        let mut connections = Vec::new();
+        for (id, handle) in handles {
+            let snapshot = {
+                let session = handle.read().await;
+                if session.stats.status != SessionStatus::Running {
+                    None
+                } else {
+                    let pid = session.runtime.child.as_ref().and_then(|c| c.id());
+                    let endpoint = parse_pool_url(&session.config.pool_url).ok();
+                    pid.zip(endpoint)
+                }
+            };
+            let Some((pid, endpoint)) = snapshot else {
+                continue;
+            };
+
+            let mut session = handle.write().await;
+            let (connection, _changed) = session.runtime.connection_watcher.poll(
+                &id,
+                pid,
+                &endpoint.host,
+                endpoint.port,
+            );
+            connections.push(connection);
+        }
+        connections
+    }
+
     /// Add log line to session (batched emission)
     pub async fn add_log(&self, session_id: &str, line: String) {
-        let mut sessions = self.sessions.write().await;
-        if let Some(session) = sessions.get_mut(session_id) {
-            session.runtime.logs.push(line.clone());
-            session.runtime.pending_logs.push(line);
-            
-            // Batch emit when threshold reached
-            if session.runtime.pending_logs.len() >= LOG_BATCH_SIZE {
-                let batch = std::mem::take(&mut session.runtime.pending_logs);
-                if let Some(handle) = &self.app_handle {
-                    let _ = handle.emit_all("session://log_batch", serde_json::json!({
-                        "session_id": session_id,
-                        "lines": batch,
-                    }));
-                }
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        };
+        let Some(handle) = handle else {
+            return;
+        };
+
+        let mut session = handle.write().await;
+        session.runtime.logs.push(line.clone());
+        session.runtime.pending_logs.push(line);
+
+        // Batch emit when threshold reached
+        if session.runtime.pending_logs.len() >= LOG_BATCH_SIZE {
+            let batch = std::mem::take(&mut session.runtime.pending_logs);
+            if let Some(handle) = &self.app_handle {
+                let _ = handle.emit_all("session://log_batch", serde_json::json!({
+                    "session_id": session_id,
+                    "lines": batch,
+                }));
             }
         }
     }
 
     /// Flush pending logs for a session
     pub async fn flush_logs(&self, session_id: &str) {
-        let mut sessions = self.sessions.write().await;
-        if let Some(session) = sessions.get_mut(session_id) {
-            if !session.runtime.pending_logs.is_empty() {
-                let batch = std::mem::take(&mut session.runtime.pending_logs);
-                if let Some(handle) = &self.app_handle {
-                    let _ = handle.emit_all("session://log_batch", serde_json::json!({
-                        "session_id": session_id,
-                        "lines": batch,
-                    }));
-                }
+        let handle = {
+            let sessions = self.sessions.read().await;
+            sessions.get(session_id).cloned()
+        };
+        let Some(handle) = handle else {
+            return;
+        };
+
+        let mut session = handle.write().await;
+        if !session.runtime.pending_logs.is_empty() {
+            let batch = std::mem::take(&mut session.runtime.pending_logs);
+            if let Some(handle) = &self.app_handle {
+                let _ = handle.emit_all("session://log_batch", serde_json::json!({
+                    "session_id": session_id,
+                    "lines": batch,
+                }));
             }
         }
     }
 
     /// Get active session count
     pub async fn active_count(&self) -> usize {
-        let sessions = self.sessions.read().await;
-        sessions.values()
-            .filter(|s| s.stats.status == SessionStatus::Running || s.stats.status == SessionStatus::Suspended)
-            .count()
+        let handles: Vec<SessionHandle> = {
+            let sessions = self.sessions.read().await;
+            sessions.values().cloned().collect()
+        };
+
+        let mut count = 0;
+        for handle in handles {
+            let session = handle.read().await;
+            if session.stats.status == SessionStatus::Running || session.stats.status == SessionStatus::Suspended {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Rebalance running sessions' thread counts against `budget`, applying
+    /// `enforce_budget`'s largest-remainder split when `BudgetMode` is
+    /// `EnforceLimit` and the total requested exceeds the budget cap.
+    ///
+    /// No live thread-reconfigure API exists for either miner adapter (see
+    /// `set_mining_mode`), so applying a new allocation means restarting the
+    /// affected sessions - each restart mints a fresh `SessionId`. Returns
+    /// the new IDs of whatever sessions were restarted, in no particular
+    /// order.
+    pub async fn enforce_thread_budget(&self, budget: &ThreadBudgetSettings) -> Vec<SessionId> {
+        if budget.mode != crate::BudgetMode::EnforceLimit {
+            return Vec::new();
+        }
+
+        let running: Vec<SessionSummary> = self
+            .list_sessions()
+            .await
+            .into_iter()
+            .filter(|s| s.stats.status == SessionStatus::Running)
+            .collect();
+        if running.is_empty() {
+            return Vec::new();
+        }
+
+        let total_requested: u32 = running.iter().map(|s| s.config.threads_hint.max(1)).sum();
+        let status = crate::calculate_budget(budget, running.len() as u32, total_requested);
+        if !status.is_overcommitted {
+            return Vec::new();
+        }
+
+        let requested: std::collections::BTreeMap<SessionId, (u32, crate::SessionPriority)> =
+            running
+                .iter()
+                .map(|s| (s.id.clone(), (s.config.threads_hint.max(1), s.config.priority)))
+                .collect();
+        let allocations = crate::enforce_budget(&requested, status.budget_threads);
+
+        let mut restarted = Vec::new();
+        for summary in running {
+            let new_threads = *allocations.get(&summary.id).unwrap_or(&summary.config.threads_hint);
+            if new_threads == summary.config.threads_hint {
+                continue;
+            }
+            let mut config = summary.config;
+            config.threads_hint = new_threads;
+            if self.stop_session(&summary.id).await.is_ok() {
+                if let Ok(new_id) = self.start_session(config).await {
+                    restarted.push(new_id);
+                }
+            }
+        }
+        restarted
     }
 
     /// Export sessions for crash recovery (non-secret data only)
     pub async fn export_for_recovery(&self) -> Vec<SessionConfig> {
-        let sessions = self.sessions.read().await;
-        sessions.values()
-            .filter(|s| s.stats.status == SessionStatus::Running)
-            .map(|s| s.config.clone())
-            .collect()
+        let handles: Vec<SessionHandle> = {
+            let sessions = self.sessions.read().await;
+            sessions.values().cloned().collect()
+        };
+
+        let mut configs = Vec::new();
+        for handle in handles {
+            let session = handle.read().await;
+            if session.stats.status == SessionStatus::Running {
+                configs.push(session.config.clone());
+            }
+        }
+        configs
+    }
+
+    /// Snapshot every known session (regardless of status), for durable
+    /// persistence across a crash/reboot.
+    async fn snapshot_for_persistence(&self) -> Vec<PersistedSession> {
+        let handles: Vec<SessionHandle> = {
+            let sessions = self.sessions.read().await;
+            sessions.values().cloned().collect()
+        };
+
+        let mut snapshot = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let session = handle.read().await;
+            snapshot.push(PersistedSession {
+                session_id: session.id.clone(),
+                config: session.config.clone(),
+                config_hash: session.config.config_hash.clone(),
+                status: session.stats.status,
+            });
+        }
+        snapshot
+    }
+
+    /// Write the current session set to disk, so mining can auto-resume
+    /// after a crash or reboot. Called after every lifecycle change
+    /// (start/stop/suspend/resume) rather than on a timer.
+    async fn persist_sessions(&self) {
+        let snapshot = self.snapshot_for_persistence().await;
+        if let Err(e) = session_persistence::save_sessions(&snapshot) {
+            warn!("Failed to persist session snapshot: {}", e);
+        }
+    }
+
+    /// Re-start every session that was `Running`/`Suspended` when it was
+    /// last persisted, skipping anything whose config hash is already
+    /// active (e.g. the user already restarted it manually). Best-effort:
+    /// a failed restore is logged and skipped rather than aborting the
+    /// rest. Intended to run once, early during app startup.
+    pub async fn restore_sessions(&self) -> Vec<SessionId> {
+        let persisted = session_persistence::load_sessions();
+        if persisted.is_empty() {
+            return Vec::new();
+        }
+
+        let already_running_hashes: Vec<String> = self
+            .snapshot_for_persistence()
+            .await
+            .into_iter()
+            .filter(|s| s.status != SessionStatus::Stopped)
+            .map(|s| s.config_hash)
+            .collect();
+
+        let to_restore = session_persistence::sessions_to_restore(persisted, &already_running_hashes);
+        let mut restored = Vec::with_capacity(to_restore.len());
+        for config in to_restore {
+            let symbol = config.symbol.clone();
+            match self.start_session(config).await {
+                Ok(id) => {
+                    info!("Auto-restored session {} for {}", id, symbol);
+                    restored.push(id);
+                }
+                Err(e) => warn!("Failed to auto-restore session for {}: {}", symbol, e),
+            }
+        }
+        restored
     }
 }
 
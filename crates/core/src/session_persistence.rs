@@ -0,0 +1,204 @@
+//! Durable session persistence, for auto-restoring mining across a crash or
+//! reboot.
+//!
+//! This is deliberately separate from `crash_recovery`'s lock file, which
+//! only detects an unclean shutdown and leaves resuming to the user. Here,
+//! every known session is snapshotted to disk with its status on each
+//! lifecycle change (start/stop/suspend/resume) via an atomic temp-file +
+//! rename, so a crash mid-write can't corrupt it. On the next launch,
+//! `SessionManager::restore_sessions` reads the file back and re-starts whatever was still `Running` or
+//! `Suspended`. Contents are exactly what `SessionConfig` already carries -
+//! wallet/pool, no credentials.
+
+use crate::{SessionConfig, SessionId, SessionStatus};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+const PERSISTENCE_FILE_NAME: &str = "sessions.json";
+
+/// One session's durable snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedSession {
+    pub session_id: SessionId,
+    pub config: SessionConfig,
+    pub config_hash: String,
+    pub status: SessionStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedSessions {
+    version: u32,
+    sessions: Vec<PersistedSession>,
+}
+
+fn persistence_file_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join(PERSISTENCE_FILE_NAME)
+}
+
+fn temp_persistence_file_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join(format!("{}.tmp", PERSISTENCE_FILE_NAME))
+}
+
+/// Atomic write: temp file -> fsync -> rename
+fn atomic_write(path: &PathBuf, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let temp_path = temp_persistence_file_path();
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if let Some(parent) = temp_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?; // fsync
+    }
+
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Persist the current set of sessions, overwriting whatever was there
+/// before. An empty slice removes the file entirely.
+pub fn save_sessions(sessions: &[PersistedSession]) -> std::io::Result<()> {
+    let path = persistence_file_path();
+
+    if sessions.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+
+    let content = PersistedSessions {
+        version: 1,
+        sessions: sessions.to_vec(),
+    };
+    let json = serde_json::to_string(&content)?;
+    atomic_write(&path, &json)
+}
+
+/// Load whatever was last persisted. Returns an empty vec (and logs a
+/// warning) if the file is missing, unreadable, or corrupted - restore is
+/// best-effort, not a hard failure.
+pub fn load_sessions() -> Vec<PersistedSession> {
+    let path = persistence_file_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str::<PersistedSessions>(&content) {
+            Ok(parsed) => parsed.sessions,
+            Err(e) => {
+                warn!("Corrupted session persistence file, ignoring: {}", e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            warn!("Failed to read session persistence file: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Sessions worth auto-restoring: last known to be `Running` or
+/// `Suspended`, and not already covered by `already_running_hashes` (e.g. a
+/// session the user already restarted manually before this ran).
+pub fn sessions_to_restore(
+    persisted: Vec<PersistedSession>,
+    already_running_hashes: &[String],
+) -> Vec<SessionConfig> {
+    persisted
+        .into_iter()
+        .filter(|s| matches!(s.status, SessionStatus::Running | SessionStatus::Suspended))
+        .filter(|s| !already_running_hashes.contains(&s.config_hash))
+        .map(|s| s.config)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openminedash_miner_adapters::PerformancePreset;
+    use std::sync::Mutex;
+
+    // Ensure persistence tests don't race each other over the shared file.
+    static TEST_MUTEX: Mutex<()> = Mutex::new(());
+
+    fn config(hash: &str) -> SessionConfig {
+        SessionConfig {
+            coin_id: "xmr".to_string(),
+            symbol: "XMR".to_string(),
+            algorithm: "randomx".to_string(),
+            miner_kind: crate::MinerKind::XMRig,
+            pool_url: "pool.example:3333".to_string(),
+            wallet: "wallet123".to_string(),
+            worker: "worker1".to_string(),
+            preset: PerformancePreset::Balanced,
+            threads_hint: 4,
+            created_at: 0,
+            config_hash: hash.to_string(),
+            priority: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+
+        let sessions = vec![PersistedSession {
+            session_id: "s1".to_string(),
+            config: config("hash1"),
+            config_hash: "hash1".to_string(),
+            status: SessionStatus::Running,
+        }];
+        save_sessions(&sessions).unwrap();
+
+        let loaded = load_sessions();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].config_hash, "hash1");
+
+        save_sessions(&[]).unwrap();
+        assert!(load_sessions().is_empty());
+    }
+
+    #[test]
+    fn test_sessions_to_restore_filters_status_and_dedupes() {
+        let persisted = vec![
+            PersistedSession {
+                session_id: "s1".to_string(),
+                config: config("hash1"),
+                config_hash: "hash1".to_string(),
+                status: SessionStatus::Running,
+            },
+            PersistedSession {
+                session_id: "s2".to_string(),
+                config: config("hash2"),
+                config_hash: "hash2".to_string(),
+                status: SessionStatus::Stopped,
+            },
+            PersistedSession {
+                session_id: "s3".to_string(),
+                config: config("hash3"),
+                config_hash: "hash3".to_string(),
+                status: SessionStatus::Suspended,
+            },
+        ];
+
+        let to_restore = sessions_to_restore(persisted, &["hash3".to_string()]);
+        assert_eq!(to_restore.len(), 1);
+        assert_eq!(to_restore[0].config_hash, "hash1");
+    }
+}
@@ -0,0 +1,276 @@
+//! Session scrub: a single long-lived loop that periodically walks every
+//! active session, probes its pool's health, and checks for sustained
+//! hashrate drift - complementing `SessionManager::refresh_all_stats`'s
+//! cheap per-tick TCP connection check with a deeper (and much less
+//! frequent) stratum health probe.
+//!
+//! Only one scrub loop is ever spawned; it's driven entirely by an
+//! `mpsc` command channel (`Start`/`Pause`/`Cancel`/`SetTranquility`) so
+//! every caller (Tauri commands, the tray) shares the same instance
+//! instead of each starting its own.
+
+use crate::{AlertSeverity, AlertStore, HashrateSparkline, SessionId, SessionManager};
+use openminedash_pools::PoolStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, Mutex};
+use tracing::info;
+
+/// Sessions with a sustained hashrate drop at or above this percentage get
+/// a scrub alert.
+const HASHRATE_DROP_ALERT_THRESHOLD_PCT: f64 = 20.0;
+
+/// Commands accepted by the single running scrub loop.
+#[derive(Debug, Clone, Copy)]
+pub enum ScrubCommand {
+    Start,
+    Pause,
+    Cancel,
+    SetTranquility(u8),
+}
+
+/// Persisted scrub knobs (lives in `AppConfig`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrubSettings {
+    /// Throttle: after a pass takes `t` ms, sleep for `t * tranquility`
+    /// before the next one. 0 = continuous, higher = gentler on the CPU.
+    pub tranquility: u8,
+    /// Unix timestamp (seconds) of the last completed scrub pass.
+    pub last_scrub_at: Option<u64>,
+}
+
+impl Default for ScrubSettings {
+    fn default() -> Self {
+        Self { tranquility: 3, last_scrub_at: None }
+    }
+}
+
+/// Live scrub state, reported by `get_scrub_status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScrubStatus {
+    pub paused: bool,
+    pub tranquility: u8,
+    pub last_scrub_at: Option<u64>,
+}
+
+struct ScrubInner {
+    paused: bool,
+    settings: ScrubSettings,
+}
+
+/// Shared handle to the scrub loop - cheap to clone, every clone sees the
+/// same underlying state and can command the same loop, same as
+/// `AutoMinerHandle`/`WorkerManager`.
+#[derive(Clone)]
+pub struct ScrubHandle {
+    inner: Arc<StdMutex<ScrubInner>>,
+    commands: mpsc::Sender<ScrubCommand>,
+}
+
+impl ScrubHandle {
+    /// Builds a handle plus the receiver half the spawned loop owns.
+    /// Callers should only ever do this once per process - that's what
+    /// keeps "only one instance ever runs" true.
+    pub fn new(settings: ScrubSettings) -> (Self, mpsc::Receiver<ScrubCommand>) {
+        let (tx, rx) = mpsc::channel(8);
+        let handle = Self {
+            inner: Arc::new(StdMutex::new(ScrubInner { paused: false, settings })),
+            commands: tx,
+        };
+        (handle, rx)
+    }
+
+    /// Send a command to the scrub loop. Silently dropped if the loop has
+    /// already exited (e.g. after `Cancel`) - matches the fire-and-forget
+    /// style `AutoMinerHandle::update_settings` uses for its own state.
+    pub fn send(&self, command: ScrubCommand) {
+        let _ = self.commands.try_send(command);
+    }
+
+    pub fn status(&self) -> ScrubStatus {
+        let inner = self.inner.lock().unwrap();
+        ScrubStatus {
+            paused: inner.paused,
+            tranquility: inner.settings.tranquility,
+            last_scrub_at: inner.settings.last_scrub_at,
+        }
+    }
+
+    pub fn settings(&self) -> ScrubSettings {
+        self.inner.lock().unwrap().settings.clone()
+    }
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// Spawn the single scrub loop. `commands` must be the receiver returned
+/// alongside `handle` from `ScrubHandle::new` - the loop exits for good on
+/// `ScrubCommand::Cancel` (or if every sender is dropped).
+pub fn spawn_session_scrub(
+    handle: ScrubHandle,
+    mut commands: mpsc::Receiver<ScrubCommand>,
+    sessions: Arc<Mutex<SessionManager>>,
+    alerts: Arc<Mutex<AlertStore>>,
+    sparkline: HashrateSparkline,
+) {
+    tokio::spawn(async move {
+        let mut paused = false;
+        let mut pool_health: HashMap<String, PoolStatus> = HashMap::new();
+        let mut was_dropping: HashMap<SessionId, bool> = HashMap::new();
+        let mut fleet_was_degraded = false;
+        let next_pass = tokio::time::sleep(Duration::from_secs(0));
+        tokio::pin!(next_pass);
+
+        loop {
+            tokio::select! {
+                cmd = commands.recv() => {
+                    match cmd {
+                        Some(ScrubCommand::Start) => {
+                            paused = false;
+                            handle.inner.lock().unwrap().paused = false;
+                            next_pass.as_mut().reset(tokio::time::Instant::now());
+                        }
+                        Some(ScrubCommand::Pause) => {
+                            paused = true;
+                            handle.inner.lock().unwrap().paused = true;
+                        }
+                        Some(ScrubCommand::SetTranquility(t)) => {
+                            handle.inner.lock().unwrap().settings.tranquility = t;
+                        }
+                        Some(ScrubCommand::Cancel) | None => {
+                            info!("Session scrub cancelled");
+                            return;
+                        }
+                    }
+                }
+                _ = &mut next_pass, if !paused => {
+                    let started = Instant::now();
+                    scrub_once(&sessions, &alerts, &mut pool_health, &mut was_dropping, &sparkline, &mut fleet_was_degraded).await;
+                    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+                    let tranquility = {
+                        let mut inner = handle.inner.lock().unwrap();
+                        inner.settings.last_scrub_at = Some(now_unix_secs());
+                        inner.settings.tranquility
+                    };
+
+                    let delay_ms = elapsed_ms.saturating_mul(tranquility as u64);
+                    next_pass.as_mut().reset(tokio::time::Instant::now() + Duration::from_millis(delay_ms));
+                }
+            }
+        }
+    });
+}
+
+/// One scrub pass: re-check pool health for every pool an active session
+/// uses, and flag sessions whose hashrate has sustained a significant drop.
+async fn scrub_once(
+    sessions: &Arc<Mutex<SessionManager>>,
+    alerts: &Arc<Mutex<AlertStore>>,
+    pool_health: &mut HashMap<String, PoolStatus>,
+    was_dropping: &mut HashMap<SessionId, bool>,
+    sparkline: &HashrateSparkline,
+    fleet_was_degraded: &mut bool,
+) {
+    let summaries = sessions.lock().await.list_sessions().await;
+
+    let fleet_hashrate: f64 = summaries
+        .iter()
+        .filter(|s| s.stats.status == crate::SessionStatus::Running)
+        .map(|s| s.stats.hashrate_current)
+        .sum();
+    sparkline.push(fleet_hashrate);
+    let fleet_degraded = sparkline.is_degraded();
+    if fleet_degraded && !*fleet_was_degraded {
+        alerts.lock().await.record(
+            "scrub_fleet_hashrate_drift",
+            None,
+            None,
+            "Fleet-wide hashrate has dropped sharply against its recent peak",
+            AlertSeverity::Warning,
+            true,
+            None,
+        );
+    }
+    *fleet_was_degraded = fleet_degraded;
+
+    let mut pools_to_check: Vec<String> = summaries
+        .iter()
+        .filter(|s| s.stats.status == crate::SessionStatus::Running)
+        .map(|s| s.config.pool_url.clone())
+        .collect();
+    pools_to_check.sort();
+    pools_to_check.dedup();
+
+    for pool_url in pools_to_check {
+        let Ok(result) = openminedash_pools::check_health(&pool_url).await else {
+            continue;
+        };
+        let previous = pool_health.insert(pool_url.clone(), result.status);
+        if previous != Some(result.status) && result.status != PoolStatus::Ok {
+            alerts.lock().await.record(
+                "scrub_pool_health_degraded",
+                None,
+                None,
+                &format!("Pool {} health is now {:?}", pool_url, result.status),
+                AlertSeverity::Warning,
+                true,
+                None,
+            );
+        }
+    }
+
+    for summary in &summaries {
+        if summary.stats.status != crate::SessionStatus::Running {
+            continue;
+        }
+        let dropping = summary.stats.hashrate_drop_pct.unwrap_or(0.0) >= HASHRATE_DROP_ALERT_THRESHOLD_PCT;
+        let previously_dropping = was_dropping.insert(summary.id.clone(), dropping).unwrap_or(false);
+        if dropping && !previously_dropping {
+            alerts.lock().await.record(
+                "scrub_hashrate_drift",
+                Some(&summary.id),
+                Some(&summary.config.symbol),
+                &format!(
+                    "{} hashrate has dropped {:.0}% against its recent baseline",
+                    summary.config.symbol,
+                    summary.stats.hashrate_drop_pct.unwrap_or(0.0),
+                ),
+                AlertSeverity::Warning,
+                true,
+                None,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_settings_default_is_gentle_not_continuous() {
+        let settings = ScrubSettings::default();
+        assert!(settings.tranquility > 0);
+        assert_eq!(settings.last_scrub_at, None);
+    }
+
+    #[test]
+    fn test_status_reflects_initial_settings() {
+        let (handle, _rx) = ScrubHandle::new(ScrubSettings { tranquility: 5, last_scrub_at: None });
+        let status = handle.status();
+        assert!(!status.paused);
+        assert_eq!(status.tranquility, 5);
+    }
+
+    #[test]
+    fn test_send_pause_queues_command_for_loop() {
+        let (handle, mut rx) = ScrubHandle::new(ScrubSettings::default());
+        handle.send(ScrubCommand::Pause);
+        assert!(matches!(rx.try_recv(), Ok(ScrubCommand::Pause)));
+    }
+}
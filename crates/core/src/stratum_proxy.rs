@@ -0,0 +1,226 @@
+//! Local Stratum proxy for high-confidence telemetry on miners without an
+//! HTTP API (e.g. cpuminer-opt).
+//!
+//! The proxy binds a localhost TCP listener and sits between the miner and
+//! the real pool: the adapter is pointed at the proxy instead of the pool,
+//! and every line is forwarded upstream unchanged while being parsed as
+//! line-delimited Stratum JSON-RPC. This gives cpuminer-opt the same
+//! share/difficulty fidelity XMRig gets from its HTTP API, independent of
+//! log scraping. Only plain-TCP pools are supported today; TLS pools fall
+//! back to the existing log-parsing path.
+
+use crate::{ConnectionState, CoreError, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Telemetry observed on the wire by the proxy, polled into `SessionStats`.
+#[derive(Debug, Clone, Default)]
+pub struct StratumProxyStats {
+    pub connection_state: ConnectionState,
+    pub difficulty: f64,
+    pub accepted: u64,
+    pub rejected: u64,
+    /// Number of `mining.submit` requests sent upstream that haven't had a
+    /// matching response observed yet. Used by graceful shutdown to let
+    /// in-flight shares land before the miner is torn down.
+    pub pending_submits: u64,
+}
+
+/// A running Stratum proxy for a single mining session. Cheap to clone -
+/// all clones share the same listener task and stats.
+#[derive(Clone)]
+pub struct StratumProxy {
+    local_addr: SocketAddr,
+    stats: Arc<Mutex<StratumProxyStats>>,
+}
+
+impl StratumProxy {
+    /// Bind a localhost listener and start forwarding connections to
+    /// `upstream_host:upstream_port`.
+    pub async fn start(upstream_host: String, upstream_port: u16) -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|e| CoreError::Miner(format!("Failed to bind Stratum proxy: {}", e)))?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| CoreError::Miner(format!("Failed to read Stratum proxy address: {}", e)))?;
+
+        let stats = Arc::new(Mutex::new(StratumProxyStats::default()));
+        let accept_stats = stats.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((miner_stream, _)) => {
+                        let upstream_host = upstream_host.clone();
+                        let stats = accept_stats.clone();
+                        tokio::spawn(async move {
+                            match TcpStream::connect((upstream_host.as_str(), upstream_port)).await {
+                                Ok(pool_stream) => forward(miner_stream, pool_stream, stats).await,
+                                Err(e) => warn!(
+                                    "Stratum proxy failed to reach upstream pool {}:{}: {}",
+                                    upstream_host, upstream_port, e
+                                ),
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        warn!("Stratum proxy accept failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self { local_addr, stats })
+    }
+
+    /// Local address the adapter should be pointed at instead of the real pool.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Snapshot of everything observed on the wire so far.
+    pub async fn get_stats(&self) -> StratumProxyStats {
+        self.stats.lock().await.clone()
+    }
+}
+
+/// Forward the line-delimited Stratum stream in both directions, observing
+/// `mining.subscribe`/`mining.authorize`/`mining.submit` requests from the
+/// miner and their matching responses (plus unsolicited notifications like
+/// `mining.set_difficulty`) from the pool.
+async fn forward(miner: TcpStream, pool: TcpStream, stats: Arc<Mutex<StratumProxyStats>>) {
+    stats.lock().await.connection_state = ConnectionState::Connected;
+
+    let (miner_read, mut miner_write) = miner.into_split();
+    let (pool_read, mut pool_write) = pool.into_split();
+
+    // Request ids the miner is waiting on a response for, mapped to their
+    // method, so a later `{"id":..,"result":..}` can be matched back to its request.
+    let pending: Arc<Mutex<HashMap<u64, String>>> = Arc::new(Mutex::new(HashMap::new()));
+
+    let request_pending = pending.clone();
+    let request_stats = stats.clone();
+    let miner_to_pool = tokio::spawn(async move {
+        let mut reader = BufReader::new(miner_read);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    observe_request(&line, &request_stats, &request_pending).await;
+                    if pool_write.write_all(line.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let pool_to_miner = tokio::spawn(async move {
+        let mut reader = BufReader::new(pool_read);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    observe_response(&line, &stats, &pending).await;
+                    if miner_write.write_all(line.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    let _ = tokio::join!(miner_to_pool, pool_to_miner);
+}
+
+/// Record the method of a miner -> pool request, keyed by id, so the
+/// matching pool -> miner response can be identified later.
+async fn observe_request(
+    line: &str,
+    stats: &Arc<Mutex<StratumProxyStats>>,
+    pending: &Arc<Mutex<HashMap<u64, String>>>,
+) {
+    let Ok(value) = serde_json::from_str::<Value>(line.trim()) else {
+        return;
+    };
+    let (Some(id), Some(method)) = (
+        value.get("id").and_then(Value::as_u64),
+        value.get("method").and_then(Value::as_str),
+    ) else {
+        return;
+    };
+
+    if method == "mining.submit" {
+        stats.lock().await.pending_submits += 1;
+    }
+    pending.lock().await.insert(id, method.to_string());
+}
+
+/// Handle a pool -> miner message: either an unsolicited notification
+/// (`mining.set_difficulty`) or a response to a request recorded by
+/// `observe_request`.
+async fn observe_response(
+    line: &str,
+    stats: &Arc<Mutex<StratumProxyStats>>,
+    pending: &Arc<Mutex<HashMap<u64, String>>>,
+) {
+    let Ok(value) = serde_json::from_str::<Value>(line.trim()) else {
+        return;
+    };
+
+    if let Some(method) = value.get("method").and_then(Value::as_str) {
+        if method == "mining.set_difficulty" {
+            if let Some(difficulty) = value
+                .get("params")
+                .and_then(Value::as_array)
+                .and_then(|params| params.first())
+                .and_then(Value::as_f64)
+            {
+                stats.lock().await.difficulty = difficulty;
+            }
+        }
+        return;
+    }
+
+    let Some(id) = value.get("id").and_then(Value::as_u64) else {
+        return;
+    };
+    let Some(method) = pending.lock().await.remove(&id) else {
+        return;
+    };
+
+    match method.as_str() {
+        "mining.subscribe" => {
+            if value.get("result").is_some() {
+                stats.lock().await.connection_state = ConnectionState::Subscribed;
+            }
+        }
+        "mining.authorize" => {
+            if value.get("result").and_then(Value::as_bool) == Some(true) {
+                stats.lock().await.connection_state = ConnectionState::Authorized;
+            }
+        }
+        "mining.submit" => {
+            let mut guard = stats.lock().await;
+            guard.pending_submits = guard.pending_submits.saturating_sub(1);
+            if value.get("result").and_then(Value::as_bool) == Some(true) {
+                guard.accepted += 1;
+            } else {
+                guard.rejected += 1;
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 /// Stats from XMRig API
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -42,3 +44,267 @@ impl XMRigStats {
         self.results.shares_total.saturating_sub(self.results.shares_good)
     }
 }
+
+/// Max number of raw samples kept regardless of age - bounds memory for a
+/// session left running for days.
+const RING_CAPACITY: usize = 1024;
+/// Samples older than this are evicted regardless of count.
+const MAX_SAMPLE_AGE_SECS: u64 = 3600;
+/// Smoothing factor for the hashrate EMA (0-1, higher reacts faster).
+const EMA_ALPHA: f64 = 0.2;
+/// How many emitted snapshots are kept for the UI's trend-line history.
+const SNAPSHOT_HISTORY_CAPACITY: usize = 180;
+
+/// Tick cadence and alerting knobs for `StatsTracker`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetrySettings {
+    /// Seconds between emitted `TelemetrySnapshot`s.
+    pub tick_secs: u64,
+    /// Rejection ratio (0-1) over a tick window above which
+    /// `TelemetrySnapshot::reject_warning` is set.
+    pub reject_ratio_warning_threshold: f64,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            tick_secs: 20,
+            reject_ratio_warning_threshold: 0.05,
+        }
+    }
+}
+
+struct Sample {
+    at: Instant,
+    hashrate: f64,
+    accepted: u64,
+    rejected: u64,
+}
+
+/// Derived metrics computed once per tick - cheap to serialize straight to
+/// the UI for trend-line rendering.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelemetrySnapshot {
+    pub ema_hashrate: f64,
+    pub accepted_per_min: f64,
+    pub rejected_per_min: f64,
+    pub reject_ratio: f64,
+    pub reject_warning: bool,
+    pub hashrate_delta: f64,
+    pub accepted_delta: u64,
+    pub rejected_delta: u64,
+}
+
+/// Rolling statistics subsystem for the legacy single-session mining path.
+///
+/// `AppState::refresh_stats` polls the active miner adapter roughly once a
+/// second and writes the raw numbers straight into `MiningStatus` - useful
+/// for "what is it doing right now" but not for judging whether the session
+/// is *stable*. `StatsTracker` sits alongside that: it keeps a time-windowed
+/// ring buffer of `(timestamp, hashrate, accepted, rejected)` samples and,
+/// on a fixed cadence (`TelemetrySettings::tick_secs`, default 20s), folds
+/// the window since the previous tick into a `TelemetrySnapshot` - an EMA of
+/// hashrate, accepted/rejected shares-per-minute, a rejection ratio, and the
+/// deltas since the last tick so the UI can draw trend lines. This mirrors
+/// the periodic stratum statistics report (hash rate + share rate,
+/// rejected-share handling) that mature miners print every 20 seconds.
+pub struct StatsTracker {
+    samples: VecDeque<Sample>,
+    snapshots: VecDeque<TelemetrySnapshot>,
+    ema: Option<f64>,
+    last_tick_at: Option<Instant>,
+    last_tick_hashrate: f64,
+    last_tick_accepted: u64,
+    last_tick_rejected: u64,
+}
+
+impl StatsTracker {
+    pub fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(RING_CAPACITY),
+            snapshots: VecDeque::with_capacity(SNAPSHOT_HISTORY_CAPACITY),
+            ema: None,
+            last_tick_at: None,
+            last_tick_hashrate: 0.0,
+            last_tick_accepted: 0,
+            last_tick_rejected: 0,
+        }
+    }
+
+    /// Most recently emitted snapshot, `None` until the first tick elapses.
+    pub fn latest(&self) -> Option<&TelemetrySnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Emitted snapshots, oldest first, for UI trend-line rendering.
+    pub fn history(&self) -> impl Iterator<Item = &TelemetrySnapshot> {
+        self.snapshots.iter()
+    }
+
+    /// Ingest one poll. `accepted`/`rejected` are the adapter's cumulative
+    /// counters, not deltas. Returns a fresh `TelemetrySnapshot` once
+    /// `settings.tick_secs` have elapsed since the previous tick, `None`
+    /// otherwise (including on the very first call, which only seeds the
+    /// baseline).
+    pub fn record(
+        &mut self,
+        settings: &TelemetrySettings,
+        hashrate: f64,
+        accepted: u64,
+        rejected: u64,
+    ) -> Option<TelemetrySnapshot> {
+        let now = Instant::now();
+        self.ema = Some(match self.ema {
+            Some(prev) => EMA_ALPHA * hashrate + (1.0 - EMA_ALPHA) * prev,
+            None => hashrate,
+        });
+        self.samples.push_back(Sample { at: now, hashrate, accepted, rejected });
+        self.evict(now);
+
+        let Some(last_tick_at) = self.last_tick_at else {
+            self.last_tick_at = Some(now);
+            self.last_tick_hashrate = hashrate;
+            self.last_tick_accepted = accepted;
+            self.last_tick_rejected = rejected;
+            return None;
+        };
+
+        let elapsed = now.duration_since(last_tick_at);
+        if elapsed < Duration::from_secs(settings.tick_secs) {
+            return None;
+        }
+
+        let accepted_delta = accepted.saturating_sub(self.last_tick_accepted);
+        let rejected_delta = rejected.saturating_sub(self.last_tick_rejected);
+        let total_delta = accepted_delta + rejected_delta;
+        let reject_ratio = if total_delta == 0 {
+            0.0
+        } else {
+            rejected_delta as f64 / total_delta as f64
+        };
+        let elapsed_secs = elapsed.as_secs_f64().max(1.0);
+
+        let snapshot = TelemetrySnapshot {
+            ema_hashrate: self.ema.unwrap_or(0.0),
+            accepted_per_min: accepted_delta as f64 / elapsed_secs * 60.0,
+            rejected_per_min: rejected_delta as f64 / elapsed_secs * 60.0,
+            reject_ratio,
+            reject_warning: reject_ratio > settings.reject_ratio_warning_threshold,
+            hashrate_delta: hashrate - self.last_tick_hashrate,
+            accepted_delta,
+            rejected_delta,
+        };
+
+        self.last_tick_at = Some(now);
+        self.last_tick_hashrate = hashrate;
+        self.last_tick_accepted = accepted;
+        self.last_tick_rejected = rejected;
+        self.snapshots.push_back(snapshot.clone());
+        while self.snapshots.len() > SNAPSHOT_HISTORY_CAPACITY {
+            self.snapshots.pop_front();
+        }
+
+        Some(snapshot)
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while self.samples.len() > RING_CAPACITY {
+            self.samples.pop_front();
+        }
+        let cutoff = now.checked_sub(Duration::from_secs(MAX_SAMPLE_AGE_SECS));
+        if let Some(cutoff) = cutoff {
+            while self.samples.front().map(|s| s.at < cutoff).unwrap_or(false) {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// Clear all accumulated state. A session restart creates a fresh
+    /// `StatsTracker` via `new()` anyway, but this is exposed so the
+    /// baseline can be reset in place without dropping the tracker itself.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.snapshots.clear();
+        self.ema = None;
+        self.last_tick_at = None;
+        self.last_tick_hashrate = 0.0;
+        self.last_tick_accepted = 0;
+        self.last_tick_rejected = 0;
+    }
+}
+
+impl Default for StatsTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> TelemetrySettings {
+        TelemetrySettings {
+            tick_secs: 0,
+            reject_ratio_warning_threshold: 0.05,
+        }
+    }
+
+    #[test]
+    fn test_first_record_seeds_baseline_without_emitting() {
+        let mut tracker = StatsTracker::new();
+        assert!(tracker.record(&settings(), 1000.0, 0, 0).is_none());
+        assert!(tracker.latest().is_none());
+    }
+
+    #[test]
+    fn test_second_record_emits_deltas_against_first() {
+        let mut tracker = StatsTracker::new();
+        tracker.record(&settings(), 1000.0, 10, 1);
+        let snapshot = tracker.record(&settings(), 1200.0, 20, 2).unwrap();
+        assert_eq!(snapshot.accepted_delta, 10);
+        assert_eq!(snapshot.rejected_delta, 1);
+        assert!((snapshot.hashrate_delta - 200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reject_ratio_and_warning_threshold() {
+        let mut tracker = StatsTracker::new();
+        tracker.record(&settings(), 1000.0, 0, 0);
+        let snapshot = tracker.record(&settings(), 1000.0, 90, 10).unwrap();
+        assert!((snapshot.reject_ratio - 0.1).abs() < 1e-9);
+        assert!(snapshot.reject_warning);
+    }
+
+    #[test]
+    fn test_no_shares_yields_zero_ratio_no_warning() {
+        let mut tracker = StatsTracker::new();
+        tracker.record(&settings(), 1000.0, 0, 0);
+        let snapshot = tracker.record(&settings(), 1000.0, 0, 0).unwrap();
+        assert_eq!(snapshot.reject_ratio, 0.0);
+        assert!(!snapshot.reject_warning);
+    }
+
+    #[test]
+    fn test_tick_not_yet_due_returns_none() {
+        let mut tracker = StatsTracker::new();
+        let patient = TelemetrySettings {
+            tick_secs: 600,
+            reject_ratio_warning_threshold: 0.05,
+        };
+        tracker.record(&patient, 1000.0, 0, 0);
+        assert!(tracker.record(&patient, 1000.0, 5, 0).is_none());
+    }
+
+    #[test]
+    fn test_history_retains_emitted_snapshots_in_order() {
+        let mut tracker = StatsTracker::new();
+        tracker.record(&settings(), 1000.0, 0, 0);
+        tracker.record(&settings(), 1000.0, 1, 0);
+        tracker.record(&settings(), 1000.0, 2, 0);
+        let history: Vec<_> = tracker.history().collect();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].accepted_delta, 1);
+        assert_eq!(history[1].accepted_delta, 1);
+    }
+}
@@ -4,6 +4,7 @@
 //! User can enable AUTO_DISTRIBUTE or ENFORCE_LIMIT if desired.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// CPU budget management mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -112,6 +113,115 @@ pub fn suggest_threads_for_new_session(
     (budget / future_sessions).max(1)
 }
 
+/// Per-session priority class used to weight thread allocation when
+/// `BudgetMode::EnforceLimit` has to shrink total requested threads down to
+/// the budget cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionPriority {
+    /// Gets squeezed first when over budget.
+    Background,
+    /// [DEFAULT]
+    #[default]
+    Normal,
+    /// Gets squeezed last when over budget.
+    Foreground,
+}
+
+impl SessionPriority {
+    pub fn weight(&self) -> f32 {
+        match self {
+            Self::Background => 0.5,
+            Self::Normal => 1.0,
+            Self::Foreground => 2.0,
+        }
+    }
+}
+
+/// Distribute `budget_threads` across `requested` (session id -> (requested
+/// threads, priority)) using the largest-remainder (Hamilton) method, with
+/// each session's share weighted by its priority class.
+///
+/// Every session that requested at least one thread gets at least one back
+/// as long as `budget_threads >= requested.len()`; if the budget is smaller
+/// than the session count this is best-effort (some sessions may land at 0).
+pub fn enforce_budget(
+    requested: &BTreeMap<String, (u32, SessionPriority)>,
+    budget_threads: u32,
+) -> BTreeMap<String, u32> {
+    if requested.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let weighted: BTreeMap<&String, f32> = requested
+        .iter()
+        .map(|(id, (threads, priority))| (id, *threads as f32 * priority.weight()))
+        .collect();
+    let total_weighted: f32 = weighted.values().sum();
+
+    let mut allocations: BTreeMap<String, u32> = BTreeMap::new();
+    let mut remainders: Vec<(String, f32)> = Vec::new();
+    let mut allocated_so_far: u32 = 0;
+
+    if total_weighted <= 0.0 {
+        // Nothing meaningfully requested - split the budget evenly.
+        let share = budget_threads / requested.len() as u32;
+        for id in requested.keys() {
+            allocations.insert(id.clone(), share);
+            allocated_so_far += share;
+        }
+    } else {
+        for (id, weight) in &weighted {
+            let exact_share = budget_threads as f32 * weight / total_weighted;
+            let floor_share = exact_share.floor();
+            allocations.insert((*id).clone(), floor_share as u32);
+            allocated_so_far += floor_share as u32;
+            remainders.push(((*id).clone(), exact_share - floor_share));
+        }
+    }
+
+    // Hand out the leftover threads one at a time to the largest fractional
+    // remainders, tie-broken by session id for determinism.
+    remainders.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.cmp(&b.0)));
+    let mut leftover = budget_threads.saturating_sub(allocated_so_far);
+    for (id, _) in remainders {
+        if leftover == 0 {
+            break;
+        }
+        *allocations.get_mut(&id).unwrap() += 1;
+        leftover -= 1;
+    }
+
+    // Best-effort fixup: every session that requested threads should keep at
+    // least one, as long as there's enough budget to go around. Steal one
+    // thread at a time from the current largest allocation.
+    if budget_threads >= requested.len() as u32 {
+        loop {
+            let starved = allocations
+                .iter()
+                .find(|(id, &threads)| threads == 0 && requested[*id].0 > 0)
+                .map(|(id, _)| id.clone());
+            let Some(starved_id) = starved else {
+                break;
+            };
+            let donor = allocations
+                .iter()
+                .filter(|(_, &threads)| threads > 1)
+                .max_by_key(|(_, &threads)| threads)
+                .map(|(id, _)| id.clone());
+            match donor {
+                Some(donor_id) => {
+                    *allocations.get_mut(&donor_id).unwrap() -= 1;
+                    *allocations.get_mut(&starved_id).unwrap() += 1;
+                }
+                None => break,
+            }
+        }
+    }
+
+    allocations
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,4 +268,44 @@ mod tests {
         let suggested_with_existing = suggest_threads_for_new_session(&settings, 2);
         assert!(suggested_with_existing <= suggested);
     }
+
+    #[test]
+    fn test_enforce_budget_sums_to_cap() {
+        let mut requested = BTreeMap::new();
+        requested.insert("a".to_string(), (4, SessionPriority::Normal));
+        requested.insert("b".to_string(), (4, SessionPriority::Normal));
+        requested.insert("c".to_string(), (4, SessionPriority::Normal));
+
+        let allocations = enforce_budget(&requested, 6);
+        let total: u32 = allocations.values().sum();
+        assert_eq!(total, 6);
+        assert_eq!(allocations.len(), 3);
+    }
+
+    #[test]
+    fn test_enforce_budget_weights_by_priority() {
+        let mut requested = BTreeMap::new();
+        requested.insert("background".to_string(), (4, SessionPriority::Background));
+        requested.insert("foreground".to_string(), (4, SessionPriority::Foreground));
+
+        let allocations = enforce_budget(&requested, 6);
+        assert_eq!(allocations.values().sum::<u32>(), 6);
+        assert!(allocations["foreground"] > allocations["background"]);
+    }
+
+    #[test]
+    fn test_enforce_budget_guarantees_minimum_one_thread() {
+        let mut requested = BTreeMap::new();
+        requested.insert("a".to_string(), (8, SessionPriority::Foreground));
+        requested.insert("b".to_string(), (1, SessionPriority::Background));
+
+        let allocations = enforce_budget(&requested, 3);
+        assert_eq!(allocations.values().sum::<u32>(), 3);
+        assert!(*allocations.get("b").unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_session_priority_default_is_normal() {
+        assert_eq!(SessionPriority::default(), SessionPriority::Normal);
+    }
 }
@@ -0,0 +1,228 @@
+//! Supervises background worker loops (stat refresh, pool-health polling,
+//! history retention sweeps, ...) so there's one place to see what's
+//! actually running instead of each being an ad-hoc `tokio::spawn` nobody
+//! can introspect - the same problem the auto-miner's idle poller or the
+//! session manager's stats loop solve individually, generalized.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tracing::warn;
+
+/// Outcome of one `Worker::step` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerState {
+    /// Did something this tick; the manager re-polls immediately.
+    Busy,
+    /// Nothing to do; the manager waits `idle_interval` before the next poll.
+    Idle,
+    /// Finished for good (not an error) - the manager stops polling and
+    /// marks it dead.
+    Done,
+}
+
+/// Persisted health `list_workers` reports, distinct from the instantaneous
+/// `WorkerState` a single `step()` call returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkerHealth {
+    Active,
+    Idle,
+    Dead,
+}
+
+/// Something `WorkerManager` can run in its own loop and introspect.
+pub trait Worker {
+    /// Human name shown in `list_workers`/the tray's "Background Tasks" submenu.
+    fn name(&self) -> &str;
+    /// Short human status line, e.g. "2 sessions refreshed".
+    fn status(&self) -> String;
+    /// Do one unit of work and report whether more is pending.
+    async fn step(&mut self) -> WorkerState;
+}
+
+/// Point-in-time view of one worker, returned by `list_workers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerSnapshot {
+    pub name: String,
+    pub health: WorkerHealth,
+    pub status: String,
+    pub last_error: Option<String>,
+}
+
+struct WorkerRecord {
+    name: String,
+    health: WorkerHealth,
+    status: String,
+    last_error: Option<String>,
+}
+
+/// Shared handle to the running workers - cheap to clone, every clone sees
+/// the same underlying records, same as `AutoMinerHandle`.
+#[derive(Clone, Default)]
+pub struct WorkerManager {
+    records: Arc<StdMutex<Vec<WorkerRecord>>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns `worker` in its own loop: polls `step()` back-to-back while
+    /// `Busy`, sleeps `idle_interval` between polls while `Idle`, and stops
+    /// on `Done`. A second, tiny supervisor task awaits the loop's
+    /// `JoinHandle` so a panicking `step()` is recorded as `Dead` with the
+    /// panic message as `last_error` instead of the worker silently
+    /// vanishing from `list_workers`.
+    pub fn spawn<W>(&self, mut worker: W, idle_interval: Duration)
+    where
+        W: Worker + Send + 'static,
+    {
+        let records = self.records.clone();
+        let index = {
+            let mut guard = records.lock().unwrap();
+            guard.push(WorkerRecord {
+                name: worker.name().to_string(),
+                health: WorkerHealth::Idle,
+                status: worker.status(),
+                last_error: None,
+            });
+            guard.len() - 1
+        };
+
+        let loop_records = records.clone();
+        let handle = tokio::spawn(async move {
+            loop {
+                let state = worker.step().await;
+                let mut guard = loop_records.lock().unwrap();
+                if let Some(record) = guard.get_mut(index) {
+                    record.status = worker.status();
+                    record.health = match state {
+                        WorkerState::Busy => WorkerHealth::Active,
+                        WorkerState::Idle => WorkerHealth::Idle,
+                        WorkerState::Done => WorkerHealth::Dead,
+                    };
+                }
+                drop(guard);
+
+                match state {
+                    WorkerState::Done => return,
+                    WorkerState::Busy => {}
+                    WorkerState::Idle => tokio::time::sleep(idle_interval).await,
+                }
+            }
+        });
+
+        tokio::spawn(async move {
+            if let Err(join_err) = handle.await {
+                warn!("Background worker task panicked: {}", join_err);
+                let mut guard = records.lock().unwrap();
+                if let Some(record) = guard.get_mut(index) {
+                    record.health = WorkerHealth::Dead;
+                    record.last_error = Some(join_err.to_string());
+                    record.status = "crashed".to_string();
+                }
+            }
+        });
+    }
+
+    /// Snapshot of every worker registered so far, in registration order.
+    pub fn list_workers(&self) -> Vec<WorkerSnapshot> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| WorkerSnapshot {
+                name: r.name.clone(),
+                health: r.health,
+                status: r.status.clone(),
+                last_error: r.last_error.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingWorker {
+        remaining: usize,
+        steps: Arc<AtomicUsize>,
+    }
+
+    impl Worker for CountingWorker {
+        fn name(&self) -> &str {
+            "counting-worker"
+        }
+
+        fn status(&self) -> String {
+            format!("{} steps remaining", self.remaining)
+        }
+
+        async fn step(&mut self) -> WorkerState {
+            self.steps.fetch_add(1, Ordering::SeqCst);
+            if self.remaining == 0 {
+                return WorkerState::Done;
+            }
+            self.remaining -= 1;
+            WorkerState::Busy
+        }
+    }
+
+    struct PanickingWorker;
+
+    impl Worker for PanickingWorker {
+        fn name(&self) -> &str {
+            "panicking-worker"
+        }
+
+        fn status(&self) -> String {
+            "about to panic".to_string()
+        }
+
+        async fn step(&mut self) -> WorkerState {
+            panic!("boom");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawned_worker_reaches_dead_after_done() {
+        let manager = WorkerManager::new();
+        let steps = Arc::new(AtomicUsize::new(0));
+        manager.spawn(CountingWorker { remaining: 3, steps: steps.clone() }, Duration::from_millis(10));
+
+        for _ in 0..50 {
+            if manager.list_workers()[0].health == WorkerHealth::Dead {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let snapshot = manager.list_workers();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].name, "counting-worker");
+        assert_eq!(snapshot[0].health, WorkerHealth::Dead);
+        assert!(steps.load(Ordering::SeqCst) >= 4);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_worker_is_recorded_dead_with_last_error() {
+        let manager = WorkerManager::new();
+        manager.spawn(PanickingWorker, Duration::from_millis(10));
+
+        for _ in 0..50 {
+            if manager.list_workers()[0].last_error.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let snapshot = manager.list_workers();
+        assert_eq!(snapshot[0].health, WorkerHealth::Dead);
+        assert!(snapshot[0].last_error.is_some());
+    }
+}
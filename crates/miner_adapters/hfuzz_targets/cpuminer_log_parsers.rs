@@ -0,0 +1,31 @@
+//! honggfuzz target for cpuminer-opt's stdout log extractors.
+//!
+//! `extract_hashrate`, `extract_shares`, and `extract_difficulty` run
+//! untrusted miner/pool output through regexes - feed them arbitrary byte
+//! strings (non-UTF8 reassembled as lossy, giant numbers, embedded ANSI
+//! escapes) and assert they never panic and that `extract_shares` never
+//! reports `accepted` exceeding the cumulative total it was parsed from.
+//!
+//! Run with `cargo hfuzz run cpuminer_log_parsers` (requires building this
+//! crate with `--cfg fuzzing`, which is what exposes `fuzz_support`).
+
+use honggfuzz::fuzz;
+use openminedash_miner_adapters::fuzz_support::{extract_difficulty, extract_hashrate, extract_shares};
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            let line = String::from_utf8_lossy(data);
+
+            let _ = extract_hashrate(&line);
+            let _ = extract_difficulty(&line);
+
+            if let Some((accepted, rejected)) = extract_shares(&line) {
+                assert!(
+                    accepted <= accepted + rejected,
+                    "extract_shares reported an impossible accepted/rejected split for {line:?}"
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,45 @@
+//! Common lifecycle for everything that can mine: `ensure_binary`, `start`,
+//! `stop`, `get_stats`, `state`, extracted from `XMRigAdapter` so that
+//! `CoinDefinition::recommended_miner` values other than `"xmrig"`
+//! (`external-asic`, `external-gpu`, `custom`) have something to actually
+//! spawn instead of only passing `validate_plugin`.
+//!
+//! Each backend normalizes its own stats type into `NormalizedMinerStats` so
+//! callers (the UI layer, `AppState::refresh_stats`) don't need to know
+//! which binary is running underneath.
+
+use crate::{MiningConfig, MinerState, Result};
+use serde::{Deserialize, Serialize};
+use tokio::process::Child;
+
+/// Stats common to every backend, regardless of the underlying miner's
+/// native reporting format.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NormalizedMinerStats {
+    pub hashrate: f64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub uptime_secs: u64,
+}
+
+/// Lifecycle shared by every miner backend (XMRig, cpuminer-opt, external
+/// GPU/ASIC/custom binaries).
+pub trait MinerBackend {
+    /// Verify (and if necessary locate) the backend's binary, returning its
+    /// path on success.
+    async fn ensure_binary(&mut self) -> Result<std::path::PathBuf>;
+
+    /// Launch the backend, streaming its output to `app_handle` as
+    /// `"miner-log"` events. Returns the spawned child process so the
+    /// caller can manage its lifetime alongside other sessions.
+    async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<Child>;
+
+    /// Stop a previously-started child, attempting a graceful shutdown
+    /// before forcing termination.
+    async fn stop(&mut self, child: &mut Child);
+
+    /// Fetch the backend's current stats, normalized to a common shape.
+    async fn get_stats(&self) -> Result<NormalizedMinerStats>;
+
+    fn state(&self) -> MinerState;
+}
@@ -0,0 +1,124 @@
+//! Algorithm parameter/DAG provisioning cache for GPU-mineable algorithms.
+//!
+//! Several GPU algorithms (`ethash`, `etchash`, `kawpow`, `equihash`,
+//! `cuckoo`/`cuckatoo`/`cuckaroo`) need a large parameter or DAG file keyed
+//! by an epoch or block height. Generating or downloading these is
+//! expensive, so this cache persists them once under `params_dir()` and
+//! verifies their integrity with SHA256 before reuse on every later launch -
+//! the same fetch-once-and-persist-with-integrity-check approach used for
+//! Zcash Sprout/Sapling parameters.
+
+use crate::{AdapterError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Identifies one cached parameter set: the algorithm plus the epoch
+/// (ethash-family) or block height (equihash/cuckoo) it was generated for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DagCacheKey {
+    pub algorithm: String,
+    pub epoch_or_height: u64,
+}
+
+impl DagCacheKey {
+    fn file_name(&self) -> String {
+        format!("{}-{}.params", self.algorithm, self.epoch_or_height)
+    }
+}
+
+/// Where to fetch a param file from and what its verified hash should be.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DagSource {
+    pub url: String,
+    pub sha256: String,
+}
+
+fn params_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join("bin")
+        .join("params")
+}
+
+/// Path the cached file for `key` would live at, whether or not it exists yet.
+pub fn cached_path(key: &DagCacheKey) -> PathBuf {
+    params_dir().join(key.file_name())
+}
+
+async fn sha256_of(path: &std::path::Path) -> Result<String> {
+    let bytes = tokio::fs::read(path).await?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Ensure the param/DAG file for `key` is present at `cached_path(key)` and
+/// matches `source.sha256`, downloading it first if missing or mismatched.
+/// Blocks until the file is verified; callers that need it before starting a
+/// miner should `await` this ahead of spawning the process.
+pub async fn ensure_params(key: &DagCacheKey, source: &DagSource) -> Result<PathBuf> {
+    let path = cached_path(key);
+
+    if path.exists() {
+        match sha256_of(&path).await {
+            Ok(hash) if hash.eq_ignore_ascii_case(&source.sha256) => {
+                info!("Reusing cached params for {}@{}", key.algorithm, key.epoch_or_height);
+                return Ok(path);
+            }
+            Ok(_) => warn!(
+                "Cached params for {}@{} failed integrity check, re-downloading",
+                key.algorithm, key.epoch_or_height
+            ),
+            Err(e) => warn!("Failed to hash cached params, re-downloading: {}", e),
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    info!("Downloading params for {}@{} from {}", key.algorithm, key.epoch_or_height, source.url);
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(&source.url)
+        .send()
+        .await
+        .map_err(|e| AdapterError::DownloadFailed(e.to_string()))?;
+
+    if !resp.status().is_success() {
+        return Err(AdapterError::DownloadFailed(format!(
+            "params download returned status {}",
+            resp.status()
+        )));
+    }
+
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| AdapterError::DownloadFailed(e.to_string()))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let computed = hex::encode(hasher.finalize());
+    if !computed.eq_ignore_ascii_case(&source.sha256) {
+        return Err(AdapterError::ChecksumMismatch);
+    }
+
+    tokio::fs::write(&path, &bytes).await?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_path_includes_algorithm_and_epoch() {
+        let key = DagCacheKey { algorithm: "ethash".to_string(), epoch_or_height: 512 };
+        let path = cached_path(&key);
+        assert!(path.to_string_lossy().contains("ethash-512.params"));
+    }
+}
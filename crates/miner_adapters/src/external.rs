@@ -0,0 +1,221 @@
+//! Generic external-process backend for GPU/ASIC/custom miners.
+//!
+//! Unlike XMRig and cpuminer-opt, external GPU miners (e.g. lolMiner,
+//! T-Rex, gminer for kawpow/autolykos2/etc.) and ASIC bridges don't share a
+//! single stats protocol this crate can special-case. This adapter spawns
+//! whatever binary the user configured with `{pool}`/`{wallet}`/`{worker}`
+//! substituted into its arguments, and reports only what's true for any
+//! process: whether it's running and for how long. Miners that expose an
+//! HTTP/TCP stats API can be wrapped with a dedicated adapter later without
+//! changing this one.
+
+use crate::xmrig::{MinerState, MiningConfig};
+use crate::{AdapterError, DagCacheKey, DagSource, MinerBackend, NormalizedMinerStats, Result};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Instant;
+use tauri::Manager;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tracing::{info, warn};
+
+/// Config for spawning an arbitrary external miner binary.
+#[derive(Debug, Clone)]
+pub struct ExternalMinerConfig {
+    pub binary_path: PathBuf,
+    /// Argument template; `{pool}`, `{wallet}`, `{worker}` are substituted
+    /// from `MiningConfig` before spawning.
+    pub args_template: Vec<String>,
+    /// Epoch/height-keyed DAG or parameter file this algorithm needs, if
+    /// any. When set, `start` blocks until it's downloaded and verified.
+    pub dag_params: Option<(DagCacheKey, DagSource)>,
+}
+
+pub struct ExternalAdapter {
+    config: ExternalMinerConfig,
+    state: MinerState,
+    started_at: Option<Instant>,
+}
+
+impl ExternalAdapter {
+    pub fn new(config: ExternalMinerConfig) -> Self {
+        Self {
+            config,
+            state: MinerState::Stopped,
+            started_at: None,
+        }
+    }
+
+    pub fn state(&self) -> MinerState {
+        self.state
+    }
+
+    fn build_args(&self, config: &MiningConfig) -> Vec<String> {
+        self.config
+            .args_template
+            .iter()
+            .map(|arg| {
+                arg.replace("{pool}", &config.pool)
+                    .replace("{wallet}", &config.wallet)
+                    .replace("{worker}", &config.worker)
+            })
+            .collect()
+    }
+
+    pub async fn ensure_binary(&mut self) -> Result<PathBuf> {
+        if !self.config.binary_path.exists() {
+            return Err(AdapterError::BinaryNotFound(format!(
+                "{:?} not found. Set a custom binary path for this miner in Settings.",
+                self.config.binary_path
+            )));
+        }
+        Ok(self.config.binary_path.clone())
+    }
+
+    pub async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<Child> {
+        if self.state != MinerState::Stopped && self.state != MinerState::Error {
+            return Err(AdapterError::Process("Miner already running".to_string()));
+        }
+
+        self.state = MinerState::Starting;
+
+        if let Some((key, source)) = &self.config.dag_params {
+            if let Err(e) = crate::ensure_params(key, source).await {
+                self.state = MinerState::Error;
+                return Err(e);
+            }
+        }
+
+        let binary = self.ensure_binary().await?;
+        let args = self.build_args(config);
+
+        info!("Starting external miner: {:?} {:?}", binary, args);
+
+        let mut child = Command::new(&binary)
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AdapterError::Process(format!("Failed to spawn: {}", e)))?;
+
+        if let Some(stdout) = child.stdout.take() {
+            let handle = app_handle.clone();
+            tokio::spawn(async move {
+                let reader = BufReader::new(stdout);
+                let mut lines = reader.lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let _ = handle.emit_all("miner-log", &line);
+                }
+            });
+        }
+
+        if let Some(stderr) = child.stderr.take() {
+            let handle = app_handle.clone();
+            tokio::spawn(async move {
+                let reader = BufReader::new(stderr);
+                let mut lines = reader.lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let _ = handle.emit_all("miner-log", &format!("[stderr] {}", line));
+                }
+            });
+        }
+
+        if config.lower_priority {
+            crate::apply_background_priority(&child);
+        }
+
+        self.started_at = Some(Instant::now());
+        self.state = MinerState::Running;
+        Ok(child)
+    }
+
+    pub async fn stop(&mut self, child: &mut Child) {
+        if self.state != MinerState::Running {
+            return;
+        }
+
+        self.state = MinerState::Stopping;
+        info!("Stopping external miner (SIGTERM -> timeout -> SIGKILL)");
+
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::{kill, Signal};
+            use nix::unistd::Pid;
+            if let Some(pid) = child.id() {
+                let _ = kill(Pid::from_raw(pid as i32), Signal::SIGTERM);
+            }
+        }
+
+        match tokio::time::timeout(std::time::Duration::from_secs(3), child.wait()).await {
+            Ok(Ok(status)) => info!("External miner stopped gracefully: {}", status),
+            Ok(Err(e)) => warn!("Error waiting for external miner: {}", e),
+            Err(_) => {
+                warn!("External miner did not stop in 3s, sending SIGKILL");
+                let _ = child.kill().await;
+                let _ = child.wait().await;
+            }
+        }
+
+        self.started_at = None;
+        self.state = MinerState::Stopped;
+    }
+
+    pub async fn get_stats(&self) -> Result<NormalizedMinerStats> {
+        Ok(NormalizedMinerStats {
+            uptime_secs: self.started_at.map(|t| t.elapsed().as_secs()).unwrap_or(0),
+            ..Default::default()
+        })
+    }
+}
+
+impl MinerBackend for ExternalAdapter {
+    async fn ensure_binary(&mut self) -> Result<PathBuf> {
+        ExternalAdapter::ensure_binary(self).await
+    }
+
+    async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<Child> {
+        ExternalAdapter::start(self, config, app_handle).await
+    }
+
+    async fn stop(&mut self, child: &mut Child) {
+        ExternalAdapter::stop(self, child).await
+    }
+
+    async fn get_stats(&self) -> Result<NormalizedMinerStats> {
+        ExternalAdapter::get_stats(self).await
+    }
+
+    fn state(&self) -> MinerState {
+        ExternalAdapter::state(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_args_substitutes_tokens() {
+        let adapter = ExternalAdapter::new(ExternalMinerConfig {
+            binary_path: PathBuf::from("/tmp/lolminer"),
+            args_template: vec!["--pool".to_string(), "{pool}".to_string(), "--user".to_string(), "{wallet}.{worker}".to_string()],
+            dag_params: None,
+        });
+
+        let config = MiningConfig {
+            coin: "ravencoin".to_string(),
+            pool: "kawpow.pool.example:3333".to_string(),
+            wallet: "RWalletAddress".to_string(),
+            worker: "rig1".to_string(),
+            threads: 0,
+            preset: Default::default(),
+            failover_pools: Vec::new(),
+            lower_priority: false,
+            credential: None,
+        };
+
+        let args = adapter.build_args(&config);
+        assert_eq!(args, vec!["--pool", "kawpow.pool.example:3333", "--user", "RWalletAddress.rig1"]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Multi-pool failover for `XMRigAdapter`.
+//!
+//! Tracks connection health from repeated `/2/summary` polls and decides
+//! when to rotate to the next configured pool: after `UNREACHABLE_THRESHOLD`
+//! consecutive unreachable polls, or after `UPTIME_RESET_THRESHOLD` polls
+//! where `uptime` drops back to zero (indicating the pool connection is
+//! churning even though the API itself answers). Rotation attempts are
+//! gated by an exponential backoff (1s, 2s, 4s, ... capped at 60s), which
+//! resets once a pool has stayed connected for over two minutes.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+const UNREACHABLE_THRESHOLD: u32 = 3;
+const UPTIME_RESET_THRESHOLD: u32 = 3;
+const INITIAL_BACKOFF_SECS: u64 = 1;
+const MAX_BACKOFF_SECS: u64 = 60;
+const STABLE_CONNECTION_SECS: u64 = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolFailoverStatus {
+    pub current_pool: String,
+    pub current_index: usize,
+    pub total_pools: usize,
+    pub healthy: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSwitchEvent {
+    pub previous_pool: String,
+    pub next_pool: String,
+    pub pool_index: usize,
+    pub total_pools: usize,
+    pub reason: String,
+}
+
+#[derive(Clone)]
+pub struct PoolFailoverTracker {
+    pools: Vec<String>,
+    current_index: usize,
+    consecutive_unreachable: u32,
+    uptime_resets: u32,
+    last_uptime: u64,
+    last_rotation: Option<Instant>,
+    next_backoff: Duration,
+}
+
+impl PoolFailoverTracker {
+    pub fn new(pools: Vec<String>) -> Self {
+        Self {
+            pools,
+            current_index: 0,
+            consecutive_unreachable: 0,
+            uptime_resets: 0,
+            last_uptime: 0,
+            last_rotation: None,
+            next_backoff: Duration::from_secs(INITIAL_BACKOFF_SECS),
+        }
+    }
+
+    pub fn current_pool(&self) -> &str {
+        &self.pools[self.current_index]
+    }
+
+    pub fn status(&self) -> PoolFailoverStatus {
+        PoolFailoverStatus {
+            current_pool: self.current_pool().to_string(),
+            current_index: self.current_index,
+            total_pools: self.pools.len(),
+            healthy: self.consecutive_unreachable == 0 && self.uptime_resets == 0,
+        }
+    }
+
+    /// Record one health-poll observation. Returns `Some(next_pool)` once
+    /// the active pool should be abandoned, gated by the backoff since the
+    /// last rotation.
+    pub fn record_poll(&mut self, reachable: bool, uptime: u64) -> Option<String> {
+        if self.pools.len() < 2 {
+            return None;
+        }
+
+        if !reachable {
+            self.consecutive_unreachable += 1;
+        } else {
+            self.consecutive_unreachable = 0;
+
+            if uptime == 0 && self.last_uptime > 0 {
+                self.uptime_resets += 1;
+            } else if uptime >= STABLE_CONNECTION_SECS {
+                self.uptime_resets = 0;
+                self.next_backoff = Duration::from_secs(INITIAL_BACKOFF_SECS);
+            }
+            self.last_uptime = uptime;
+        }
+
+        let unhealthy =
+            self.consecutive_unreachable >= UNREACHABLE_THRESHOLD || self.uptime_resets >= UPTIME_RESET_THRESHOLD;
+        if !unhealthy {
+            return None;
+        }
+
+        let backoff_elapsed = self
+            .last_rotation
+            .map(|t| t.elapsed() >= self.next_backoff)
+            .unwrap_or(true);
+        if !backoff_elapsed {
+            return None;
+        }
+
+        Some(self.rotate())
+    }
+
+    fn rotate(&mut self) -> String {
+        self.current_index = (self.current_index + 1) % self.pools.len();
+        self.consecutive_unreachable = 0;
+        self.uptime_resets = 0;
+        self.last_uptime = 0;
+        // The first rotation is ungated, so only a rotation that already
+        // waited out `next_backoff` doubles it for the one after.
+        if self.last_rotation.is_some() {
+            self.next_backoff = (self.next_backoff * 2).min(Duration::from_secs(MAX_BACKOFF_SECS));
+        }
+        self.last_rotation = Some(Instant::now());
+        self.current_pool().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> PoolFailoverTracker {
+        PoolFailoverTracker::new(vec![
+            "pool-a.example:3333".to_string(),
+            "pool-b.example:3333".to_string(),
+            "pool-c.example:3333".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_single_pool_never_rotates() {
+        let mut t = PoolFailoverTracker::new(vec!["only.example:3333".to_string()]);
+        for _ in 0..10 {
+            assert_eq!(t.record_poll(false, 0), None);
+        }
+    }
+
+    #[test]
+    fn test_rotates_after_consecutive_unreachable() {
+        let mut t = tracker();
+        assert_eq!(t.record_poll(false, 0), None);
+        assert_eq!(t.record_poll(false, 0), None);
+        let next = t.record_poll(false, 0);
+        assert_eq!(next, Some("pool-b.example:3333".to_string()));
+    }
+
+    #[test]
+    fn test_uptime_resets_trigger_rotation() {
+        let mut t = tracker();
+        t.record_poll(true, 30); // connected once
+        assert_eq!(t.record_poll(true, 0), None);
+        assert_eq!(t.record_poll(true, 0), None);
+        let next = t.record_poll(true, 0);
+        assert_eq!(next, Some("pool-b.example:3333".to_string()));
+    }
+
+    #[test]
+    fn test_backoff_blocks_rapid_rotation() {
+        let mut t = tracker();
+        t.record_poll(false, 0);
+        t.record_poll(false, 0);
+        assert!(t.record_poll(false, 0).is_some()); // first rotation, no prior backoff gate
+        // Immediately unhealthy again - backoff (1s) hasn't elapsed yet.
+        t.record_poll(false, 0);
+        t.record_poll(false, 0);
+        assert_eq!(t.record_poll(false, 0), None);
+    }
+
+    #[test]
+    fn test_status_reports_current_pool() {
+        let t = tracker();
+        let status = t.status();
+        assert_eq!(status.current_pool, "pool-a.example:3333");
+        assert_eq!(status.total_pools, 3);
+        assert!(status.healthy);
+    }
+}
@@ -9,6 +9,72 @@ use tokio::sync::mpsc;
 use tokio::time::{interval, Duration};
 use tracing::info;
 
+/// A scripted fault to inject into a fake adapter's run, in place of (or on
+/// top of) the default happy-path stream. Lets integration tests exercise
+/// reconnect logic, share-rejection handling, and supervisor restart paths
+/// deterministically instead of only steady-state.
+#[derive(Debug, Clone)]
+pub enum FakeEvent {
+    /// Pool connection drops; share/hashrate progress pauses until a
+    /// `Reconnect` event (or the scenario ends).
+    PoolDisconnect,
+    /// Reconnects after a prior `PoolDisconnect`.
+    Reconnect,
+    /// Rejects the next `n` shares in a row instead of accepting them.
+    RejectedShareBurst(u32),
+    /// Drops reported hashrate by `pct` percent for the rest of the run.
+    HashrateDrop(u8),
+    /// Emits a stratum error log line with the given error code.
+    StratumError(u32),
+    /// Ends the fake mining loop outright, as if the process had crashed.
+    Crash,
+}
+
+/// A single [`FakeEvent`] scheduled at an elapsed second into the run.
+#[derive(Debug, Clone)]
+pub struct ScheduledFakeEvent {
+    pub at_secs: u64,
+    pub event: FakeEvent,
+}
+
+/// A declarative timeline of faults to inject into a fake adapter's run.
+/// Build one with [`FakeScenario::builder`]; [`FakeScenario::steady_state`]
+/// reproduces the original fixed happy-path behavior.
+#[derive(Debug, Clone, Default)]
+pub struct FakeScenario {
+    events: Vec<ScheduledFakeEvent>,
+}
+
+impl FakeScenario {
+    /// The default happy-path stream: no injected faults.
+    pub fn steady_state() -> Self {
+        Self::default()
+    }
+
+    pub fn builder() -> FakeScenarioBuilder {
+        FakeScenarioBuilder::default()
+    }
+}
+
+/// Builder for [`FakeScenario`]. Events can be added out of order;
+/// [`FakeScenarioBuilder::build`] sorts them by `at_secs`.
+#[derive(Debug, Clone, Default)]
+pub struct FakeScenarioBuilder {
+    events: Vec<ScheduledFakeEvent>,
+}
+
+impl FakeScenarioBuilder {
+    pub fn at(mut self, at_secs: u64, event: FakeEvent) -> Self {
+        self.events.push(ScheduledFakeEvent { at_secs, event });
+        self
+    }
+
+    pub fn build(mut self) -> FakeScenario {
+        self.events.sort_by_key(|e| e.at_secs);
+        FakeScenario { events: self.events }
+    }
+}
+
 pub struct FakeMinerAdapter {
     state: MinerState,
     stop_signal: Option<mpsc::Sender<()>>,
@@ -21,6 +87,9 @@ struct FakeStats {
     accepted: AtomicU64,
     rejected: AtomicU64,
     uptime: AtomicU64,
+    disconnected: AtomicBool,
+    hashrate_pct: AtomicU64,
+    reject_burst_remaining: AtomicU64,
 }
 
 impl FakeMinerAdapter {
@@ -34,6 +103,9 @@ impl FakeMinerAdapter {
                 accepted: AtomicU64::new(0),
                 rejected: AtomicU64::new(0),
                 uptime: AtomicU64::new(0),
+                disconnected: AtomicBool::new(false),
+                hashrate_pct: AtomicU64::new(100),
+                reject_burst_remaining: AtomicU64::new(0),
             }),
         }
     }
@@ -43,6 +115,15 @@ impl FakeMinerAdapter {
     }
 
     pub async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<()> {
+        self.start_with_scenario(config, app_handle, FakeScenario::steady_state()).await
+    }
+
+    pub async fn start_with_scenario(
+        &mut self,
+        config: &MiningConfig,
+        app_handle: tauri::AppHandle,
+        scenario: FakeScenario,
+    ) -> Result<()> {
         if self.state == MinerState::Running {
             return Err(AdapterError::Process("Already running".to_string()));
         }
@@ -59,6 +140,9 @@ impl FakeMinerAdapter {
         stats.accepted.store(0, Ordering::SeqCst);
         stats.rejected.store(0, Ordering::SeqCst);
         stats.uptime.store(0, Ordering::SeqCst);
+        stats.disconnected.store(false, Ordering::SeqCst);
+        stats.hashrate_pct.store(100, Ordering::SeqCst);
+        stats.reject_burst_remaining.store(0, Ordering::SeqCst);
 
         let base_hashrate = match config.preset {
             PerformancePreset::Eco => 500,
@@ -68,9 +152,11 @@ impl FakeMinerAdapter {
 
         // Spawn fake mining loop
         let handle = app_handle.clone();
+        let events = scenario.events;
         tokio::spawn(async move {
             let mut tick = interval(Duration::from_secs(1));
             let mut second = 0u64;
+            let mut next_event = 0usize;
 
             // Initial connection logs
             let _ = handle.emit_all("miner-log", "[INFO] XMRig 6.21.0 (fake)");
@@ -87,28 +173,83 @@ impl FakeMinerAdapter {
                         second += 1;
                         stats.uptime.store(second, Ordering::SeqCst);
 
-                        // Simulate hashrate with small variance
-                        let variance = (second % 10) as u64 * 10;
-                        let hr = base_hashrate + variance;
-                        stats.hashrate.store(hr, Ordering::SeqCst);
+                        #[cfg(feature = "abort_mining")]
+                        {
+                            if second == 2 {
+                                let _ = handle.emit_all("miner-log", "[FATAL] abort_mining feature forced exit");
+                                std::process::exit(70);
+                            }
+                        }
+
+                        let mut crashed = false;
+                        while next_event < events.len() && events[next_event].at_secs <= second {
+                            match &events[next_event].event {
+                                FakeEvent::PoolDisconnect => {
+                                    stats.disconnected.store(true, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", "[WARN] connection to pool lost");
+                                }
+                                FakeEvent::Reconnect => {
+                                    stats.disconnected.store(false, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", "[INFO] reconnected to pool");
+                                }
+                                FakeEvent::RejectedShareBurst(n) => {
+                                    stats.reject_burst_remaining.fetch_add(*n as u64, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", &format!("[WARN] injecting {} rejected shares", n));
+                                }
+                                FakeEvent::HashrateDrop(pct) => {
+                                    stats.hashrate_pct.store(100u64.saturating_sub(*pct as u64), Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", &format!("[WARN] hashrate dropped {}%", pct));
+                                }
+                                FakeEvent::StratumError(code) => {
+                                    let _ = handle.emit_all("miner-log", &format!("[ERROR] stratum error {}", code));
+                                }
+                                FakeEvent::Crash => {
+                                    let _ = handle.emit_all("miner-log", "[ERROR] fake miner crashed");
+                                    crashed = true;
+                                }
+                            }
+                            next_event += 1;
+                        }
 
-                        // Accept share every 5 seconds
-                        if second % 5 == 0 {
-                            let accepted = stats.accepted.fetch_add(1, Ordering::SeqCst) + 1;
-                            let _ = handle.emit_all("miner-log", 
-                                &format!("[INFO] accepted ({}/0) diff {} ({}ms)", accepted, 100000, 50));
+                        if crashed {
+                            stats.running.store(false, Ordering::SeqCst);
+                            break;
                         }
 
-                        // Reject share every 30 seconds (rare)
-                        if second % 30 == 0 && second > 0 {
+                        if stats.disconnected.load(Ordering::SeqCst) {
+                            continue;
+                        }
+
+                        // Simulate hashrate with small variance, scaled by any injected drop
+                        let variance = (second % 10) as u64 * 10;
+                        let hr = (base_hashrate + variance) * stats.hashrate_pct.load(Ordering::SeqCst) / 100;
+                        stats.hashrate.store(hr, Ordering::SeqCst);
+
+                        if stats.reject_burst_remaining.load(Ordering::SeqCst) > 0 {
+                            // Injected rejection burst takes over the normal
+                            // accept/reject cadence until it's exhausted.
+                            stats.reject_burst_remaining.fetch_sub(1, Ordering::SeqCst);
                             stats.rejected.fetch_add(1, Ordering::SeqCst);
                             let _ = handle.emit_all("miner-log", "[WARN] rejected share");
+                        } else {
+                            // Accept share every 5 seconds
+                            if second % 5 == 0 {
+                                let accepted = stats.accepted.fetch_add(1, Ordering::SeqCst) + 1;
+                                let _ = handle.emit_all("miner-log",
+                                    &format!("[INFO] accepted ({}/0) diff {} ({}ms)", accepted, 100000, 50));
+                            }
+
+                            // Reject share every 30 seconds (rare)
+                            if second % 30 == 0 && second > 0 {
+                                stats.rejected.fetch_add(1, Ordering::SeqCst);
+                                let _ = handle.emit_all("miner-log", "[WARN] rejected share");
+                            }
                         }
 
                         // Periodic speed log
                         if second % 10 == 0 {
-                            let _ = handle.emit_all("miner-log", 
-                                &format!("[INFO] speed 10s/60s/15m {:.1} {:.1} {:.1} H/s", 
+                            let _ = handle.emit_all("miner-log",
+                                &format!("[INFO] speed 10s/60s/15m {:.1} {:.1} {:.1} H/s",
                                     hr as f64, hr as f64 * 0.98, hr as f64 * 0.95));
                         }
                     }
@@ -173,6 +314,23 @@ mod tests {
         assert_eq!(stats.results.shares_good, 0);
         assert_eq!(stats.connection.uptime, 0);
     }
+
+    #[test]
+    fn test_scenario_builder_sorts_by_time() {
+        let scenario = FakeScenario::builder()
+            .at(30, FakeEvent::Reconnect)
+            .at(10, FakeEvent::PoolDisconnect)
+            .build();
+
+        assert_eq!(scenario.events[0].at_secs, 10);
+        assert_eq!(scenario.events[1].at_secs, 30);
+    }
+
+    #[test]
+    fn test_steady_state_scenario_has_no_events() {
+        let scenario = FakeScenario::steady_state();
+        assert!(scenario.events.is_empty());
+    }
 }
 
 
@@ -193,6 +351,9 @@ struct FakeCpuminerStats {
     accepted: AtomicU64,
     rejected: AtomicU64,
     uptime: AtomicU64,
+    disconnected: AtomicBool,
+    hashrate_pct: AtomicU64,
+    reject_burst_remaining: AtomicU64,
 }
 
 impl FakeCpuminerAdapter {
@@ -206,6 +367,9 @@ impl FakeCpuminerAdapter {
                 accepted: AtomicU64::new(0),
                 rejected: AtomicU64::new(0),
                 uptime: AtomicU64::new(0),
+                disconnected: AtomicBool::new(false),
+                hashrate_pct: AtomicU64::new(100),
+                reject_burst_remaining: AtomicU64::new(0),
             }),
             algorithm: String::new(),
         }
@@ -216,6 +380,15 @@ impl FakeCpuminerAdapter {
     }
 
     pub async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<()> {
+        self.start_with_scenario(config, app_handle, FakeScenario::steady_state()).await
+    }
+
+    pub async fn start_with_scenario(
+        &mut self,
+        config: &MiningConfig,
+        app_handle: tauri::AppHandle,
+        scenario: FakeScenario,
+    ) -> Result<()> {
         if self.state == MinerState::Running {
             return Err(AdapterError::Process("Already running".to_string()));
         }
@@ -233,6 +406,9 @@ impl FakeCpuminerAdapter {
         stats.accepted.store(0, Ordering::SeqCst);
         stats.rejected.store(0, Ordering::SeqCst);
         stats.uptime.store(0, Ordering::SeqCst);
+        stats.disconnected.store(false, Ordering::SeqCst);
+        stats.hashrate_pct.store(100, Ordering::SeqCst);
+        stats.reject_burst_remaining.store(0, Ordering::SeqCst);
 
         // cpuminer-opt has much lower hashrates for SHA256/Scrypt on CPU
         let base_hashrate = match config.preset {
@@ -243,10 +419,12 @@ impl FakeCpuminerAdapter {
 
         let algo = config.coin.clone();
         let handle = app_handle.clone();
-        
+        let events = scenario.events;
+
         tokio::spawn(async move {
             let mut tick = interval(Duration::from_secs(1));
             let mut second = 0u64;
+            let mut next_event = 0usize;
 
             // cpuminer-opt style logs
             let _ = handle.emit_all("miner-log", &format!("[INFO] cpuminer-opt 3.24.5 (fake)"));
@@ -264,28 +442,81 @@ impl FakeCpuminerAdapter {
                         second += 1;
                         stats.uptime.store(second, Ordering::SeqCst);
 
-                        // Simulate very low hashrate with variance
-                        let variance = (second % 5) as u64 * 5;
-                        let hr = base_hashrate + variance;
-                        stats.hashrate.store(hr, Ordering::SeqCst);
+                        #[cfg(feature = "abort_mining")]
+                        {
+                            if second == 2 {
+                                let _ = handle.emit_all("miner-log", "[FATAL] abort_mining feature forced exit");
+                                std::process::exit(70);
+                            }
+                        }
 
-                        // Accept share every 10 seconds (slower than XMRig due to difficulty)
-                        if second % 10 == 0 {
-                            let accepted = stats.accepted.fetch_add(1, Ordering::SeqCst) + 1;
-                            let total = accepted + stats.rejected.load(Ordering::SeqCst);
-                            let _ = handle.emit_all("miner-log", 
-                                &format!("[INFO] accepted: {}/{} (diff {})", accepted, total, 1));
+                        let mut crashed = false;
+                        while next_event < events.len() && events[next_event].at_secs <= second {
+                            match &events[next_event].event {
+                                FakeEvent::PoolDisconnect => {
+                                    stats.disconnected.store(true, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", "[WARN] connection to stratum server lost");
+                                }
+                                FakeEvent::Reconnect => {
+                                    stats.disconnected.store(false, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", "[INFO] reconnected to stratum server");
+                                }
+                                FakeEvent::RejectedShareBurst(n) => {
+                                    stats.reject_burst_remaining.fetch_add(*n as u64, Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", &format!("[WARN] injecting {} rejected shares", n));
+                                }
+                                FakeEvent::HashrateDrop(pct) => {
+                                    stats.hashrate_pct.store(100u64.saturating_sub(*pct as u64), Ordering::SeqCst);
+                                    let _ = handle.emit_all("miner-log", &format!("[WARN] hashrate dropped {}%", pct));
+                                }
+                                FakeEvent::StratumError(code) => {
+                                    let _ = handle.emit_all("miner-log", &format!("[ERROR] stratum error {}", code));
+                                }
+                                FakeEvent::Crash => {
+                                    let _ = handle.emit_all("miner-log", "[ERROR] fake miner crashed");
+                                    crashed = true;
+                                }
+                            }
+                            next_event += 1;
+                        }
+
+                        if crashed {
+                            stats.running.store(false, Ordering::SeqCst);
+                            break;
+                        }
+
+                        if stats.disconnected.load(Ordering::SeqCst) {
+                            continue;
                         }
 
-                        // Reject share every 60 seconds
-                        if second % 60 == 0 && second > 0 {
+                        // Simulate very low hashrate with variance, scaled by any injected drop
+                        let variance = (second % 5) as u64 * 5;
+                        let hr = (base_hashrate + variance) * stats.hashrate_pct.load(Ordering::SeqCst) / 100;
+                        stats.hashrate.store(hr, Ordering::SeqCst);
+
+                        if stats.reject_burst_remaining.load(Ordering::SeqCst) > 0 {
+                            stats.reject_burst_remaining.fetch_sub(1, Ordering::SeqCst);
                             stats.rejected.fetch_add(1, Ordering::SeqCst);
                             let _ = handle.emit_all("miner-log", "[WARN] rejected share (stale)");
+                        } else {
+                            // Accept share every 10 seconds (slower than XMRig due to difficulty)
+                            if second % 10 == 0 {
+                                let accepted = stats.accepted.fetch_add(1, Ordering::SeqCst) + 1;
+                                let total = accepted + stats.rejected.load(Ordering::SeqCst);
+                                let _ = handle.emit_all("miner-log",
+                                    &format!("[INFO] accepted: {}/{} (diff {})", accepted, total, 1));
+                            }
+
+                            // Reject share every 60 seconds
+                            if second % 60 == 0 && second > 0 {
+                                stats.rejected.fetch_add(1, Ordering::SeqCst);
+                                let _ = handle.emit_all("miner-log", "[WARN] rejected share (stale)");
+                            }
                         }
 
                         // Periodic hashrate log (cpuminer style)
                         if second % 15 == 0 {
-                            let _ = handle.emit_all("miner-log", 
+                            let _ = handle.emit_all("miner-log",
                                 &format!("[INFO] CPU: {:.2} H/s", hr as f64));
                         }
                     }
@@ -314,6 +545,8 @@ impl FakeCpuminerAdapter {
             difficulty: 1.0,
             uptime: self.stats.uptime.load(Ordering::SeqCst),
             hashrate_unknown: false,
+            active_pool: String::new(),
+            pool_shares: std::collections::HashMap::new(),
         }
     }
 }
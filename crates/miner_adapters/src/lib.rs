@@ -1,22 +1,50 @@
+mod backend;
 mod cpuminer_opt;
+mod dag_cache;
+mod external;
+mod failover;
+mod manifest;
+mod metrics;
+mod process_priority;
+mod stratum_v2;
 mod xmrig;
 
 #[cfg(any(test, feature = "test-miners"))]
 mod fake;
 
+#[cfg(test)]
+mod txtar;
+
+pub use backend::{MinerBackend, NormalizedMinerStats};
+pub use dag_cache::{cached_path, ensure_params, DagCacheKey, DagSource};
+pub use external::{ExternalAdapter, ExternalMinerConfig};
+pub use failover::{PoolFailoverStatus, PoolFailoverTracker, PoolSwitchEvent};
+pub use metrics::{MetricsParser, MinerStats};
+pub use manifest::{
+    current_target_triple, install_from_manifest, parse_signed_manifest, resolve_entry,
+    BinaryManifest, ManifestEntry, ProvisioningEvent,
+};
+pub use process_priority::apply_background_priority;
+pub use stratum_v2::StratumV2Adapter;
+
 // Re-export common types from xmrig (canonical definitions)
-pub use xmrig::{MinerState, MiningConfig, PerformancePreset};
+pub use xmrig::{
+    resolve_credential, validate_config, MinerState, MiningConfig, PerformancePreset,
+    RECOGNIZED_POOL_SCHEMES,
+};
 
 // Re-export adapters
 pub use cpuminer_opt::{
-    CpuminerOptAdapter, CpuminerOptStats, 
+    CpuminerOptAdapter, CpuminerOptStats, Level, PoolShareBreakdown,
     map_algorithm as cpuminer_map_algorithm,
     supports_algorithm as cpuminer_supports_algorithm,
     SUPPORTED_ALGORITHMS as CPUMINER_SUPPORTED_ALGORITHMS,
 };
+#[cfg(fuzzing)]
+pub use cpuminer_opt::fuzz_support;
 
 #[cfg(any(test, feature = "test-miners"))]
-pub use fake::{FakeMinerAdapter, FakeCpuminerAdapter};
+pub use fake::{FakeCpuminerAdapter, FakeEvent, FakeMinerAdapter, FakeScenario, FakeScenarioBuilder, ScheduledFakeEvent};
 
 pub use xmrig::{XMRigAdapter, XMRigStats, XMRigHashrate, XMRigResults, XMRigConnection, XMRigCpu};
 
@@ -36,10 +64,14 @@ pub enum AdapterError {
     DownloadFailed(String),
     #[error("Process error: {0}")]
     Process(String),
+    #[error("Protocol error: {0}")]
+    Protocol(String),
     #[error("Path traversal detected")]
     PathTraversal,
     #[error("Invalid file permissions")]
     InvalidPermissions,
+    #[error("Invalid mining config: {0}")]
+    InvalidPoolConfig(String),
 }
 
 pub type Result<T> = std::result::Result<T, AdapterError>;
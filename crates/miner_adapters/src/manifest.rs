@@ -0,0 +1,324 @@
+//! Signed, architecture-aware binary manifest for miner provisioning.
+//!
+//! A manifest is a small JSON document mapping each supported miner and
+//! target triple to a download URL and pinned SHA-256 digest, e.g.
+//!
+//! ```json
+//! {
+//!   "xmrig": {
+//!     "x86_64-apple-darwin": { "url": "https://...", "sha256": "..." },
+//!     "aarch64-apple-darwin": { "url": "https://...", "sha256": "..." }
+//!   }
+//! }
+//! ```
+//!
+//! The manifest is fetched alongside a detached Ed25519 signature over its
+//! raw bytes; the signature is verified against `MANIFEST_PUBLIC_KEY` before
+//! any URL in it is trusted. This lets cross-arch installs (Apple Silicon
+//! vs Intel, ARM Linux boards) pick the right build automatically instead
+//! of relying on a single hardcoded `custom_binary_path`.
+
+use crate::{AdapterError, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use tracing::{info, warn};
+
+/// Public key used to verify the manifest's detached signature. Pinned in
+/// the binary, same spirit as `PINNED_CHECKSUMS` in xmrig.rs/cpuminer_opt.rs
+/// - replaced with the real release-signing key at build time.
+const MANIFEST_PUBLIC_KEY: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Public key used to verify a release binary's own detached (minisign-style)
+/// signature, separate from `MANIFEST_PUBLIC_KEY` so manifest and binary
+/// signing can be rotated independently.
+const BINARY_SIGNING_PUBLIC_KEY: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestEntry {
+    pub url: String,
+    pub sha256: String,
+    /// Detached ed25519 signature (hex) over the downloaded bytes, verified
+    /// against `BINARY_SIGNING_PUBLIC_KEY` in addition to the checksum.
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+/// Progress/verification events emitted to the UI during provisioning.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "lowercase")]
+pub enum ProvisioningEvent {
+    Downloading { url: String },
+    Verifying { sha256: String },
+    Installed { path: String },
+    Failed { reason: String },
+}
+
+fn emit_progress(app_handle: &tauri::AppHandle, event: ProvisioningEvent) {
+    let _ = app_handle.emit_all("binary-provisioning", &event);
+}
+
+/// `miner name -> target triple -> entry`.
+pub type BinaryManifest = HashMap<String, HashMap<String, ManifestEntry>>;
+
+/// The Rust-style target triple for the platform this process is running
+/// on, used as the manifest lookup key.
+pub fn current_target_triple() -> Option<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+        ("linux", "arm") => Some("armv7-unknown-linux-gnueabihf"),
+        ("windows", "x86_64") => Some("x86_64-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+/// Verify the manifest's detached signature against the embedded public key.
+/// Skipped (with a warning) when the key hasn't been replaced from its
+/// placeholder zero value, matching the dev-mode fallback used for pinned
+/// checksums elsewhere in this crate.
+fn verify_manifest_signature(manifest_bytes: &[u8], signature_hex: &str) -> Result<()> {
+    if MANIFEST_PUBLIC_KEY.chars().all(|c| c == '0') {
+        warn!("Manifest signature verification skipped (no embedded public key)");
+        return Ok(());
+    }
+
+    let key_bytes: [u8; 32] = hex::decode(MANIFEST_PUBLIC_KEY)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid embedded public key: {}", e)))?
+        .try_into()
+        .map_err(|_| AdapterError::DownloadFailed("embedded public key is not 32 bytes".to_string()))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid embedded public key: {}", e)))?;
+
+    let sig_bytes: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid manifest signature: {}", e)))?
+        .try_into()
+        .map_err(|_| AdapterError::DownloadFailed("manifest signature is not 64 bytes".to_string()))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    verifying_key
+        .verify(manifest_bytes, &signature)
+        .map_err(|_| AdapterError::DownloadFailed("manifest signature verification failed".to_string()))
+}
+
+/// Parse a signed manifest, verifying its signature first. Callers must not
+/// trust any URL from an unverified manifest.
+pub fn parse_signed_manifest(manifest_bytes: &[u8], signature_hex: &str) -> Result<BinaryManifest> {
+    verify_manifest_signature(manifest_bytes, signature_hex)?;
+
+    serde_json::from_slice(manifest_bytes)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid manifest JSON: {}", e)))
+}
+
+/// Verify a downloaded binary's detached signature against
+/// `BINARY_SIGNING_PUBLIC_KEY`. Skipped (with a warning), like manifest
+/// signature verification, when the key is still the zero placeholder.
+fn verify_binary_signature(bytes: &[u8], signature_hex: &str) -> Result<()> {
+    if BINARY_SIGNING_PUBLIC_KEY.chars().all(|c| c == '0') {
+        warn!("Binary signature verification skipped (no embedded public key)");
+        return Ok(());
+    }
+
+    let key_bytes: [u8; 32] = hex::decode(BINARY_SIGNING_PUBLIC_KEY)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid embedded public key: {}", e)))?
+        .try_into()
+        .map_err(|_| AdapterError::DownloadFailed("embedded public key is not 32 bytes".to_string()))?;
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid embedded public key: {}", e)))?;
+
+    let sig_bytes: [u8; 64] = hex::decode(signature_hex)
+        .map_err(|e| AdapterError::DownloadFailed(format!("invalid binary signature: {}", e)))?
+        .try_into()
+        .map_err(|_| AdapterError::DownloadFailed("binary signature is not 64 bytes".to_string()))?;
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    verifying_key
+        .verify(bytes, &signature)
+        .map_err(|_| AdapterError::DownloadFailed("binary signature verification failed".to_string()))
+}
+
+/// Path to the persisted blacklist of hashes that failed verification. A
+/// hash only ever lands here after its checksum/signature check fails, so
+/// reloading it guards against re-trusting a previously-pinned but since
+/// compromised release (mirrors the Parity snapshot client's approach of
+/// blacklisting bad manifest hashes on failure).
+fn blacklist_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("openminedash")
+        .join("blacklisted_hashes.json")
+}
+
+fn load_blacklist() -> HashSet<String> {
+    let path = blacklist_path();
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+fn blacklist_hash(sha256: &str) {
+    let path = blacklist_path();
+    let mut hashes = load_blacklist();
+    if !hashes.insert(sha256.to_lowercase()) {
+        return;
+    }
+
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(&hashes) {
+        if let Err(e) = std::fs::write(&path, json) {
+            warn!("Failed to persist blacklisted hash {}: {}", sha256, e);
+        }
+    }
+}
+
+/// Look up the manifest entry for `miner` on the current platform.
+pub fn resolve_entry<'a>(manifest: &'a BinaryManifest, miner: &str) -> Result<&'a ManifestEntry> {
+    let triple = current_target_triple().ok_or_else(|| {
+        AdapterError::DownloadFailed("no known binary build for this platform".to_string())
+    })?;
+
+    let entry = manifest
+        .get(miner)
+        .and_then(|targets| targets.get(triple))
+        .ok_or_else(|| {
+            AdapterError::DownloadFailed(format!(
+                "manifest has no entry for {} on {}",
+                miner, triple
+            ))
+        })?;
+
+    if load_blacklist().contains(&entry.sha256.to_lowercase()) {
+        return Err(AdapterError::DownloadFailed(format!(
+            "refusing to trust blacklisted hash for {} ({})",
+            miner, entry.sha256
+        )));
+    }
+
+    Ok(entry)
+}
+
+/// Download, verify, and install the binary described by `entry` to `dest`,
+/// emitting `binary-provisioning` events so the UI can show progress. Checks
+/// both the pinned SHA256 and (when configured) a detached release signature
+/// before trusting the download, and blacklists the computed hash on any
+/// verification failure so it is never re-trusted on a later launch. On
+/// macOS, clears the quarantine xattr so the binary runs without a Gatekeeper
+/// prompt.
+pub async fn install_from_manifest(
+    entry: &ManifestEntry,
+    dest: &Path,
+    app_handle: &tauri::AppHandle,
+) -> Result<PathBuf> {
+    if load_blacklist().contains(&entry.sha256.to_lowercase()) {
+        let reason = format!("refusing to trust blacklisted hash {}", entry.sha256);
+        emit_progress(app_handle, ProvisioningEvent::Failed { reason: reason.clone() });
+        return Err(AdapterError::DownloadFailed(reason));
+    }
+
+    if let Some(parent) = dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    info!("Downloading {} -> {:?}", entry.url, dest);
+    emit_progress(app_handle, ProvisioningEvent::Downloading { url: entry.url.clone() });
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(&entry.url)
+        .send()
+        .await
+        .map_err(|e| AdapterError::DownloadFailed(e.to_string()))?;
+
+    if !resp.status().is_success() {
+        let reason = format!("download returned status {}", resp.status());
+        emit_progress(app_handle, ProvisioningEvent::Failed { reason: reason.clone() });
+        return Err(AdapterError::DownloadFailed(reason));
+    }
+
+    let bytes = resp
+        .bytes()
+        .await
+        .map_err(|e| AdapterError::DownloadFailed(e.to_string()))?;
+
+    emit_progress(app_handle, ProvisioningEvent::Verifying { sha256: entry.sha256.clone() });
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let computed = hex::encode(hasher.finalize());
+    if !computed.eq_ignore_ascii_case(&entry.sha256) {
+        blacklist_hash(&computed);
+        emit_progress(app_handle, ProvisioningEvent::Failed { reason: "checksum mismatch".to_string() });
+        return Err(AdapterError::ChecksumMismatch);
+    }
+
+    if let Some(signature) = &entry.signature {
+        if let Err(e) = verify_binary_signature(&bytes, signature) {
+            blacklist_hash(&computed);
+            emit_progress(app_handle, ProvisioningEvent::Failed { reason: e.to_string() });
+            return Err(e);
+        }
+    }
+
+    tokio::fs::write(dest, &bytes).await?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = tokio::fs::metadata(dest).await?.permissions();
+        perms.set_mode(0o755);
+        tokio::fs::set_permissions(dest, perms).await?;
+    }
+
+    #[cfg(target_os = "macos")]
+    clear_quarantine(dest)?;
+
+    emit_progress(app_handle, ProvisioningEvent::Installed { path: dest.display().to_string() });
+
+    Ok(dest.to_path_buf())
+}
+
+#[cfg(target_os = "macos")]
+fn clear_quarantine(path: &Path) -> Result<()> {
+    let output = std::process::Command::new("xattr")
+        .args(["-d", "com.apple.quarantine"])
+        .arg(path)
+        .output()?;
+
+    // `xattr -d` exits non-zero if the attribute was never set; that's fine.
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.contains("No such xattr") {
+            warn!("Failed to clear quarantine xattr on {:?}: {}", path, stderr);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_signed_manifest_without_key_skips_verification() {
+        let manifest = br#"{"xmrig":{"x86_64-apple-darwin":{"url":"https://example.com/xmrig","sha256":"abc"}}}"#;
+        let parsed = parse_signed_manifest(manifest, "deadbeef").unwrap();
+        assert!(parsed.contains_key("xmrig"));
+    }
+
+    #[test]
+    fn test_resolve_entry_missing_miner() {
+        let manifest: BinaryManifest = HashMap::new();
+        let result = resolve_entry(&manifest, "xmrig");
+        assert!(result.is_err());
+    }
+}
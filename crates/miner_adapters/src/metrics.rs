@@ -0,0 +1,260 @@
+//! Backend-agnostic extraction of structured mining metrics from raw log
+//! lines.
+//!
+//! `StatsCollector` in `cpuminer_opt.rs` already does similar regex-based
+//! parsing, but it's wired into cpuminer-opt's own API-socket/failover
+//! logic and only ever sees that adapter's output. `MetricsParser` is the
+//! UI/telemetry-facing counterpart: feed it any line - xmrig or
+//! cpuminer-opt, it doesn't care which - and it keeps a `MinerStats`
+//! snapshot up to date, notifying subscribers whenever it changes. Unknown
+//! line formats are ignored rather than treated as errors.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Rolling average window for hashrate (samples), matching the window used
+/// by `cpuminer_opt::StatsCollector`.
+const AVG_HASHRATE_WINDOW: usize = 60;
+
+static HASHRATE_PATTERNS: Lazy<Vec<(Regex, f64)>> = Lazy::new(|| {
+    vec![
+        (Regex::new(r"(\d+\.?\d*)\s*GH/s").unwrap(), 1_000_000_000.0),
+        (Regex::new(r"(\d+\.?\d*)\s*MH/s").unwrap(), 1_000_000.0),
+        (Regex::new(r"(\d+\.?\d*)\s*kH/s").unwrap(), 1_000.0),
+        (Regex::new(r"(\d+\.?\d*)\s*H/s").unwrap(), 1.0),
+    ]
+});
+static ACCEPTED_TOTAL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)accepted[:\s]+(\d+)/(\d+)").unwrap());
+static ACCEPTED_PAREN_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)accepted\s*\((\d+)/(\d+)\)").unwrap());
+static DIFFICULTY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)diff[:\s]+(\d+\.?\d*)").unwrap());
+static POOL_CONNECTED_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(connected to|new job from|use pool)").unwrap());
+static POOL_DISCONNECTED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(connection.*(failed|lost|closed)|stratum_connect failed|no response from pool)")
+        .unwrap()
+});
+
+/// Snapshot of mining stats extracted so far from whatever lines
+/// `MetricsParser::feed_line` has seen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MinerStats {
+    pub hashrate: f64,
+    pub avg_hashrate: f64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub difficulty: f64,
+    pub pool_connected: bool,
+}
+
+type Subscriber = Box<dyn Fn(&MinerStats) + Send + Sync>;
+
+struct ParserInner {
+    stats: MinerStats,
+    hashrate_samples: VecDeque<f64>,
+    subscribers: Vec<Subscriber>,
+}
+
+/// Incrementally parses raw miner output into a `MinerStats` snapshot.
+/// Cheap to `Clone` - every clone shares the same underlying state, same
+/// as `cpuminer_opt::StatsCollector`.
+#[derive(Clone)]
+pub struct MetricsParser {
+    inner: Arc<Mutex<ParserInner>>,
+}
+
+impl MetricsParser {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ParserInner {
+                stats: MinerStats::default(),
+                hashrate_samples: VecDeque::with_capacity(AVG_HASHRATE_WINDOW),
+                subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Parse one line, updating `latest_stats()` and notifying subscribers
+    /// if anything changed. Lines matching none of the known patterns are
+    /// silently ignored.
+    pub fn feed_line(&self, line: &str) {
+        let snapshot = self.inner.lock().ok().and_then(|mut inner| {
+            let mut changed = false;
+
+            if let Some(hr) = Self::extract_hashrate(line) {
+                inner.stats.hashrate = hr;
+                if inner.hashrate_samples.len() >= AVG_HASHRATE_WINDOW {
+                    inner.hashrate_samples.pop_front();
+                }
+                inner.hashrate_samples.push_back(hr);
+                inner.stats.avg_hashrate = inner.hashrate_samples.iter().sum::<f64>()
+                    / inner.hashrate_samples.len() as f64;
+                changed = true;
+            }
+
+            if let Some((accepted, rejected)) = Self::extract_shares(line) {
+                inner.stats.accepted = accepted;
+                inner.stats.rejected = rejected;
+                changed = true;
+            }
+
+            if let Some(difficulty) = Self::extract_difficulty(line) {
+                inner.stats.difficulty = difficulty;
+                changed = true;
+            }
+
+            if POOL_CONNECTED_RE.is_match(line) && !inner.stats.pool_connected {
+                inner.stats.pool_connected = true;
+                changed = true;
+            } else if POOL_DISCONNECTED_RE.is_match(line) && inner.stats.pool_connected {
+                inner.stats.pool_connected = false;
+                changed = true;
+            }
+
+            changed.then(|| inner.stats.clone())
+        });
+
+        if let Some(stats) = snapshot {
+            self.notify(&stats);
+        }
+    }
+
+    /// The most recently computed snapshot.
+    pub fn latest_stats(&self) -> MinerStats {
+        self.inner.lock().map(|i| i.stats.clone()).unwrap_or_default()
+    }
+
+    /// Register a callback invoked with the new snapshot every time
+    /// `feed_line` changes it, so a UI or telemetry exporter can react to
+    /// changes instead of polling `latest_stats()`.
+    pub fn subscribe(&self, callback: impl Fn(&MinerStats) + Send + Sync + 'static) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.subscribers.push(Box::new(callback));
+        }
+    }
+
+    fn notify(&self, stats: &MinerStats) {
+        if let Ok(inner) = self.inner.lock() {
+            for subscriber in &inner.subscribers {
+                subscriber(stats);
+            }
+        }
+    }
+
+    /// Patterns: "1.23 kH/s", "1.23 MH/s", "1.23 H/s", "1.23 GH/s".
+    fn extract_hashrate(line: &str) -> Option<f64> {
+        for (re, multiplier) in HASHRATE_PATTERNS.iter() {
+            if let Some(caps) = re.captures(line) {
+                if let Ok(num) = caps[1].parse::<f64>() {
+                    return Some(num * multiplier);
+                }
+            }
+        }
+        None
+    }
+
+    /// Patterns: "accepted: 5/6", "accepted (5/6)". `accepted > total` is
+    /// malformed input and rejected rather than silently clamped.
+    fn extract_shares(line: &str) -> Option<(u64, u64)> {
+        let caps = ACCEPTED_TOTAL_RE.captures(line).or_else(|| ACCEPTED_PAREN_RE.captures(line))?;
+        let accepted: u64 = caps[1].parse().ok()?;
+        let total: u64 = caps[2].parse().ok()?;
+        if accepted > total {
+            return None;
+        }
+        Some((accepted, total - accepted))
+    }
+
+    fn extract_difficulty(line: &str) -> Option<f64> {
+        DIFFICULTY_RE.captures(line)?[1].parse().ok()
+    }
+}
+
+impl Default for MetricsParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_line_extracts_hashrate_and_shares_from_cpuminer_output() {
+        let parser = MetricsParser::new();
+        parser.feed_line("[2024-01-01 00:00:00] accepted: 5/6 (83.33%) diff: 1.5");
+        parser.feed_line("CPU: 1.5 kH/s");
+
+        let stats = parser.latest_stats();
+        assert_eq!(stats.accepted, 5);
+        assert_eq!(stats.rejected, 1);
+        assert_eq!(stats.difficulty, 1.5);
+        assert_eq!(stats.hashrate, 1500.0);
+    }
+
+    #[test]
+    fn test_feed_line_extracts_hashrate_from_xmrig_style_output() {
+        let parser = MetricsParser::new();
+        parser.feed_line("[2024-01-01 00:00:00] speed 10s/60s/15m 1.23 MH/s");
+        assert_eq!(parser.latest_stats().hashrate, 1_230_000.0);
+    }
+
+    #[test]
+    fn test_feed_line_tracks_pool_connection_events() {
+        let parser = MetricsParser::new();
+        assert!(!parser.latest_stats().pool_connected);
+
+        parser.feed_line("[2024-01-01 00:00:00] use pool pool.example:3333");
+        assert!(parser.latest_stats().pool_connected);
+
+        parser.feed_line("[2024-01-01 00:00:01] connection to pool.example:3333 failed");
+        assert!(!parser.latest_stats().pool_connected);
+    }
+
+    #[test]
+    fn test_feed_line_ignores_unrecognized_formats() {
+        let parser = MetricsParser::new();
+        parser.feed_line("just some unrelated startup banner text");
+        assert_eq!(parser.latest_stats(), MinerStats::default());
+    }
+
+    #[test]
+    fn test_feed_line_rejects_malformed_accepted_over_total() {
+        let parser = MetricsParser::new();
+        parser.feed_line("accepted: 9/3");
+        assert_eq!(parser.latest_stats().accepted, 0);
+    }
+
+    #[test]
+    fn test_rolling_average_hashrate() {
+        let parser = MetricsParser::new();
+        parser.feed_line("CPU: 1000 H/s");
+        parser.feed_line("CPU: 2000 H/s");
+        parser.feed_line("CPU: 3000 H/s");
+
+        let stats = parser.latest_stats();
+        assert_eq!(stats.hashrate, 3000.0);
+        assert_eq!(stats.avg_hashrate, 2000.0);
+    }
+
+    #[test]
+    fn test_subscribe_is_notified_on_change_but_not_on_ignored_lines() {
+        let parser = MetricsParser::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        parser.subscribe(move |stats| {
+            seen_clone.lock().unwrap().push(stats.hashrate);
+        });
+
+        parser.feed_line("unrelated banner");
+        parser.feed_line("CPU: 500 H/s");
+        parser.feed_line("CPU: 750 H/s");
+
+        assert_eq!(*seen.lock().unwrap(), vec![500.0, 750.0]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Cross-platform OS scheduling priority for spawned miner processes.
+//!
+//! "Background" mode (`MiningConfig::lower_priority`) lets users mine
+//! opportunistically without their machine becoming unusable: the OS
+//! scheduler still gives the mining process CPU time, but yields to
+//! foreground work first.
+
+use tokio::process::Child;
+use tracing::warn;
+
+/// Nice offset applied on Linux/macOS when background mode is on. Positive
+/// values are lower priority; 10 is a mild, non-intrusive background level.
+#[cfg(unix)]
+const BACKGROUND_NICE: i32 = 10;
+
+/// Lower `child`'s OS scheduling priority to background level: a nice
+/// offset on Linux/macOS, `BELOW_NORMAL_PRIORITY_CLASS` on Windows.
+pub fn apply_background_priority(child: &Child) {
+    let Some(pid) = child.id() else {
+        warn!("Cannot lower miner priority: child has no PID (already exited?)");
+        return;
+    };
+
+    #[cfg(unix)]
+    {
+        use nix::sys::resource::{setpriority, Which};
+        use nix::unistd::Pid;
+
+        if let Err(e) = setpriority(Which::Process(Pid::from_raw(pid as i32)), BACKGROUND_NICE) {
+            warn!("Failed to lower miner process priority: {}", e);
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::Foundation::CloseHandle;
+        use windows_sys::Win32::System::Threading::{
+            OpenProcess, SetPriorityClass, BELOW_NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+        };
+
+        unsafe {
+            let handle = OpenProcess(PROCESS_SET_INFORMATION, 0, pid);
+            if handle == 0 {
+                warn!("Failed to open miner process to lower its priority");
+                return;
+            }
+            if SetPriorityClass(handle, BELOW_NORMAL_PRIORITY_CLASS) == 0 {
+                warn!("Failed to set BELOW_NORMAL priority class on miner process");
+            }
+            CloseHandle(handle);
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        warn!("Lowering priority for miner process {} is not supported on this platform", pid);
+    }
+}
@@ -0,0 +1,139 @@
+//! Minimal txtar archive parser for log-parser regression fixtures.
+//!
+//! A txtar archive is zero or more comment lines followed by file entries,
+//! each introduced by a marker line of the form `-- NAME --` (three-byte
+//! `-- ` prefix, ` --` suffix, name whitespace-trimmed). Everything up to
+//! the next marker (or end of input) is that entry's content; a missing
+//! trailing newline on the final entry is treated as present. This lets a
+//! captured miner-output session be stored as one plain-text file that
+//! diffs cleanly in git instead of bespoke Rust per regression case.
+//!
+//! Same idea as Go's `golang.org/x/tools/txtar`, reimplemented minimally
+//! here since this crate has no such dependency.
+
+/// One `-- NAME --`-delimited entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    pub name: String,
+    pub content: String,
+}
+
+/// A parsed archive: the free-form text before the first marker line, then
+/// the named file entries in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Archive {
+    pub comment: String,
+    pub files: Vec<File>,
+}
+
+impl Archive {
+    /// The content of the first entry named `name`, if any.
+    pub fn file(&self, name: &str) -> Option<&str> {
+        self.files.iter().find(|f| f.name == name).map(|f| f.content.as_str())
+    }
+
+    /// `file(name)`'s content split into lines (no trailing empty line for
+    /// the one the format always treats as present).
+    pub fn lines(&self, name: &str) -> Vec<&str> {
+        self.file(name).map(|c| c.lines().collect()).unwrap_or_default()
+    }
+}
+
+fn marker_name(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("-- ")?;
+    let name = rest.strip_suffix(" --")?;
+    Some(name.trim())
+}
+
+/// Parse a full txtar archive from `data`.
+pub fn parse(data: &str) -> Archive {
+    let mut lines = data.lines();
+
+    let mut comment = String::new();
+    let mut current_name: Option<String> = None;
+    for line in lines.by_ref() {
+        if let Some(name) = marker_name(line) {
+            current_name = Some(name.to_string());
+            break;
+        }
+        comment.push_str(line);
+        comment.push('\n');
+    }
+
+    let mut files = Vec::new();
+    let mut current_content = String::new();
+    for line in lines {
+        if let Some(name) = marker_name(line) {
+            if let Some(name) = current_name.take() {
+                files.push(File { name, content: std::mem::take(&mut current_content) });
+            }
+            current_name = Some(name.to_string());
+        } else {
+            current_content.push_str(line);
+            current_content.push('\n');
+        }
+    }
+    if let Some(name) = current_name.take() {
+        files.push(File { name, content: current_content });
+    }
+
+    Archive { comment, files }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_file_with_comment() {
+        let archive = parse(
+            "this is a comment\nspanning two lines\n-- input --\nline one\nline two\n",
+        );
+        assert_eq!(archive.comment, "this is a comment\nspanning two lines\n");
+        assert_eq!(archive.files, vec![File {
+            name: "input".to_string(),
+            content: "line one\nline two\n".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_parse_multiple_files() {
+        let archive = parse("-- input --\nfoo\nbar\n-- expected --\nbaz\n");
+        assert_eq!(archive.file("input"), Some("foo\nbar\n"));
+        assert_eq!(archive.file("expected"), Some("baz\n"));
+        assert_eq!(archive.file("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_treats_missing_trailing_newline_as_present() {
+        let archive = parse("-- input --\nfoo\nbar");
+        assert_eq!(archive.file("input"), Some("foo\nbar\n"));
+    }
+
+    #[test]
+    fn test_parse_marker_name_is_whitespace_trimmed() {
+        let archive = parse("--  spaced name  --\nfoo\n");
+        assert_eq!(archive.file("spaced name"), Some("foo\n"));
+    }
+
+    #[test]
+    fn test_lines_helper_splits_content() {
+        let archive = parse("-- input --\nfoo\nbar\nbaz\n");
+        assert_eq!(archive.lines("input"), vec!["foo", "bar", "baz"]);
+    }
+
+    #[test]
+    fn test_parse_empty_archive_has_no_files() {
+        let archive = parse("");
+        assert_eq!(archive.comment, "");
+        assert!(archive.files.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_lines_that_merely_resemble_a_marker() {
+        // Missing the trailing " --" - not a marker, stays comment/content.
+        let archive = parse("-- not a real marker\n-- input --\nfoo\n");
+        assert_eq!(archive.comment, "-- not a real marker\n");
+        assert_eq!(archive.file("input"), Some("foo\n"));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{AdapterError, Result};
+use crate::{AdapterError, MinerBackend, NormalizedMinerStats, PoolFailoverStatus, PoolFailoverTracker, PoolSwitchEvent, Result};
+use openminedash_pools::parse_pool_url;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::path::PathBuf;
@@ -28,6 +29,84 @@ pub struct MiningConfig {
     pub worker: String,
     pub threads: u32,
     pub preset: PerformancePreset,
+    /// Additional pools to fail over to, in order, after `pool`. Empty
+    /// means failover is disabled.
+    #[serde(default)]
+    pub failover_pools: Vec<String>,
+    /// Run the spawned miner process at reduced OS scheduling priority so
+    /// the machine stays responsive while mining in the background.
+    #[serde(default)]
+    pub lower_priority: bool,
+    /// Pool password, resolved by `resolve_credential` at spawn time so the
+    /// plaintext value never has to live in the persisted config. `None`
+    /// means "no password needed" (most pools). One of:
+    /// - a literal password
+    /// - `env:VAR` - read from the named environment variable
+    /// - `file:/path` - read from a file, trimmed of trailing whitespace
+    #[serde(default)]
+    pub credential: Option<String>,
+}
+
+/// Pool URL schemes `validate_config`/`build_args` understand. `+tls` is
+/// accepted as an alias of `+ssl` and normalized by the caller.
+pub const RECOGNIZED_POOL_SCHEMES: &[&str] =
+    &["stratum+tcp://", "stratum+ssl://", "stratum+tls://"];
+
+/// Validate a `MiningConfig` before it's handed to an adapter's `start`,
+/// modeled on OpenEthereum's `validate_node_url`: reject an unrecognized
+/// scheme, a malformed host or a missing/invalid port on every pool (no DNS
+/// lookup is made), and confirm
+/// `credential` (if set) actually resolves, so a typo'd env var or missing
+/// secrets file fails fast instead of surfacing as an opaque connection
+/// error later.
+pub fn validate_config(config: &MiningConfig) -> Result<()> {
+    validate_pool_url(&config.pool)?;
+    for pool in &config.failover_pools {
+        validate_pool_url(pool)?;
+    }
+    if let Some(credential) = &config.credential {
+        resolve_credential(credential)?;
+    }
+    Ok(())
+}
+
+/// Like `parse_pool_url`, a bare `host:port` (no scheme) is accepted -
+/// only a scheme that's actually present and not one of
+/// `RECOGNIZED_POOL_SCHEMES` is rejected.
+fn validate_pool_url(url: &str) -> Result<()> {
+    if let Some(idx) = url.find("://") {
+        let scheme = &url[..idx + 3];
+        if !RECOGNIZED_POOL_SCHEMES.contains(&scheme) {
+            return Err(AdapterError::InvalidPoolConfig(format!(
+                "unrecognized pool URL scheme '{}' - expected one of {:?}: {}",
+                scheme, RECOGNIZED_POOL_SCHEMES, url
+            )));
+        }
+    }
+    parse_pool_url(url)
+        .map(|_| ())
+        .map_err(|e| AdapterError::InvalidPoolConfig(e.to_string()))
+}
+
+/// Resolve a `MiningConfig::credential` value at spawn time. See the field
+/// doc comment on `MiningConfig::credential` for the supported forms.
+pub fn resolve_credential(raw: &str) -> Result<String> {
+    if let Some(var) = raw.strip_prefix("env:") {
+        return std::env::var(var).map_err(|_| {
+            AdapterError::InvalidPoolConfig(format!("environment variable '{}' is not set", var))
+        });
+    }
+    if let Some(path) = raw.strip_prefix("file:") {
+        return std::fs::read_to_string(path)
+            .map(|s| s.trim_end().to_string())
+            .map_err(|e| {
+                AdapterError::InvalidPoolConfig(format!(
+                    "failed to read credential file '{}': {}",
+                    path, e
+                ))
+            });
+    }
+    Ok(raw.to_string())
 }
 
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
@@ -67,10 +146,12 @@ pub enum MinerState {
     Error,
 }
 
+#[derive(Clone)]
 pub struct XMRigAdapter {
     binary_path: Option<PathBuf>,
     custom_binary_path: Option<PathBuf>,
     state: MinerState,
+    failover: Option<PoolFailoverTracker>,
 }
 
 impl XMRigAdapter {
@@ -79,6 +160,7 @@ impl XMRigAdapter {
             binary_path: None,
             custom_binary_path: None,
             state: MinerState::Stopped,
+            failover: None,
         }
     }
 
@@ -208,12 +290,57 @@ impl XMRigAdapter {
         XMRIG_API_PORT_BASE
     }
 
+    /// Write an XMRig JSON config embedding the full ordered pool list
+    /// (`config.pool` followed by `config.failover_pools`), so XMRig's own
+    /// pool-list parsing and our health-poll rotation agree on pool order.
+    fn write_failover_config(config: &MiningConfig, threads: u32, api_port: u16) -> Result<PathBuf> {
+        let pools: Vec<serde_json::Value> = std::iter::once(config.pool.clone())
+            .chain(config.failover_pools.iter().cloned())
+            .map(|url| {
+                serde_json::json!({
+                    "url": url,
+                    "user": config.wallet,
+                    "pass": config.worker,
+                    "keepalive": true,
+                    "tls": false,
+                })
+            })
+            .collect();
+
+        let json = serde_json::json!({
+            "cpu": {
+                "priority": config.preset.cpu_priority(),
+            },
+            "threads": threads,
+            "http": {
+                "enabled": true,
+                "host": "127.0.0.1",
+                "port": api_port,
+            },
+            "pools": pools,
+        });
+
+        let dir = Self::binary_dir();
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join("xmrig-failover-config.json");
+        std::fs::write(&path, serde_json::to_vec_pretty(&json).unwrap_or_default())?;
+        Ok(path)
+    }
+
     pub async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<Child> {
         if self.state == MinerState::Running || self.state == MinerState::Starting {
             return Err(AdapterError::Process("Miner already running or starting".to_string()));
         }
 
         self.state = MinerState::Starting;
+        self.failover = if config.failover_pools.is_empty() {
+            None
+        } else {
+            let mut pools = vec![config.pool.clone()];
+            pools.extend(config.failover_pools.iter().cloned());
+            Some(PoolFailoverTracker::new(pools))
+        };
+
         let binary = match self.ensure_binary().await {
             Ok(b) => b,
             Err(e) => {
@@ -235,18 +362,36 @@ impl XMRigAdapter {
         };
 
         let mut cmd = Command::new(&binary);
-        cmd.args([
-            "-o", &config.pool,
-            "-u", &config.wallet,
-            "-p", &config.worker,
-            "-t", &threads.to_string(),
-            "--cpu-priority", &config.preset.cpu_priority().to_string(),
-            "--http-enabled",
-            "--http-host", "127.0.0.1",
-            "--http-port", &api_port.to_string(),
-            "--no-color",
-        ])
-        .stdout(Stdio::piped())
+
+        let config_path = if !config.failover_pools.is_empty() {
+            match Self::write_failover_config(config, threads, api_port) {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    warn!("Failed to write failover config, falling back to single pool: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(path) = &config_path {
+            cmd.args(["--config", path.to_str().unwrap_or_default(), "--no-color"]);
+        } else {
+            cmd.args([
+                "-o", &config.pool,
+                "-u", &config.wallet,
+                "-p", &config.worker,
+                "-t", &threads.to_string(),
+                "--cpu-priority", &config.preset.cpu_priority().to_string(),
+                "--http-enabled",
+                "--http-host", "127.0.0.1",
+                "--http-port", &api_port.to_string(),
+                "--no-color",
+            ]);
+        }
+
+        cmd.stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .kill_on_drop(true);
 
@@ -282,6 +427,10 @@ impl XMRigAdapter {
             });
         }
 
+        if config.lower_priority {
+            crate::apply_background_priority(&child);
+        }
+
         self.state = MinerState::Running;
         Ok(child)
     }
@@ -360,6 +509,49 @@ impl XMRigAdapter {
 
         Ok(stats)
     }
+
+    /// Current failover status, if failover pools were configured for this run.
+    pub fn failover_status(&self) -> Option<PoolFailoverStatus> {
+        self.failover.as_ref().map(|f| f.status())
+    }
+
+    /// Poll connection health and rotate to the next pool if the active one
+    /// looks unhealthy. Returns the next pool URL when a rotation occurred;
+    /// the caller is responsible for actually restarting XMRig against it.
+    pub async fn check_failover(&mut self, app_handle: &tauri::AppHandle) -> Option<String> {
+        if self.state != MinerState::Running {
+            return None;
+        }
+
+        let (reachable, uptime) = match self.get_stats().await {
+            Ok(stats) => (true, stats.connection.uptime),
+            Err(_) => (false, 0),
+        };
+        let failover = self.failover.as_mut()?;
+
+        let previous_pool = failover.current_pool().to_string();
+        let previous_index = failover.status().current_index;
+        let next_pool = failover.record_poll(reachable, uptime)?;
+
+        let event = PoolSwitchEvent {
+            previous_pool,
+            next_pool: next_pool.clone(),
+            pool_index: failover.status().current_index,
+            total_pools: failover.status().total_pools,
+            reason: if reachable {
+                "connection uptime kept resetting to zero".to_string()
+            } else {
+                "pool unreachable".to_string()
+            },
+        };
+        info!(
+            "Rotating pool {} -> {} (index {} -> {})",
+            event.previous_pool, event.next_pool, previous_index, event.pool_index
+        );
+        let _ = app_handle.emit_all("pool-switch", &event);
+
+        Some(next_pool)
+    }
 }
 
 impl Default for XMRigAdapter {
@@ -368,6 +560,34 @@ impl Default for XMRigAdapter {
     }
 }
 
+impl MinerBackend for XMRigAdapter {
+    async fn ensure_binary(&mut self) -> Result<PathBuf> {
+        XMRigAdapter::ensure_binary(self).await
+    }
+
+    async fn start(&mut self, config: &MiningConfig, app_handle: tauri::AppHandle) -> Result<Child> {
+        XMRigAdapter::start(self, config, app_handle).await
+    }
+
+    async fn stop(&mut self, child: &mut Child) {
+        XMRigAdapter::stop(self, child).await
+    }
+
+    async fn get_stats(&self) -> Result<NormalizedMinerStats> {
+        let stats = XMRigAdapter::get_stats(self).await?;
+        Ok(NormalizedMinerStats {
+            hashrate: stats.current_hashrate(),
+            accepted_shares: stats.results.shares_good,
+            rejected_shares: stats.results.shares_total.saturating_sub(stats.results.shares_good),
+            uptime_secs: stats.connection.uptime,
+        })
+    }
+
+    fn state(&self) -> MinerState {
+        XMRigAdapter::state(self)
+    }
+}
+
 /// Drop guard ensures process is killed even on panic
 impl Drop for XMRigAdapter {
     fn drop(&mut self) {
@@ -426,3 +646,74 @@ impl XMRigStats {
         self.results.shares_total.saturating_sub(self.results.shares_good)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config(pool: &str) -> MiningConfig {
+        MiningConfig {
+            coin: "randomx".to_string(),
+            pool: pool.to_string(),
+            wallet: "wallet".to_string(),
+            worker: "worker".to_string(),
+            threads: 1,
+            preset: PerformancePreset::default(),
+            failover_pools: Vec::new(),
+            lower_priority: false,
+            credential: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_config_accepts_recognized_schemes_and_bare_host_port() {
+        assert!(validate_config(&base_config("stratum+tcp://pool.example:3333")).is_ok());
+        assert!(validate_config(&base_config("stratum+ssl://pool.example:14433")).is_ok());
+        assert!(validate_config(&base_config("stratum+tls://pool.example:14433")).is_ok());
+        assert!(validate_config(&base_config("pool.example:3333")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_unrecognized_scheme() {
+        assert!(validate_config(&base_config("http://pool.example:3333")).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_missing_port() {
+        assert!(validate_config(&base_config("stratum+tcp://pool.example")).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_checks_failover_pools_too() {
+        let mut config = base_config("stratum+tcp://pool.example:3333");
+        config.failover_pools = vec!["stratum+tcp://backup.example:3333".to_string(), "not a pool".to_string()];
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_resolve_credential_literal_passthrough() {
+        assert_eq!(resolve_credential("hunter2").unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn test_resolve_credential_env_var() {
+        std::env::set_var("OPENMINEDASH_TEST_CREDENTIAL", "from-env");
+        assert_eq!(
+            resolve_credential("env:OPENMINEDASH_TEST_CREDENTIAL").unwrap(),
+            "from-env"
+        );
+        std::env::remove_var("OPENMINEDASH_TEST_CREDENTIAL");
+    }
+
+    #[test]
+    fn test_resolve_credential_missing_env_var_errors() {
+        assert!(resolve_credential("env:OPENMINEDASH_DOES_NOT_EXIST").is_err());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_unresolvable_credential() {
+        let mut config = base_config("stratum+tcp://pool.example:3333");
+        config.credential = Some("env:OPENMINEDASH_DOES_NOT_EXIST".to_string());
+        assert!(validate_config(&config).is_err());
+    }
+}
@@ -121,12 +121,121 @@ pub async fn fetch_nanopool_balance(wallet: &str) -> Result<PoolBalance, String>
     })
 }
 
-/// Generic pool balance fetcher
-pub async fn fetch_pool_balance(pool_host: &str, wallet: &str) -> Result<PoolBalance, String> {
+/// A user-defined pool description, for pool APIs that don't have a
+/// built-in fetcher. The wallet is substituted into `url_template`, and
+/// each field is read out of the response via a JSON pointer
+/// (https://datatracker.ietf.org/doc/html/rfc6901, e.g. "/amtDue").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolTemplate {
+    pub name: String,
+    /// Host to match against, e.g. "pool.example.com" - same value passed
+    /// as `pool_host` to `fetch_pool_balance`.
+    pub host: String,
+    /// URL with a `{wallet}` placeholder, e.g.
+    /// "https://pool.example.com/api/miner/{wallet}/stats"
+    pub url_template: String,
+    pub pending_pointer: String,
+    pub paid_pointer: String,
+    pub hashrate_pointer: Option<String>,
+    /// Divides the raw field value to get whole coin units, e.g. 1e12 for
+    /// piconero.
+    pub divisor: f64,
+    pub min_payout: f64,
+    pub symbol: String,
+}
+
+/// Fetch a pool balance using a user-configured `PoolTemplate`, resolving
+/// each field via its JSON pointer against the parsed response body.
+pub async fn fetch_templated_balance(
+    template: &PoolTemplate,
+    wallet: &str,
+) -> Result<PoolBalance, String> {
+    let url = template.url_template.replace("{wallet}", wallet);
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        return Err(format!("API returned status: {}", resp.status()));
+    }
+
+    let data: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    let pending = data
+        .pointer(&template.pending_pointer)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        / template.divisor;
+    let paid = data
+        .pointer(&template.paid_pointer)
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0)
+        / template.divisor;
+    let hashrate = template
+        .hashrate_pointer
+        .as_ref()
+        .and_then(|pointer| data.pointer(pointer))
+        .and_then(|v| v.as_f64());
+
+    Ok(PoolBalance {
+        pool_name: template.name.clone(),
+        pending_balance: pending,
+        total_paid: paid,
+        min_payout: template.min_payout,
+        symbol: template.symbol.clone(),
+        last_payment: None,
+        hashrate,
+    })
+}
+
+/// Generic pool balance fetcher. Falls back to matching a user-defined
+/// `PoolTemplate` by host when `pool_host` isn't one of the built-ins, so
+/// pools like Herominers, 2Miners, or f2pool work without a code change.
+pub async fn fetch_pool_balance(
+    pool_host: &str,
+    wallet: &str,
+    templates: &[PoolTemplate],
+) -> Result<PoolBalance, String> {
     match pool_host {
         "gulf.moneroocean.stream" => fetch_moneroocean_balance(wallet).await,
         "pool.supportxmr.com" => fetch_supportxmr_balance(wallet).await,
         "xmr.nanopool.org" => fetch_nanopool_balance(wallet).await,
-        _ => Err(format!("Pool API not supported: {}", pool_host)),
+        _ => match templates.iter().find(|t| t.host == pool_host) {
+            Some(template) => fetch_templated_balance(template, wallet).await,
+            None => Err(format!("Pool API not supported: {}", pool_host)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_template_url_substitution() {
+        let template = PoolTemplate {
+            name: "Example Pool".to_string(),
+            host: "pool.example.com".to_string(),
+            url_template: "https://pool.example.com/api/miner/{wallet}/stats".to_string(),
+            pending_pointer: "/amtDue".to_string(),
+            paid_pointer: "/amtPaid".to_string(),
+            hashrate_pointer: Some("/hash".to_string()),
+            divisor: 1e12,
+            min_payout: 0.1,
+            symbol: "XMR".to_string(),
+        };
+
+        assert_eq!(
+            template.url_template.replace("{wallet}", "abc123"),
+            "https://pool.example.com/api/miner/abc123/stats"
+        );
     }
 }
@@ -1,7 +1,9 @@
+use crate::stratum::{connect, parse_pool_url, StratumVersion};
+use crate::sv2::probe_sv2;
 use crate::{PoolError, Result};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 use tokio::time::timeout;
 use tracing::{info, warn};
@@ -22,51 +24,126 @@ pub struct PoolHealthResult {
     pub tls_verified: Option<bool>,
     pub latency_ms: Option<u64>,
     pub error: Option<String>,
+    /// Which stratum generation the URL was parsed as, regardless of
+    /// whether the probe below actually confirmed it.
+    #[serde(default = "default_protocol")]
+    pub protocol: StratumVersion,
+    /// `Some(true)` once the Noise_NX transport handshake completes against
+    /// an SV2 endpoint, `Some(false)` if it was attempted and failed,
+    /// `None` for V1 endpoints (no Noise handshake applies).
+    #[serde(default)]
+    pub sv2_noise_handshake: Option<bool>,
+    /// Flags the pool echoed back in `SetupConnection.Success`, `None` if
+    /// the endpoint isn't SV2 or the setup exchange didn't complete.
+    #[serde(default)]
+    pub sv2_flags: Option<u32>,
+}
+
+fn default_protocol() -> StratumVersion {
+    StratumVersion::V1
 }
 
 /// Comprehensive pool health check with TCP + optional TLS + stratum probe
 pub async fn check_health(url: &str) -> Result<PoolHealthResult> {
-    let (host, port, use_tls) = parse_stratum_url(url)?;
+    let endpoint = parse_pool_url(url)?;
 
-    info!("Checking pool health: {}:{} (TLS: {})", host, port, use_tls);
+    info!("Checking pool health: {}:{} (TLS: {})", endpoint.host, endpoint.port, endpoint.tls);
 
     let start = Instant::now();
 
     // Step 1: TCP connect
-    let stream = match timeout(
-        Duration::from_secs(5),
-        TcpStream::connect((host.as_str(), port)),
-    )
-    .await
-    {
-        Ok(Ok(s)) => s,
-        Ok(Err(e)) => {
+    let stream = match connect(&endpoint).await {
+        Ok(s) => s,
+        Err(PoolError::Timeout) => {
             return Ok(PoolHealthResult {
                 url: url.to_string(),
                 status: PoolStatus::Down,
                 connected: false,
                 tls_verified: None,
                 latency_ms: None,
-                error: Some(format!("TCP connect failed: {}", e)),
+                error: Some("Connection timeout (5s)".to_string()),
+                protocol: endpoint.version,
+                sv2_noise_handshake: None,
+                sv2_flags: None,
             });
         }
-        Err(_) => {
+        Err(e) => {
             return Ok(PoolHealthResult {
                 url: url.to_string(),
                 status: PoolStatus::Down,
                 connected: false,
                 tls_verified: None,
                 latency_ms: None,
-                error: Some("Connection timeout (5s)".to_string()),
+                error: Some(format!("TCP connect failed: {}", e)),
+                protocol: endpoint.version,
+                sv2_noise_handshake: None,
+                sv2_flags: None,
             });
         }
     };
 
     let tcp_latency = start.elapsed().as_millis() as u64;
 
-    // Step 2: TLS handshake if needed
-    let tls_verified = if use_tls {
-        match try_tls_handshake(stream, &host).await {
+    // Step 2: protocol-specific probe - Stratum V2's Noise handshake and
+    // SetupConnection exchange, or the existing V1 TLS/subscribe probe.
+    if endpoint.version == StratumVersion::V2 {
+        return match probe_sv2(stream, &endpoint.host, endpoint.port).await {
+            Ok(probe) if probe.noise_handshake_completed => {
+                let total_latency = start.elapsed().as_millis() as u64;
+                let status = if !probe.setup_connection_success {
+                    PoolStatus::Degraded
+                } else if total_latency > 500 {
+                    PoolStatus::Degraded
+                } else {
+                    PoolStatus::Ok
+                };
+                Ok(PoolHealthResult {
+                    url: url.to_string(),
+                    status,
+                    connected: true,
+                    tls_verified: None,
+                    latency_ms: Some(total_latency),
+                    error: if probe.setup_connection_success {
+                        None
+                    } else {
+                        Some("SV2 SetupConnection was not acknowledged".to_string())
+                    },
+                    protocol: StratumVersion::V2,
+                    sv2_noise_handshake: Some(true),
+                    sv2_flags: probe.flags,
+                })
+            }
+            Ok(_) => Ok(PoolHealthResult {
+                url: url.to_string(),
+                status: PoolStatus::Degraded,
+                connected: true,
+                tls_verified: None,
+                latency_ms: Some(tcp_latency),
+                error: Some("SV2 Noise handshake did not complete".to_string()),
+                protocol: StratumVersion::V2,
+                sv2_noise_handshake: Some(false),
+                sv2_flags: None,
+            }),
+            Err(e) => {
+                warn!("SV2 probe failed: {}", e);
+                Ok(PoolHealthResult {
+                    url: url.to_string(),
+                    status: PoolStatus::Degraded,
+                    connected: true,
+                    tls_verified: None,
+                    latency_ms: Some(tcp_latency),
+                    error: Some(format!("SV2 probe failed: {}", e)),
+                    protocol: StratumVersion::V2,
+                    sv2_noise_handshake: Some(false),
+                    sv2_flags: None,
+                })
+            }
+        };
+    }
+
+    // Step 2 (V1): TLS handshake if needed
+    let tls_verified = if endpoint.tls {
+        match try_tls_handshake(stream, &endpoint.host).await {
             Ok(_) => Some(true),
             Err(e) => {
                 warn!("TLS handshake failed: {}", e);
@@ -77,6 +154,9 @@ pub async fn check_health(url: &str) -> Result<PoolHealthResult> {
                     tls_verified: Some(false),
                     latency_ms: Some(tcp_latency),
                     error: Some(format!("TLS handshake failed: {}", e)),
+                    protocol: StratumVersion::V1,
+                    sv2_noise_handshake: None,
+                    sv2_flags: None,
                 });
             }
         }
@@ -94,6 +174,9 @@ pub async fn check_health(url: &str) -> Result<PoolHealthResult> {
                     tls_verified: None,
                     latency_ms: Some(tcp_latency),
                     error: Some(format!("Stratum probe failed: {}", e)),
+                    protocol: StratumVersion::V1,
+                    sv2_noise_handshake: None,
+                    sv2_flags: None,
                 });
             }
         }
@@ -115,10 +198,170 @@ pub async fn check_health(url: &str) -> Result<PoolHealthResult> {
         tls_verified,
         latency_ms: Some(total_latency),
         error: None,
+        protocol: StratumVersion::V1,
+        sv2_noise_handshake: None,
+        sv2_flags: None,
     })
 }
 
+/// Number of equal-width buckets `check_health_detailed` sorts samples
+/// into - enough to see the shape of the distribution without the UI
+/// needing to render dozens of bars for a handful of samples.
+const LATENCY_HISTOGRAM_BUCKETS: usize = 5;
+
+/// One equal-width slice of the latency distribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    pub range_start_ms: u64,
+    pub range_end_ms: u64,
+    pub count: u32,
+}
+
+/// Latency distribution over a `check_health_detailed` run - a single
+/// `latency_ms` hides jitter, this surfaces it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    pub samples: u32,
+    pub min_ms: u64,
+    pub max_ms: u64,
+    pub mean_ms: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+    pub stddev_ms: f64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// Result of a repeated-probe health check: the last individual probe
+/// (same shape `check_pool_health` already returns) plus the latency
+/// distribution across all of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolHealthDetail {
+    pub last: PoolHealthResult,
+    pub histogram: LatencyHistogram,
+}
+
+/// Run `check_health` `samples` times sequentially and return the latency
+/// distribution alongside the last probe's result. Promotes `last.status`
+/// to `Degraded` when p99 exceeds `p99_ceiling_ms`, even if the median (and
+/// therefore the last individual probe) looked fine - a pool that's
+/// intermittently slow will pass a single ping but show up here.
+pub async fn check_health_detailed(
+    url: &str,
+    samples: u32,
+    p99_ceiling_ms: u64,
+) -> Result<PoolHealthDetail> {
+    let samples = samples.max(1);
+    let mut latencies: Vec<u64> = Vec::with_capacity(samples as usize);
+    let mut last: Option<PoolHealthResult> = None;
+
+    for _ in 0..samples {
+        let result = check_health(url).await?;
+        if let Some(latency) = result.latency_ms {
+            latencies.push(latency);
+        }
+        last = Some(result);
+    }
+
+    let mut last = last.expect("samples is clamped to at least 1");
+    let histogram = build_latency_histogram(&latencies, LATENCY_HISTOGRAM_BUCKETS);
+
+    if histogram.p99_ms > p99_ceiling_ms && last.status == PoolStatus::Ok {
+        last.status = PoolStatus::Degraded;
+        last.error = Some(format!(
+            "p99 latency {}ms over {} samples exceeds {}ms ceiling",
+            histogram.p99_ms, samples, p99_ceiling_ms
+        ));
+    }
+
+    Ok(PoolHealthDetail { last, histogram })
+}
+
+/// Sorts `latencies` and derives min/max/mean/percentiles/stddev plus
+/// `bucket_count` equal-width buckets. `latencies` empty yields an
+/// all-zero histogram with an empty bucket list rather than panicking -
+/// callers always have at least one sample in practice (`check_health`
+/// only fails to report `latency_ms` when the TCP connect itself failed),
+/// but this keeps the math total.
+fn build_latency_histogram(latencies: &[u64], bucket_count: usize) -> LatencyHistogram {
+    if latencies.is_empty() {
+        return LatencyHistogram {
+            samples: 0,
+            min_ms: 0,
+            max_ms: 0,
+            mean_ms: 0.0,
+            p50_ms: 0,
+            p90_ms: 0,
+            p99_ms: 0,
+            stddev_ms: 0.0,
+            buckets: Vec::new(),
+        };
+    }
+
+    let mut sorted = latencies.to_vec();
+    sorted.sort_unstable();
+    let n = sorted.len();
+    let min = sorted[0];
+    let max = sorted[n - 1];
+    let mean = sorted.iter().sum::<u64>() as f64 / n as f64;
+    let variance = sorted.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n as f64;
+    let stddev = variance.sqrt();
+
+    let percentile = |p: f64| -> u64 {
+        let idx = (p * (n - 1) as f64).round() as usize;
+        sorted[idx.min(n - 1)]
+    };
+
+    let bucket_count = bucket_count.max(1);
+    let width = (max - min) as f64 / bucket_count as f64;
+    let mut counts = vec![0u32; bucket_count];
+    for &v in &sorted {
+        let idx = if width > 0.0 {
+            (((v - min) as f64 / width) as usize).min(bucket_count - 1)
+        } else {
+            0
+        };
+        counts[idx] += 1;
+    }
+
+    let buckets = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let range_start_ms = min + (width * i as f64).round() as u64;
+            let range_end_ms = if i == bucket_count - 1 {
+                max
+            } else {
+                min + (width * (i + 1) as f64).round() as u64
+            };
+            LatencyBucket { range_start_ms, range_end_ms, count }
+        })
+        .collect();
+
+    LatencyHistogram {
+        samples: n as u32,
+        min_ms: min,
+        max_ms: max,
+        mean_ms: mean,
+        p50_ms: percentile(0.5),
+        p90_ms: percentile(0.9),
+        p99_ms: percentile(0.99),
+        stddev_ms: stddev,
+        buckets,
+    }
+}
+
 async fn try_tls_handshake(stream: TcpStream, host: &str) -> Result<()> {
+    establish_tls(stream, host).await.map(|_| ())
+}
+
+/// Completes the TLS handshake and hands back the live stream, unlike
+/// `try_tls_handshake` which only reports success/failure - `check_health_full`
+/// needs the encrypted stream itself to keep talking stratum afterwards.
+async fn establish_tls(
+    stream: TcpStream,
+    host: &str,
+) -> Result<tokio_native_tls::TlsStream<TcpStream>> {
     use tokio_native_tls::TlsConnector;
 
     let connector = native_tls::TlsConnector::new()
@@ -128,9 +371,44 @@ async fn try_tls_handshake(stream: TcpStream, host: &str) -> Result<()> {
     timeout(Duration::from_secs(5), connector.connect(host, stream))
         .await
         .map_err(|_| PoolError::Timeout)?
-        .map_err(|e| PoolError::ConnectionFailed(format!("TLS error: {}", e)))?;
+        .map_err(|e| PoolError::ConnectionFailed(format!("TLS error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    #[test]
+    fn test_histogram_percentiles_and_bucket_counts() {
+        let latencies: Vec<u64> = (1..=100).collect();
+        let histogram = build_latency_histogram(&latencies, 5);
+        assert_eq!(histogram.samples, 100);
+        assert_eq!(histogram.min_ms, 1);
+        assert_eq!(histogram.max_ms, 100);
+        // p50 index is round(0.5 * 99) = round(49.5) = 50 -> value 51.
+        assert_eq!(histogram.p50_ms, 51);
+        assert_eq!(histogram.p99_ms, 99);
+        assert_eq!(histogram.buckets.len(), 5);
+        let total: u32 = histogram.buckets.iter().map(|b| b.count).sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn test_histogram_single_sample_has_zero_width_bucket() {
+        let histogram = build_latency_histogram(&[42], 5);
+        assert_eq!(histogram.min_ms, 42);
+        assert_eq!(histogram.max_ms, 42);
+        assert_eq!(histogram.p50_ms, 42);
+        assert_eq!(histogram.stddev_ms, 0.0);
+        assert_eq!(histogram.buckets.iter().map(|b| b.count).sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn test_histogram_empty_input_is_zeroed_not_panicking() {
+        let histogram = build_latency_histogram(&[], 5);
+        assert_eq!(histogram.samples, 0);
+        assert!(histogram.buckets.is_empty());
+    }
 }
 
 async fn try_stratum_probe(mut stream: TcpStream) -> Result<()> {
@@ -153,45 +431,294 @@ async fn try_stratum_probe(mut stream: TcpStream) -> Result<()> {
     }
 }
 
-fn parse_stratum_url(url: &str) -> Result<(String, u16, bool)> {
-    let use_tls = url.contains("+ssl") || url.contains("+tls");
+/// Outcome of a full `mining.subscribe` -> `mining.authorize` -> first-job
+/// stratum session probe. Each stage is reported independently so the
+/// caller can tell a rejected wallet (`subscribed && !authorized`) apart
+/// from a dead job feed (`authorized && !received_job`) rather than
+/// collapsing both into a single "degraded" verdict.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StratumSessionProbe {
+    pub subscribed: bool,
+    pub authorized: bool,
+    pub received_job: bool,
+    pub extranonce1: Option<String>,
+    pub starting_difficulty: Option<f64>,
+    pub time_to_first_job_ms: Option<u64>,
+    pub error: Option<String>,
+}
 
-    let cleaned = url
-        .trim_start_matches("stratum+tcp://")
-        .trim_start_matches("stratum+ssl://")
-        .trim_start_matches("stratum+tls://")
-        .trim_start_matches("stratum://");
+/// Result of `check_health_full`: the same connect/TLS shape as
+/// `PoolHealthResult` plus the session probe that actually authorized
+/// against the pool and waited for a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolHealthFull {
+    pub url: String,
+    pub status: PoolStatus,
+    pub connected: bool,
+    pub tls_verified: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub session: StratumSessionProbe,
+}
 
-    let parts: Vec<&str> = cleaned.split(':').collect();
-    if parts.len() != 2 {
-        return Err(PoolError::InvalidUrl(format!(
-            "Invalid stratum URL format: {}",
-            url
-        )));
+/// Runs the subscribe/authorize/first-job sequence over an already
+/// connected (and, if applicable, already TLS-wrapped) stream. Generic over
+/// `AsyncRead + AsyncWrite` so the same logic serves both plain TCP and
+/// `tokio_native_tls::TlsStream` callers.
+async fn run_session_probe<S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin>(
+    stream: S,
+    wallet: &str,
+    worker: &str,
+    job_timeout: Duration,
+) -> StratumSessionProbe {
+    let mut probe = StratumSessionProbe::default();
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut reader = BufReader::new(read_half);
+
+    let subscribe = serde_json::json!({"id": 1, "method": "mining.subscribe", "params": []});
+    if let Err(e) = send_line(&mut write_half, &subscribe).await {
+        probe.error = Some(format!("subscribe failed: {e}"));
+        return probe;
     }
+    let subscribe_reply = match read_line_within(&mut reader, Duration::from_secs(5)).await {
+        Ok(line) => line,
+        Err(e) => {
+            probe.error = Some(format!("no subscribe reply: {e}"));
+            return probe;
+        }
+    };
+    match parse_subscribe_reply(&subscribe_reply) {
+        Some(extranonce1) => {
+            probe.subscribed = true;
+            probe.extranonce1 = extranonce1;
+        }
+        None => {
+            probe.error = Some("malformed subscribe reply".to_string());
+            return probe;
+        }
+    }
+
+    let authorize = serde_json::json!({
+        "id": 2,
+        "method": "mining.authorize",
+        "params": [format!("{wallet}.{worker}"), "x"],
+    });
+    if let Err(e) = send_line(&mut write_half, &authorize).await {
+        probe.error = Some(format!("authorize failed: {e}"));
+        return probe;
+    }
+    let authorize_reply = match read_line_within(&mut reader, Duration::from_secs(5)).await {
+        Ok(line) => line,
+        Err(e) => {
+            probe.error = Some(format!("no authorize reply: {e}"));
+            return probe;
+        }
+    };
+    match parse_authorize_reply(&authorize_reply) {
+        Some(true) => probe.authorized = true,
+        Some(false) => {
+            probe.error = Some("pool rejected wallet/worker".to_string());
+            return probe;
+        }
+        None => {
+            probe.error = Some("malformed authorize reply".to_string());
+            return probe;
+        }
+    }
+
+    let job_wait_start = Instant::now();
+    loop {
+        if job_wait_start.elapsed() > job_timeout {
+            probe.error = Some("timed out waiting for set_difficulty/notify".to_string());
+            return probe;
+        }
+        let remaining = job_timeout.saturating_sub(job_wait_start.elapsed());
+        let line = match read_line_within(&mut reader, remaining).await {
+            Ok(line) => line,
+            Err(e) => {
+                probe.error = Some(format!("job feed ended early: {e}"));
+                return probe;
+            }
+        };
+        if let Some(difficulty) = parse_set_difficulty(&line) {
+            probe.starting_difficulty = Some(difficulty);
+        } else if is_mining_notify(&line) {
+            probe.received_job = true;
+            probe.time_to_first_job_ms = Some(job_wait_start.elapsed().as_millis() as u64);
+            return probe;
+        }
+    }
+}
+
+async fn send_line<W: tokio::io::AsyncWrite + Unpin>(
+    write_half: &mut W,
+    value: &serde_json::Value,
+) -> Result<()> {
+    let mut line = serde_json::to_string(value)
+        .map_err(|e| PoolError::ConnectionFailed(format!("failed to encode request: {e}")))?;
+    line.push('\n');
+    timeout(Duration::from_secs(3), write_half.write_all(line.as_bytes()))
+        .await
+        .map_err(|_| PoolError::Timeout)?
+        .map_err(PoolError::Io)
+}
+
+async fn read_line_within<R: tokio::io::AsyncRead + Unpin>(
+    reader: &mut BufReader<R>,
+    budget: Duration,
+) -> Result<String> {
+    let mut line = String::new();
+    let n = timeout(budget, reader.read_line(&mut line))
+        .await
+        .map_err(|_| PoolError::Timeout)?
+        .map_err(PoolError::Io)?;
+    if n == 0 {
+        return Err(PoolError::ConnectionFailed("connection closed".to_string()));
+    }
+    Ok(line)
+}
+
+/// Pulls `extranonce1` out of a `mining.subscribe` reply's result array
+/// (`[subscriptions, extranonce1, extranonce2_size]`). Absence of an
+/// extranonce still counts as a successful subscribe - some pools omit it.
+fn parse_subscribe_reply(line: &str) -> Option<Option<String>> {
+    let response: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    let result = response.get("result").filter(|r| !r.is_null())?;
+    let extranonce1 = result.get(1).and_then(|v| v.as_str()).map(|s| s.to_string());
+    Some(extranonce1)
+}
+
+/// `mining.authorize` replies with a boolean `result` - `Some(true/false)`
+/// for a well-formed reply, `None` if the line isn't a stratum response at
+/// all.
+fn parse_authorize_reply(line: &str) -> Option<bool> {
+    let response: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    response.get("result")?.as_bool()
+}
+
+/// `mining.set_difficulty` is a notification (no `id`) carrying the new
+/// difficulty as its sole param.
+fn parse_set_difficulty(line: &str) -> Option<f64> {
+    let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+    if value.get("method")?.as_str()? != "mining.set_difficulty" {
+        return None;
+    }
+    value.get("params")?.get(0)?.as_f64()
+}
+
+fn is_mining_notify(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line.trim())
+        .ok()
+        .and_then(|v| v.get("method").and_then(|m| m.as_str()).map(|m| m == "mining.notify"))
+        .unwrap_or(false)
+}
+
+/// Opt-in, slower sibling of `check_health`: instead of stopping at "did we
+/// get any bytes back", actually authorizes the wallet/worker and waits for
+/// a job, catching pools that accept TCP but reject the wallet, wrong-port
+/// misconfigurations, and dead job feeds that the minimal probe reports as
+/// healthy.
+pub async fn check_health_full(
+    url: &str,
+    wallet: &str,
+    worker: &str,
+    job_timeout_secs: u64,
+) -> Result<PoolHealthFull> {
+    let endpoint = parse_pool_url(url)?;
+    let start = Instant::now();
+    let job_timeout = Duration::from_secs(job_timeout_secs.max(1));
+
+    let stream = match connect(&endpoint).await {
+        Ok(s) => s,
+        Err(e) => {
+            return Ok(PoolHealthFull {
+                url: url.to_string(),
+                status: PoolStatus::Down,
+                connected: false,
+                tls_verified: None,
+                latency_ms: None,
+                session: StratumSessionProbe {
+                    error: Some(format!("TCP connect failed: {}", e)),
+                    ..Default::default()
+                },
+            });
+        }
+    };
 
-    let host = parts[0].to_string();
-    let port = parts[1]
-        .parse::<u16>()
-        .map_err(|_| PoolError::InvalidUrl(format!("Invalid port in URL: {}", url)))?;
+    let (session, tls_verified) = if endpoint.tls {
+        match establish_tls(stream, &endpoint.host).await {
+            Ok(tls_stream) => (
+                run_session_probe(tls_stream, wallet, worker, job_timeout).await,
+                Some(true),
+            ),
+            Err(e) => {
+                return Ok(PoolHealthFull {
+                    url: url.to_string(),
+                    status: PoolStatus::Degraded,
+                    connected: true,
+                    tls_verified: Some(false),
+                    latency_ms: Some(start.elapsed().as_millis() as u64),
+                    session: StratumSessionProbe {
+                        error: Some(format!("TLS handshake failed: {}", e)),
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+    } else {
+        (run_session_probe(stream, wallet, worker, job_timeout).await, None)
+    };
 
-    Ok((host, port, use_tls))
+    let status = if session.received_job {
+        PoolStatus::Ok
+    } else if session.authorized {
+        PoolStatus::Degraded
+    } else {
+        PoolStatus::Down
+    };
+
+    Ok(PoolHealthFull {
+        url: url.to_string(),
+        status,
+        connected: true,
+        tls_verified,
+        latency_ms: Some(start.elapsed().as_millis() as u64),
+        session,
+    })
 }
 
 #[cfg(test)]
-mod tests {
+mod session_probe_tests {
     use super::*;
 
     #[test]
-    fn test_parse_stratum_url() {
-        let (host, port, tls) = parse_stratum_url("stratum+tcp://pool.example.com:3333").unwrap();
-        assert_eq!(host, "pool.example.com");
-        assert_eq!(port, 3333);
-        assert!(!tls);
+    fn test_parse_subscribe_reply_extracts_extranonce1() {
+        let line = r#"{"id":1,"result":[[["mining.notify","ae6812eb"]],"f000000e",4],"error":null}"#;
+        let extranonce1 = parse_subscribe_reply(line).unwrap();
+        assert_eq!(extranonce1.as_deref(), Some("f000000e"));
+    }
 
-        let (host, port, tls) = parse_stratum_url("stratum+ssl://pool.example.com:14433").unwrap();
-        assert_eq!(host, "pool.example.com");
-        assert_eq!(port, 14433);
-        assert!(tls);
+    #[test]
+    fn test_parse_subscribe_reply_rejects_error_only_response() {
+        let line = r#"{"id":1,"result":null,"error":[20,"Unknown method",null]}"#;
+        assert!(parse_subscribe_reply(line).is_none());
+    }
+
+    #[test]
+    fn test_parse_authorize_reply_true_and_false() {
+        assert_eq!(parse_authorize_reply(r#"{"id":2,"result":true,"error":null}"#), Some(true));
+        assert_eq!(parse_authorize_reply(r#"{"id":2,"result":false,"error":null}"#), Some(false));
+    }
+
+    #[test]
+    fn test_parse_set_difficulty_reads_first_param() {
+        let line = r#"{"id":null,"method":"mining.set_difficulty","params":[16384]}"#;
+        assert_eq!(parse_set_difficulty(line), Some(16384.0));
+    }
+
+    #[test]
+    fn test_is_mining_notify_detects_method_only() {
+        assert!(is_mining_notify(r#"{"id":null,"method":"mining.notify","params":[]}"#));
+        assert!(!is_mining_notify(r#"{"id":null,"method":"mining.set_difficulty","params":[]}"#));
     }
 }
+
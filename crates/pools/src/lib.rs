@@ -1,10 +1,14 @@
 mod api;
 mod health;
 mod stratum;
+mod sv2;
+mod wallet;
 
 pub use api::*;
 pub use health::*;
 pub use stratum::*;
+pub use sv2::*;
+pub use wallet::*;
 
 use thiserror::Error;
 
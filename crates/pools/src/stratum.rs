@@ -1,4 +1,101 @@
+use crate::{PoolError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, oneshot, Mutex as AsyncMutex};
+use tokio::time::timeout;
+use tracing::warn;
+
+/// Which stratum generation a `PoolEndpoint` was addressed with - V1
+/// plaintext/binary JSON-RPC-ish framing, or V2's Noise-encrypted binary
+/// protocol. See `crate::sv2` for the V2 handshake/probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StratumVersion {
+    V1,
+    V2,
+}
+
+/// A parsed, validated stratum pool endpoint - host, port, protocol
+/// generation, and whether the connection must be upgraded to TLS.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolEndpoint {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    #[serde(default = "default_stratum_version")]
+    pub version: StratumVersion,
+}
+
+fn default_stratum_version() -> StratumVersion {
+    StratumVersion::V1
+}
+
+/// Parse and validate a pool URL, like OpenEthereum's `validate_node_url`.
+///
+/// Accepts `stratum+tcp://host:port`, `stratum+ssl://host:port`,
+/// `stratum2+tcp://host:port`/`stratum2+ssl://host:port` for Stratum V2,
+/// and bare `host:port`. Rejects missing ports and malformed hosts with
+/// `PoolError::InvalidUrl` up front, instead of waiting for the connection
+/// attempt to fail.
+pub fn parse_pool_url(url: &str) -> Result<PoolEndpoint> {
+    let tls = url.contains("+ssl") || url.contains("+tls");
+    let version = if url.starts_with("stratum2+") || url.starts_with("stratum2://") {
+        StratumVersion::V2
+    } else {
+        StratumVersion::V1
+    };
+
+    let cleaned = url
+        .trim_start_matches("stratum2+tcp://")
+        .trim_start_matches("stratum2+ssl://")
+        .trim_start_matches("stratum2+tls://")
+        .trim_start_matches("stratum2://")
+        .trim_start_matches("stratum+tcp://")
+        .trim_start_matches("stratum+ssl://")
+        .trim_start_matches("stratum+tls://")
+        .trim_start_matches("stratum://");
+
+    let parts: Vec<&str> = cleaned.split(':').collect();
+    if parts.len() != 2 {
+        return Err(PoolError::InvalidUrl(format!(
+            "Expected host:port in pool URL: {}",
+            url
+        )));
+    }
+
+    let host = parts[0];
+    if host.is_empty() || host.contains('/') || host.contains(char::is_whitespace) {
+        return Err(PoolError::InvalidUrl(format!(
+            "Malformed host in pool URL: {}",
+            url
+        )));
+    }
+
+    let port = parts[1]
+        .parse::<u16>()
+        .map_err(|_| PoolError::InvalidUrl(format!("Invalid or missing port in pool URL: {}", url)))?;
+
+    Ok(PoolEndpoint { host: host.to_string(), port, tls, version })
+}
+
+/// Open a TCP connection to a parsed endpoint. TLS upgrade (when
+/// `endpoint.tls` is set) is left to the caller, which already owns the
+/// handshake logic for its use case (see `health::check_health`).
+pub async fn connect(endpoint: &PoolEndpoint) -> Result<TcpStream> {
+    timeout(
+        Duration::from_secs(5),
+        TcpStream::connect((endpoint.host.as_str(), endpoint.port)),
+    )
+    .await
+    .map_err(|_| PoolError::Timeout)?
+    .map_err(PoolError::Io)
+}
 
 /// Stratum protocol message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,3 +130,477 @@ pub fn create_login_request(id: u64, wallet: &str, worker: &str) -> StratumReque
         }),
     }
 }
+
+/// Create a stratum share submission request.
+pub fn create_submit_request(id: u64, session_id: &str, job_id: &str, nonce: &str, result: &str) -> StratumRequest {
+    StratumRequest {
+        id,
+        method: "submit".to_string(),
+        params: serde_json::json!({
+            "id": session_id,
+            "job_id": job_id,
+            "nonce": nonce,
+            "result": result,
+        }),
+    }
+}
+
+/// Base and cap for `StratumClient`'s reconnect backoff: 1s, 2s, 4s, 8s,
+/// 16s, then held at 30s.
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// A mining job pushed by the pool, with difficulty already derived from
+/// its target so callers don't have to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StratumJob {
+    pub job_id: String,
+    pub blob: String,
+    pub target: String,
+    pub height: u64,
+    pub difficulty: f64,
+}
+
+/// Live connection/share state, polled by `stratum_stats`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StratumStats {
+    pub connected: bool,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub difficulty: f64,
+    /// Estimated effective hashrate derived from accepted shares and
+    /// difficulty over session uptime (shares * difficulty * 2^32 / secs),
+    /// the same estimator pools themselves use.
+    pub hashrate: f64,
+    pub reconnect_attempts: u64,
+}
+
+/// Connection lifecycle events, surfaced so a caller (e.g. `SessionManager`)
+/// can raise an `Alert` without this crate depending on `openminedash_core`.
+#[derive(Debug, Clone)]
+pub enum StratumEvent {
+    Connected,
+    Disconnected { reason: String },
+    Reconnecting { attempt: u32, delay: Duration },
+    ReconnectFailed { attempt: u32, error: String },
+}
+
+/// First 4 bytes of `target` (big-endian hex) interpreted as the leading
+/// u32, converted to a difficulty via `0xffffffff / leading`. Malformed or
+/// too-short targets yield `0.0` rather than erroring - a job with a
+/// difficulty we can't parse is still usable for mining, just not for the
+/// stats display.
+fn difficulty_from_target(target: &str) -> f64 {
+    if target.len() < 8 {
+        return 0.0;
+    }
+    match u32::from_str_radix(&target[..8], 16) {
+        Ok(0) | Err(_) => 0.0,
+        Ok(leading) => 0xffffffffu32 as f64 / leading as f64,
+    }
+}
+
+fn parse_job_value(value: &serde_json::Value) -> Option<StratumJob> {
+    let job_id = value.get("job_id")?.as_str()?.to_string();
+    let blob = value.get("blob")?.as_str()?.to_string();
+    let target = value.get("target")?.as_str()?.to_string();
+    let height = value.get("height").and_then(|v| v.as_u64()).unwrap_or(0);
+    let difficulty = difficulty_from_target(&target);
+    Some(StratumJob { job_id, blob, target, height, difficulty })
+}
+
+/// Pulls the session id and (if present) the first job out of a parsed
+/// login response, the same shape used for unsolicited job pushes.
+fn parse_login_result(response: &StratumResponse) -> Result<(String, Option<StratumJob>)> {
+    let result = response.result.as_ref().ok_or_else(|| {
+        PoolError::ConnectionFailed(
+            response
+                .error
+                .as_ref()
+                .map(|e| e.message.clone())
+                .unwrap_or_else(|| "login rejected with no result".to_string()),
+        )
+    })?;
+
+    let session_id = result.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+    let job = result.get("job").and_then(parse_job_value);
+    Ok((session_id, job))
+}
+
+async fn send_request(write: &Arc<AsyncMutex<OwnedWriteHalf>>, request: &StratumRequest) -> Result<()> {
+    let mut line = serde_json::to_string(request)
+        .map_err(|e| PoolError::ConnectionFailed(format!("failed to encode stratum request: {e}")))?;
+    line.push('\n');
+    let mut write = write.lock().await;
+    write.write_all(line.as_bytes()).await?;
+    write.flush().await?;
+    Ok(())
+}
+
+/// Connect, send `create_login_request`, and read back the login response -
+/// shared by the initial connect and every reconnect attempt.
+async fn login(
+    endpoint: &PoolEndpoint,
+    wallet: &str,
+    worker: &str,
+    next_id: &AtomicU64,
+) -> Result<(BufReader<OwnedReadHalf>, OwnedWriteHalf, String, Option<StratumJob>)> {
+    let stream = connect(endpoint).await?;
+    let (read_half, mut write_half) = stream.into_split();
+
+    let login_id = next_id.fetch_add(1, Ordering::Relaxed);
+    let request = create_login_request(login_id, wallet, worker);
+    let mut line = serde_json::to_string(&request)
+        .map_err(|e| PoolError::ConnectionFailed(format!("failed to encode login request: {e}")))?;
+    line.push('\n');
+    write_half.write_all(line.as_bytes()).await?;
+    write_half.flush().await?;
+
+    let mut reader = BufReader::new(read_half);
+    let mut response_line = String::new();
+    reader.read_line(&mut response_line).await?;
+    let response: StratumResponse = serde_json::from_str(response_line.trim())
+        .map_err(|e| PoolError::ConnectionFailed(format!("malformed login response: {e}")))?;
+    let (session_id, job) = parse_login_result(&response)?;
+
+    Ok((reader, write_half, session_id, job))
+}
+
+/// Background task owning the read half: matches responses to pending
+/// submits by id, fans out unsolicited job pushes, and reconnects with
+/// exponential backoff on any read error or clean close.
+#[allow(clippy::too_many_arguments)]
+async fn run_session(
+    mut reader: BufReader<OwnedReadHalf>,
+    write: Arc<AsyncMutex<OwnedWriteHalf>>,
+    pending: Arc<StdMutex<HashMap<u64, oneshot::Sender<StratumResponse>>>>,
+    jobs_tx: broadcast::Sender<StratumJob>,
+    events_tx: broadcast::Sender<StratumEvent>,
+    stats: Arc<StdMutex<StratumStats>>,
+    session_id: Arc<StdMutex<String>>,
+    shutdown: Arc<AtomicBool>,
+    endpoint: PoolEndpoint,
+    wallet: String,
+    worker: String,
+    next_id: Arc<AtomicU64>,
+) {
+    loop {
+        let mut line = String::new();
+        let read_result = reader.read_line(&mut line).await;
+
+        let closed_reason = match read_result {
+            Ok(0) => Some("connection closed by peer".to_string()),
+            Ok(_) => {
+                if let Ok(response) = serde_json::from_str::<StratumResponse>(line.trim()) {
+                    if let Some(tx) = pending.lock().unwrap().remove(&response.id) {
+                        let _ = tx.send(response);
+                    }
+                } else if let Ok(push) = serde_json::from_str::<serde_json::Value>(line.trim()) {
+                    if let Some(job) = push.get("params").and_then(parse_job_value) {
+                        stats.lock().unwrap().difficulty = job.difficulty;
+                        let _ = jobs_tx.send(job);
+                    }
+                }
+                None
+            }
+            Err(e) => Some(e.to_string()),
+        };
+
+        let Some(reason) = closed_reason else { continue };
+        if shutdown.load(Ordering::Relaxed) {
+            return;
+        }
+
+        warn!("Stratum connection to {}:{} lost: {}", endpoint.host, endpoint.port, reason);
+        stats.lock().unwrap().connected = false;
+        let _ = events_tx.send(StratumEvent::Disconnected { reason });
+
+        let mut attempt: u32 = 0;
+        loop {
+            if shutdown.load(Ordering::Relaxed) {
+                return;
+            }
+            attempt += 1;
+            let backoff = 2u32.saturating_pow((attempt - 1).min(5));
+            let delay = (RECONNECT_BASE_DELAY * backoff).min(RECONNECT_MAX_DELAY);
+            let _ = events_tx.send(StratumEvent::Reconnecting { attempt, delay });
+            tokio::time::sleep(delay).await;
+
+            match login(&endpoint, &wallet, &worker, &next_id).await {
+                Ok((new_reader, new_write, sid, initial_job)) => {
+                    *session_id.lock().unwrap() = sid;
+                    *write.lock().await = new_write;
+                    reader = new_reader;
+                    {
+                        let mut s = stats.lock().unwrap();
+                        s.connected = true;
+                        s.reconnect_attempts += attempt as u64;
+                        if let Some(job) = &initial_job {
+                            s.difficulty = job.difficulty;
+                        }
+                    }
+                    if let Some(job) = initial_job {
+                        let _ = jobs_tx.send(job);
+                    }
+                    let _ = events_tx.send(StratumEvent::Connected);
+                    break;
+                }
+                Err(e) => {
+                    let _ = events_tx.send(StratumEvent::ReconnectFailed { attempt, error: e.to_string() });
+                }
+            }
+        }
+    }
+}
+
+/// A live Stratum session: owns the TCP connection, drives login/job/submit
+/// message flow, and reconnects itself on failure. This lets a session mine
+/// directly against a pool instead of shelling out to an external miner
+/// binary for protocol handling.
+pub struct StratumClient {
+    write: Arc<AsyncMutex<OwnedWriteHalf>>,
+    pending: Arc<StdMutex<HashMap<u64, oneshot::Sender<StratumResponse>>>>,
+    next_id: Arc<AtomicU64>,
+    session_id: Arc<StdMutex<String>>,
+    jobs_tx: broadcast::Sender<StratumJob>,
+    events_tx: broadcast::Sender<StratumEvent>,
+    stats: Arc<StdMutex<StratumStats>>,
+    started_at: Instant,
+    shutdown: Arc<AtomicBool>,
+    reader_task: tokio::task::JoinHandle<()>,
+}
+
+impl StratumClient {
+    /// Connect to `endpoint`, log in as `wallet`/`worker`, and spawn the
+    /// background session task. Fails if the initial connection or login
+    /// handshake fails; once established, subsequent failures are handled
+    /// internally via reconnect rather than surfaced as an error.
+    pub async fn connect(endpoint: PoolEndpoint, wallet: String, worker: String) -> Result<Self> {
+        let next_id = Arc::new(AtomicU64::new(1));
+        let (reader, write_half, session_id, initial_job) =
+            login(&endpoint, &wallet, &worker, &next_id).await?;
+
+        let pending = Arc::new(StdMutex::new(HashMap::new()));
+        let (jobs_tx, _) = broadcast::channel(16);
+        let (events_tx, _) = broadcast::channel(16);
+        let difficulty = initial_job.as_ref().map(|j| j.difficulty).unwrap_or(0.0);
+        let stats = Arc::new(StdMutex::new(StratumStats { connected: true, difficulty, ..Default::default() }));
+        let session_id = Arc::new(StdMutex::new(session_id));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let write = Arc::new(AsyncMutex::new(write_half));
+
+        if let Some(job) = initial_job {
+            let _ = jobs_tx.send(job);
+        }
+
+        let reader_task = tokio::spawn(run_session(
+            reader,
+            write.clone(),
+            pending.clone(),
+            jobs_tx.clone(),
+            events_tx.clone(),
+            stats.clone(),
+            session_id.clone(),
+            shutdown.clone(),
+            endpoint,
+            wallet,
+            worker,
+            next_id.clone(),
+        ));
+
+        Ok(Self {
+            write,
+            pending,
+            next_id,
+            session_id,
+            jobs_tx,
+            events_tx,
+            stats,
+            started_at: Instant::now(),
+            shutdown,
+            reader_task,
+        })
+    }
+
+    /// Submit a share and await the pool's response, updating `stats()`
+    /// with the accept/reject outcome and refreshed hashrate estimate.
+    pub async fn submit(&self, job_id: &str, nonce: &str, result: &str) -> Result<StratumResponse> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let session_id = self.session_id.lock().unwrap().clone();
+        let request = create_submit_request(id, &session_id, job_id, nonce, result);
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        if let Err(e) = send_request(&self.write, &request).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(e);
+        }
+
+        let response = timeout(Duration::from_secs(10), rx)
+            .await
+            .map_err(|_| PoolError::Timeout)?
+            .map_err(|_| PoolError::ConnectionFailed("connection closed while awaiting submit response".to_string()))?;
+
+        {
+            let mut stats = self.stats.lock().unwrap();
+            if response.error.is_some() {
+                stats.rejected += 1;
+            } else {
+                stats.accepted += 1;
+            }
+            let elapsed = self.started_at.elapsed().as_secs_f64().max(1.0);
+            stats.hashrate = (stats.accepted as f64 * stats.difficulty * 4_294_967_296.0) / elapsed;
+        }
+
+        Ok(response)
+    }
+
+    /// Subscribe to unsolicited job pushes from the pool.
+    pub fn subscribe_jobs(&self) -> broadcast::Receiver<StratumJob> {
+        self.jobs_tx.subscribe()
+    }
+
+    /// Subscribe to connection lifecycle events (e.g. to drive `Alert`s).
+    pub fn subscribe_events(&self) -> broadcast::Receiver<StratumEvent> {
+        self.events_tx.subscribe()
+    }
+
+    /// Current snapshot of connection/share stats.
+    pub fn stats(&self) -> StratumStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Stop reconnecting and tear down the background session task.
+    pub fn disconnect(self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.reader_task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool_url_schemes() {
+        let endpoint = parse_pool_url("stratum+tcp://pool.example.com:3333").unwrap();
+        assert_eq!(endpoint.host, "pool.example.com");
+        assert_eq!(endpoint.port, 3333);
+        assert!(!endpoint.tls);
+
+        let endpoint = parse_pool_url("stratum+ssl://pool.example.com:14433").unwrap();
+        assert_eq!(endpoint.host, "pool.example.com");
+        assert_eq!(endpoint.port, 14433);
+        assert!(endpoint.tls);
+    }
+
+    #[test]
+    fn test_parse_pool_url_sv2_schemes() {
+        let endpoint = parse_pool_url("stratum2+tcp://pool.example.com:34333").unwrap();
+        assert_eq!(endpoint.host, "pool.example.com");
+        assert_eq!(endpoint.port, 34333);
+        assert!(!endpoint.tls);
+        assert_eq!(endpoint.version, StratumVersion::V2);
+
+        let endpoint = parse_pool_url("stratum+tcp://pool.example.com:3333").unwrap();
+        assert_eq!(endpoint.version, StratumVersion::V1);
+    }
+
+    #[test]
+    fn test_parse_pool_url_bare_host_port() {
+        let endpoint = parse_pool_url("pool.example.com:3333").unwrap();
+        assert_eq!(endpoint.host, "pool.example.com");
+        assert_eq!(endpoint.port, 3333);
+        assert!(!endpoint.tls);
+    }
+
+    #[test]
+    fn test_parse_pool_url_rejects_missing_port() {
+        assert!(parse_pool_url("stratum+tcp://pool.example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_pool_url_rejects_malformed_host() {
+        assert!(parse_pool_url("stratum+tcp:// :3333").is_err());
+        assert!(parse_pool_url("stratum+tcp://pool.example.com/path:3333").is_err());
+    }
+
+    #[test]
+    fn test_create_submit_request_shape() {
+        let request = create_submit_request(5, "session-1", "job-1", "deadbeef", "cafebabe");
+        assert_eq!(request.id, 5);
+        assert_eq!(request.method, "submit");
+        assert_eq!(request.params["id"], "session-1");
+        assert_eq!(request.params["job_id"], "job-1");
+        assert_eq!(request.params["nonce"], "deadbeef");
+        assert_eq!(request.params["result"], "cafebabe");
+    }
+
+    #[test]
+    fn test_difficulty_from_target_max_target_is_difficulty_one() {
+        assert_eq!(difficulty_from_target("ffffffff"), 1.0);
+    }
+
+    #[test]
+    fn test_difficulty_from_target_minimum_leading_value_is_max_difficulty() {
+        assert_eq!(difficulty_from_target("00000001"), 0xffffffffu32 as f64);
+    }
+
+    #[test]
+    fn test_difficulty_from_target_rejects_short_input() {
+        assert_eq!(difficulty_from_target("abcd"), 0.0);
+    }
+
+    #[test]
+    fn test_parse_job_value_extracts_fields() {
+        let value = serde_json::json!({
+            "job_id": "job-1",
+            "blob": "0100",
+            "target": "ffffffff",
+            "height": 123,
+        });
+        let job = parse_job_value(&value).expect("job should parse");
+        assert_eq!(job.job_id, "job-1");
+        assert_eq!(job.blob, "0100");
+        assert_eq!(job.height, 123);
+        assert_eq!(job.difficulty, 1.0);
+    }
+
+    #[test]
+    fn test_parse_job_value_rejects_missing_fields() {
+        let value = serde_json::json!({"job_id": "job-1"});
+        assert!(parse_job_value(&value).is_none());
+    }
+
+    #[test]
+    fn test_parse_login_result_extracts_session_and_job() {
+        let response = StratumResponse {
+            id: 1,
+            result: Some(serde_json::json!({
+                "id": "session-1",
+                "job": {
+                    "job_id": "job-1",
+                    "blob": "0100",
+                    "target": "ffffffff",
+                    "height": 7,
+                },
+            })),
+            error: None,
+        };
+        let (session_id, job) = parse_login_result(&response).expect("should parse");
+        assert_eq!(session_id, "session-1");
+        assert_eq!(job.unwrap().job_id, "job-1");
+    }
+
+    #[test]
+    fn test_parse_login_result_surfaces_pool_error_message() {
+        let response = StratumResponse {
+            id: 1,
+            result: None,
+            error: Some(StratumError { code: -1, message: "Invalid wallet address".to_string() }),
+        };
+        let err = parse_login_result(&response).unwrap_err();
+        assert!(matches!(err, PoolError::ConnectionFailed(ref m) if m == "Invalid wallet address"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Stratum V2 transport probe: the Noise_NX handshake plus the initial
+//! `SetupConnection` exchange, just enough for `health::check_health` to
+//! tell whether a pool actually speaks SV2 rather than assuming plaintext
+//! V1 everywhere. This is a health-check probe, not a mining session - see
+//! `StratumClient` for the level of protocol support a real V1 session
+//! needs.
+//!
+//! Reference: the Stratum V2 spec (stratumprotocol.org) specifies
+//! `Noise_NX_25519_ChaChaPoly_SHA256` for the transport handshake and a
+//! small binary frame format (`extension_type: u16`, `msg_type: u8`,
+//! `msg_length: u24`, `payload`) for protocol messages layered on top of
+//! the encrypted transport.
+
+use crate::{PoolError, Result};
+use snow::Builder as NoiseBuilder;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// SV2's handshake pattern: NX lets the pool (responder) present its
+/// static key without the initiator needing one of its own, appropriate
+/// for a probe that isn't a provisioned client with its own certificate.
+const NOISE_PARAMS: &str = "Noise_NX_25519_ChaChaPoly_SHA256";
+
+const SV2_MSG_SETUP_CONNECTION: u8 = 0x00;
+const SV2_MSG_SETUP_CONNECTION_SUCCESS: u8 = 0x01;
+/// `Protocol::MiningProtocol` per the SV2 spec's protocol discriminant.
+const SV2_PROTOCOL_MINING: u8 = 0x00;
+/// Min/max `SetupConnection` protocol version we're willing to speak.
+const SV2_VERSION: u16 = 2;
+
+const NOISE_FRAME_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Outcome of an SV2 probe: whether the Noise transport came up and, if the
+/// follow-on `SetupConnection` exchange also succeeded, the flags the pool
+/// echoed back in `SetupConnection.Success`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sv2ProbeResult {
+    pub noise_handshake_completed: bool,
+    pub setup_connection_success: bool,
+    pub flags: Option<u32>,
+}
+
+/// Perform the SV2 Noise handshake and `SetupConnection` probe against an
+/// already-connected TCP stream. Never surfaces protocol-level rejection as
+/// an error - a pool that isn't SV2, or that rejects the setup, just comes
+/// back with the relevant fields left `false`/`None` so the caller can
+/// report "not SV2" rather than treating the whole health check as down.
+pub async fn probe_sv2(mut stream: TcpStream, host: &str, port: u16) -> Result<Sv2ProbeResult> {
+    let mut result = Sv2ProbeResult::default();
+
+    let mut initiator = match NoiseBuilder::new(NOISE_PARAMS.parse().map_err(|e| {
+        PoolError::ConnectionFailed(format!("invalid noise pattern: {e}"))
+    })?)
+    .build_initiator()
+    {
+        Ok(initiator) => initiator,
+        Err(e) => return Err(PoolError::ConnectionFailed(format!("failed to build noise initiator: {e}"))),
+    };
+
+    // -> e
+    let mut buf = [0u8; 256];
+    let Ok(len) = initiator.write_message(&[], &mut buf) else {
+        return Ok(result);
+    };
+    if send_frame(&mut stream, &buf[..len]).await.is_err() {
+        return Ok(result);
+    }
+
+    // <- e, ee, s, es
+    let Some(response) = recv_frame(&mut stream).await? else {
+        return Ok(result);
+    };
+    let mut payload = [0u8; 256];
+    if initiator.read_message(&response, &mut payload).is_err() {
+        return Ok(result);
+    }
+
+    // NX ends here: no third message, as we have no static key to present.
+    let Ok(mut transport) = initiator.into_transport_mode() else {
+        return Ok(result);
+    };
+    result.noise_handshake_completed = true;
+
+    let setup = encode_setup_connection(host, port);
+    let mut ciphertext = vec![0u8; setup.len() + 16];
+    let Ok(len) = transport.write_message(&setup, &mut ciphertext) else {
+        return Ok(result);
+    };
+    if send_frame(&mut stream, &ciphertext[..len]).await.is_err() {
+        return Ok(result);
+    }
+
+    let Some(response) = recv_frame(&mut stream).await? else {
+        return Ok(result);
+    };
+    let mut plaintext = vec![0u8; response.len()];
+    let Ok(len) = transport.read_message(&response, &mut plaintext) else {
+        return Ok(result);
+    };
+    let Some((msg_type, msg_payload)) = decode_sv2_frame(&plaintext[..len]) else {
+        return Ok(result);
+    };
+
+    if msg_type == SV2_MSG_SETUP_CONNECTION_SUCCESS {
+        result.setup_connection_success = true;
+        result.flags = msg_payload
+            .get(2..6)
+            .and_then(|b| b.try_into().ok())
+            .map(u32::from_le_bytes);
+    }
+
+    Ok(result)
+}
+
+/// Wire framing used only during the handshake: a 2-byte little-endian
+/// length prefix followed by the raw Noise message. The post-handshake SV2
+/// message frame (`extension_type`/`msg_type`/`msg_length`) is distinct and
+/// only appears once the transport cipher is in place - see
+/// `encode_sv2_frame`/`decode_sv2_frame`.
+async fn send_frame(stream: &mut TcpStream, payload: &[u8]) -> Result<()> {
+    let len = payload.len() as u16;
+    timeout(NOISE_FRAME_TIMEOUT, async {
+        stream.write_all(&len.to_le_bytes()).await?;
+        stream.write_all(payload).await
+    })
+    .await
+    .map_err(|_| PoolError::Timeout)?
+    .map_err(PoolError::Io)
+}
+
+/// Reads one length-prefixed frame. Returns `Ok(None)` (rather than an
+/// error) on timeout or a closed connection, since both just mean "this
+/// pool isn't speaking SV2 here" to the caller.
+async fn recv_frame(stream: &mut TcpStream) -> Result<Option<Vec<u8>>> {
+    let mut len_buf = [0u8; 2];
+    match timeout(NOISE_FRAME_TIMEOUT, stream.read_exact(&mut len_buf)).await {
+        Ok(Ok(())) => {}
+        _ => return Ok(None),
+    }
+    let len = u16::from_le_bytes(len_buf) as usize;
+    let mut buf = vec![0u8; len];
+    match timeout(NOISE_FRAME_TIMEOUT, stream.read_exact(&mut buf)).await {
+        Ok(Ok(())) => Ok(Some(buf)),
+        _ => Ok(None),
+    }
+}
+
+/// Build a minimal `SetupConnection` message: the SV2 frame header followed
+/// by the fixed fields every implementation must send, with `SV2_VERSION`
+/// as both min and max version and no extension flags - this is a probe, not a
+/// provisioned mining client.
+fn encode_setup_connection(host: &str, port: u16) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.push(SV2_PROTOCOL_MINING);
+    payload.extend_from_slice(&SV2_VERSION.to_le_bytes()); // min_version
+    payload.extend_from_slice(&SV2_VERSION.to_le_bytes()); // max_version
+    payload.extend_from_slice(&0u32.to_le_bytes()); // flags
+    write_str0_255(&mut payload, host);
+    payload.extend_from_slice(&port.to_le_bytes());
+    write_str0_255(&mut payload, "OpenMineDash");
+    write_str0_255(&mut payload, "cpu");
+    write_str0_255(&mut payload, env!("CARGO_PKG_VERSION"));
+    write_str0_255(&mut payload, "health-check-probe");
+
+    encode_sv2_frame(SV2_MSG_SETUP_CONNECTION, &payload)
+}
+
+fn write_str0_255(buf: &mut Vec<u8>, s: &str) {
+    let truncated = &s.as_bytes()[..s.len().min(255)];
+    buf.push(truncated.len() as u8);
+    buf.extend_from_slice(truncated);
+}
+
+fn encode_sv2_frame(msg_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(6 + payload.len());
+    frame.extend_from_slice(&0u16.to_le_bytes()); // extension_type
+    frame.push(msg_type);
+    let len_bytes = (payload.len() as u32).to_le_bytes();
+    frame.extend_from_slice(&len_bytes[..3]); // msg_length, 24-bit little-endian
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn decode_sv2_frame(data: &[u8]) -> Option<(u8, &[u8])> {
+    if data.len() < 6 {
+        return None;
+    }
+    let msg_type = data[2];
+    let msg_length = u32::from_le_bytes([data[3], data[4], data[5], 0]) as usize;
+    let payload = data.get(6..6 + msg_length)?;
+    Some((msg_type, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_sv2_frame_roundtrip() {
+        let frame = encode_sv2_frame(SV2_MSG_SETUP_CONNECTION, &[1, 2, 3]);
+        let (msg_type, payload) = decode_sv2_frame(&frame).unwrap();
+        assert_eq!(msg_type, SV2_MSG_SETUP_CONNECTION);
+        assert_eq!(payload, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_decode_sv2_frame_rejects_short_input() {
+        assert!(decode_sv2_frame(&[0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn test_write_str0_255_length_prefixes() {
+        let mut buf = Vec::new();
+        write_str0_255(&mut buf, "abc");
+        assert_eq!(buf, vec![3, b'a', b'b', b'c']);
+    }
+
+    #[test]
+    fn test_encode_setup_connection_starts_with_mining_protocol() {
+        let frame = encode_setup_connection("pool.example.com", 34333);
+        let (msg_type, payload) = decode_sv2_frame(&frame).unwrap();
+        assert_eq!(msg_type, SV2_MSG_SETUP_CONNECTION);
+        assert_eq!(payload[0], SV2_PROTOCOL_MINING);
+    }
+}
@@ -0,0 +1,198 @@
+//! Monero wallet-rpc integration.
+//!
+//! Talks to a locally running `monero-wallet-rpc` daemon to read the actual
+//! on-chain balance, so it can be cross-referenced against what a pool
+//! reports as paid out. Read-only: only `get_balance` and `get_transfers`
+//! are called.
+
+use crate::PoolBalance;
+use serde::{Deserialize, Serialize};
+
+/// Monero's atomic unit (piconero) divisor, same as the pool APIs use.
+const PICONERO_DIVISOR: f64 = 1e12;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletRpcConfig {
+    pub url: String,
+    pub rpc_user: Option<String>,
+    pub rpc_password: Option<String>,
+}
+
+impl Default for WalletRpcConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:18082".to_string(),
+            rpc_user: None,
+            rpc_password: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncomingTransfer {
+    pub amount: f64,
+    pub height: u64,
+    pub timestamp: u64,
+    pub tx_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBalance {
+    pub confirmed_balance: f64,
+    pub unlocked_balance: f64,
+    pub transfers: Vec<IncomingTransfer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationResult {
+    pub pool_total_paid: f64,
+    pub wallet_incoming_total: f64,
+    pub discrepancy: f64,
+    pub matches: bool,
+}
+
+async fn wallet_rpc_call(
+    config: &WalletRpcConfig,
+    method: &str,
+    params: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let client = reqwest::Client::new();
+    let mut request = client
+        .post(format!("{}/json_rpc", config.url))
+        .timeout(std::time::Duration::from_secs(10))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "0",
+            "method": method,
+            "params": params,
+        }));
+
+    if let Some(user) = &config.rpc_user {
+        request = request.basic_auth(user, config.rpc_password.clone());
+    }
+
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| format!("wallet-rpc request failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        return Err(format!("wallet-rpc returned status: {}", resp.status()));
+    }
+
+    let body: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse wallet-rpc response: {}", e))?;
+
+    if let Some(error) = body.get("error") {
+        return Err(format!("wallet-rpc error: {}", error));
+    }
+
+    Ok(body["result"].clone())
+}
+
+/// Fetch confirmed/unlocked balance and recent incoming transfers from
+/// `monero-wallet-rpc`.
+pub async fn fetch_wallet_balance(config: &WalletRpcConfig) -> Result<WalletBalance, String> {
+    let balance_result = wallet_rpc_call(config, "get_balance", serde_json::json!({})).await?;
+
+    let confirmed_balance = balance_result["balance"].as_f64().unwrap_or(0.0) / PICONERO_DIVISOR;
+    let unlocked_balance = balance_result["unlocked_balance"].as_f64().unwrap_or(0.0) / PICONERO_DIVISOR;
+
+    let transfers_result =
+        wallet_rpc_call(config, "get_transfers", serde_json::json!({ "in": true })).await?;
+
+    let transfers = transfers_result["in"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|t| IncomingTransfer {
+                    amount: t["amount"].as_f64().unwrap_or(0.0) / PICONERO_DIVISOR,
+                    height: t["height"].as_u64().unwrap_or(0),
+                    timestamp: t["timestamp"].as_u64().unwrap_or(0),
+                    tx_hash: t["txid"].as_str().unwrap_or("").to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(WalletBalance {
+        confirmed_balance,
+        unlocked_balance,
+        transfers,
+    })
+}
+
+/// Cross-reference a pool's reported `total_paid` against the wallet's
+/// incoming on-chain transfers. A small epsilon accounts for rounding at the
+/// piconero level across different sources.
+pub fn reconcile_balance(pool: &PoolBalance, wallet: &WalletBalance) -> ReconciliationResult {
+    const EPSILON: f64 = 1e-6;
+
+    let wallet_incoming_total: f64 = wallet.transfers.iter().map(|t| t.amount).sum();
+    let discrepancy = pool.total_paid - wallet_incoming_total;
+
+    ReconciliationResult {
+        pool_total_paid: pool.total_paid,
+        wallet_incoming_total,
+        discrepancy,
+        matches: discrepancy.abs() < EPSILON,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_pool_balance(total_paid: f64) -> PoolBalance {
+        PoolBalance {
+            pool_name: "SupportXMR".to_string(),
+            pending_balance: 0.0,
+            total_paid,
+            min_payout: 0.1,
+            symbol: "XMR".to_string(),
+            last_payment: None,
+            hashrate: None,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_balance_matches() {
+        let pool = sample_pool_balance(1.5);
+        let wallet = WalletBalance {
+            confirmed_balance: 1.5,
+            unlocked_balance: 1.5,
+            transfers: vec![IncomingTransfer {
+                amount: 1.5,
+                height: 100,
+                timestamp: 0,
+                tx_hash: "abc".to_string(),
+            }],
+        };
+
+        let result = reconcile_balance(&pool, &wallet);
+        assert!(result.matches);
+        assert_eq!(result.discrepancy, 0.0);
+    }
+
+    #[test]
+    fn test_reconcile_balance_discrepancy() {
+        let pool = sample_pool_balance(2.0);
+        let wallet = WalletBalance {
+            confirmed_balance: 1.5,
+            unlocked_balance: 1.5,
+            transfers: vec![IncomingTransfer {
+                amount: 1.5,
+                height: 100,
+                timestamp: 0,
+                tx_hash: "abc".to_string(),
+            }],
+        };
+
+        let result = reconcile_balance(&pool, &wallet);
+        assert!(!result.matches);
+        assert!((result.discrepancy - 0.5).abs() < 1e-9);
+    }
+}